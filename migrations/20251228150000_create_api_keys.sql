@@ -0,0 +1,19 @@
+-- Long-lived API keys for scripts and partner integrations
+-- Only the SHA-256 hash of a key is stored; key_prefix identifies it in listings.
+CREATE TABLE IF NOT EXISTS api_keys (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    key_prefix VARCHAR(16) NOT NULL,
+    key_hash VARCHAR(64) NOT NULL UNIQUE,
+    scopes TEXT[] NOT NULL DEFAULT '{}',
+    daily_quota INTEGER,             -- NULL = unlimited
+    mfa_verified BOOLEAN NOT NULL DEFAULT FALSE, -- created from a session that passed 2FA
+    usage_count BIGINT NOT NULL DEFAULT 0,
+    last_used_at TIMESTAMPTZ,
+    expires_at TIMESTAMPTZ,          -- NULL = never expires
+    revoked_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_api_keys_user ON api_keys(user_id);
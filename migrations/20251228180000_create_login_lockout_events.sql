@@ -0,0 +1,17 @@
+-- History of login lockouts, so admins can see attacks against specific accounts
+CREATE TABLE IF NOT EXISTS login_lockout_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    event VARCHAR(20) NOT NULL,       -- LOCKED, UNLOCKED
+    scope VARCHAR(10) NOT NULL,       -- EMAIL, IP
+    email VARCHAR(255),
+    ip_address VARCHAR(64),
+    user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    failed_attempts INTEGER NOT NULL DEFAULT 0,
+    locked_until TIMESTAMPTZ,
+    reason VARCHAR(100),              -- for UNLOCKED: admin, password_reset, ...
+    actor_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_login_lockout_events_email ON login_lockout_events(email, created_at DESC);
+CREATE INDEX idx_login_lockout_events_created ON login_lockout_events(created_at DESC);
@@ -0,0 +1,45 @@
+-- Roles and permissions stored in the database instead of the two-value user_role enum
+
+CREATE TABLE IF NOT EXISTS roles (
+    name VARCHAR(50) PRIMARY KEY,
+    description VARCHAR(255),
+    is_system BOOLEAN NOT NULL DEFAULT FALSE, -- built-in roles cannot be deleted
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS permissions (
+    name VARCHAR(100) PRIMARY KEY,
+    description VARCHAR(255) NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS role_permissions (
+    role_name VARCHAR(50) NOT NULL REFERENCES roles(name) ON DELETE CASCADE ON UPDATE CASCADE,
+    permission VARCHAR(100) NOT NULL REFERENCES permissions(name) ON DELETE CASCADE,
+    PRIMARY KEY (role_name, permission)
+);
+
+INSERT INTO permissions (name, description) VALUES
+    ('content.write', 'Create and update movies, series, seasons and episodes, upload media'),
+    ('content.delete', 'Delete movies, series, seasons and episodes'),
+    ('genre.manage', 'Create, update and delete genres'),
+    ('users.manage', 'Manage users, roles and login lockouts');
+
+INSERT INTO roles (name, description, is_system) VALUES
+    ('ADMIN', 'Full access', TRUE),
+    ('USER', 'Regular viewer', TRUE),
+    ('EDITOR', 'Uploads and edits content, cannot delete it', FALSE);
+
+INSERT INTO role_permissions (role_name, permission)
+SELECT 'ADMIN', name FROM permissions;
+
+INSERT INTO role_permissions (role_name, permission) VALUES
+    ('EDITOR', 'content.write');
+
+-- users.role becomes a reference to roles
+ALTER TABLE users ALTER COLUMN role DROP DEFAULT;
+ALTER TABLE users ALTER COLUMN role TYPE VARCHAR(50) USING role::TEXT;
+ALTER TABLE users ALTER COLUMN role SET DEFAULT 'USER';
+ALTER TABLE users ADD CONSTRAINT fk_users_role FOREIGN KEY (role) REFERENCES roles(name) ON UPDATE CASCADE;
+
+DROP TYPE user_role;
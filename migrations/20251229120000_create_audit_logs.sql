@@ -0,0 +1,35 @@
+-- Append-only record of who changed what through the admin API
+
+CREATE TABLE IF NOT EXISTS audit_logs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    actor_id UUID, -- no FK: entries must outlive deleted users
+    actor_role VARCHAR(50),
+    action VARCHAR(100) NOT NULL, -- e.g. movie.delete
+    target_type VARCHAR(50) NOT NULL,
+    target_id VARCHAR(255),
+    before JSONB, -- changed fields only for updates, full row for deletes
+    after JSONB,
+    request_id VARCHAR(100),
+    ip_address VARCHAR(45),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_audit_logs_created_at ON audit_logs(created_at DESC);
+CREATE INDEX idx_audit_logs_actor ON audit_logs(actor_id, created_at DESC);
+CREATE INDEX idx_audit_logs_target ON audit_logs(target_type, target_id, created_at DESC);
+
+CREATE OR REPLACE FUNCTION audit_logs_append_only() RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_logs is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_audit_logs_append_only
+    BEFORE UPDATE OR DELETE ON audit_logs
+    FOR EACH ROW EXECUTE FUNCTION audit_logs_append_only();
+
+INSERT INTO permissions (name, description) VALUES
+    ('audit.read', 'Read the audit log');
+
+INSERT INTO role_permissions (role_name, permission) VALUES
+    ('ADMIN', 'audit.read');
@@ -0,0 +1,13 @@
+-- Self-service profile: avatar and playback preferences
+
+ALTER TABLE users ADD COLUMN avatar_url VARCHAR(255); -- object key in the thumbnails bucket
+
+CREATE TABLE IF NOT EXISTS user_preferences (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    preferred_audio_language VARCHAR(10),
+    preferred_subtitle_language VARCHAR(10),
+    subtitles_enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    autoplay_next_episode BOOLEAN NOT NULL DEFAULT TRUE,
+    autoplay_previews BOOLEAN NOT NULL DEFAULT TRUE,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,7 @@
+-- Admin suspension of accounts
+
+ALTER TABLE users ADD COLUMN suspended_at TIMESTAMPTZ;
+ALTER TABLE users ADD COLUMN suspended_until TIMESTAMPTZ; -- NULL while suspended means indefinitely
+ALTER TABLE users ADD COLUMN suspension_reason VARCHAR(500);
+
+CREATE INDEX idx_users_suspended_at ON users(suspended_at) WHERE suspended_at IS NOT NULL;
@@ -0,0 +1,33 @@
+-- Household profiles: several viewers share one account
+
+CREATE TABLE IF NOT EXISTS profiles (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(50) NOT NULL,
+    avatar VARCHAR(100), -- id of a built-in avatar picked by the client
+    is_kids BOOLEAN NOT NULL DEFAULT FALSE,
+    pin_hash VARCHAR(255),
+    is_default BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, name)
+);
+
+-- Exactly one default profile per account, it cannot be deleted
+CREATE UNIQUE INDEX idx_profiles_default ON profiles(user_id) WHERE is_default;
+
+INSERT INTO profiles (user_id, name, is_default)
+SELECT id, LEFT(username, 50), TRUE FROM users;
+
+-- Playback preferences belong to the viewer, not the login
+ALTER TABLE user_preferences ADD COLUMN profile_id UUID REFERENCES profiles(id) ON DELETE CASCADE;
+
+UPDATE user_preferences p
+SET profile_id = pr.id
+FROM profiles pr
+WHERE pr.user_id = p.user_id AND pr.is_default;
+
+ALTER TABLE user_preferences DROP CONSTRAINT user_preferences_pkey;
+ALTER TABLE user_preferences ALTER COLUMN profile_id SET NOT NULL;
+ALTER TABLE user_preferences ADD PRIMARY KEY (profile_id);
+CREATE INDEX idx_user_preferences_user ON user_preferences(user_id);
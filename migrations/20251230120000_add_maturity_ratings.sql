@@ -0,0 +1,36 @@
+-- Maturity ratings per title and parental limits per viewer profile
+
+CREATE TABLE IF NOT EXISTS maturity_ratings (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    system VARCHAR(20) NOT NULL, -- e.g. MPAA, AGE
+    code VARCHAR(20) NOT NULL, -- e.g. PG-13, 16
+    -- Minimum viewer age; ratings of every system are compared on this scale
+    level INT NOT NULL CHECK (level BETWEEN 0 AND 100),
+    description VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (system, code)
+);
+
+INSERT INTO maturity_ratings (system, code, level, description) VALUES
+    ('MPAA', 'G', 0, 'General audiences'),
+    ('MPAA', 'PG', 8, 'Parental guidance suggested'),
+    ('MPAA', 'PG-13', 13, 'Parents strongly cautioned'),
+    ('MPAA', 'R', 17, 'Restricted'),
+    ('MPAA', 'NC-17', 18, 'Adults only'),
+    ('AGE', '0', 0, 'All ages'),
+    ('AGE', '7', 7, '7 and older'),
+    ('AGE', '13', 13, '13 and older'),
+    ('AGE', '16', 16, '16 and older'),
+    ('AGE', '18', 18, 'Adults only');
+
+ALTER TABLE movies
+    ADD COLUMN maturity_rating_id UUID REFERENCES maturity_ratings(id),
+    ADD COLUMN content_advisories TEXT[] NOT NULL DEFAULT '{}'; -- e.g. violence, language
+
+ALTER TABLE series
+    ADD COLUMN maturity_rating_id UUID REFERENCES maturity_ratings(id),
+    ADD COLUMN content_advisories TEXT[] NOT NULL DEFAULT '{}';
+
+-- NULL is unrestricted; restricted profiles do not see unrated titles
+ALTER TABLE profiles ADD COLUMN max_maturity_level INT CHECK (max_maturity_level BETWEEN 0 AND 100);
+UPDATE profiles SET max_maturity_level = 7 WHERE is_kids;
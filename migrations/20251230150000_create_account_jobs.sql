@@ -0,0 +1,18 @@
+-- Background jobs for personal data exports and account deletion requests.
+-- Rows outlive the account (user_id is cleared) so deletions stay traceable.
+CREATE TABLE IF NOT EXISTS account_jobs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    kind VARCHAR(20) NOT NULL,                       -- EXPORT, DELETION
+    status VARCHAR(20) NOT NULL DEFAULT 'PENDING',   -- PENDING, RUNNING, COMPLETED, FAILED
+    archive_key TEXT,                                -- object key of the export archive
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+    expires_at TIMESTAMPTZ,                          -- exports can no longer be downloaded after this
+    CONSTRAINT account_jobs_kind_check CHECK (kind IN ('EXPORT', 'DELETION')),
+    CONSTRAINT account_jobs_status_check CHECK (status IN ('PENDING', 'RUNNING', 'COMPLETED', 'FAILED'))
+);
+
+CREATE INDEX idx_account_jobs_user ON account_jobs(user_id, created_at DESC);
@@ -0,0 +1,6 @@
+-- Support staff may act as a viewer (read-only) to debug what they see
+INSERT INTO permissions (name, description) VALUES
+    ('users.impersonate', 'Act as another user with a short-lived, read-only token');
+
+INSERT INTO role_permissions (role_name, permission) VALUES
+    ('ADMIN', 'users.impersonate');
@@ -0,0 +1,8 @@
+-- Views of a series are the sum of its episodes' views; used to sort the series list
+CREATE OR REPLACE FUNCTION series_views(p_series_id UUID) RETURNS BIGINT AS $$
+    SELECT COALESCE(SUM(e.views), 0)::BIGINT
+    FROM seasons s
+    JOIN episodes e ON e.season_id = s.id
+    WHERE s.series_id = p_series_id
+$$ LANGUAGE SQL STABLE;
+
@@ -0,0 +1,69 @@
+-- Full-text search over movies, series and episodes.
+-- One document per title, maintained by triggers, so the content tables and their
+-- queries stay unchanged. The 'simple' configuration does no stemming, titles are
+-- often names and the catalog is not in a single language.
+
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE TABLE IF NOT EXISTS search_documents (
+    content_type VARCHAR(20) NOT NULL, -- movie, series, episode
+    content_id UUID NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT,
+    -- Title weighs more than the description when ranking
+    search_vector TSVECTOR GENERATED ALWAYS AS (
+        setweight(to_tsvector('simple', title), 'A') ||
+        setweight(to_tsvector('simple', COALESCE(description, '')), 'B')
+    ) STORED,
+    PRIMARY KEY (content_type, content_id)
+);
+
+CREATE INDEX idx_search_documents_vector ON search_documents USING GIN (search_vector);
+-- Typo tolerance: trigram similarity on titles
+CREATE INDEX idx_search_documents_title_trgm ON search_documents USING GIN (title gin_trgm_ops);
+
+CREATE OR REPLACE FUNCTION sync_search_document() RETURNS TRIGGER AS $$
+DECLARE
+    kind VARCHAR(20) := TG_ARGV[0];
+    doc_title TEXT;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        DELETE FROM search_documents WHERE content_type = kind AND content_id = OLD.id;
+        RETURN OLD;
+    END IF;
+
+    -- Separate statements: NEW only has episode_number for episodes
+    IF kind = 'episode' THEN
+        doc_title := COALESCE(NEW.title, 'Episode ' || NEW.episode_number);
+    ELSE
+        doc_title := NEW.title;
+    END IF;
+
+    INSERT INTO search_documents (content_type, content_id, title, description)
+    VALUES (kind, NEW.id, doc_title, NEW.description)
+    ON CONFLICT (content_type, content_id)
+    DO UPDATE SET title = EXCLUDED.title, description = EXCLUDED.description;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER movies_search_document
+    AFTER INSERT OR UPDATE OF title, description OR DELETE ON movies
+    FOR EACH ROW EXECUTE FUNCTION sync_search_document('movie');
+
+CREATE TRIGGER series_search_document
+    AFTER INSERT OR UPDATE OF title, description OR DELETE ON series
+    FOR EACH ROW EXECUTE FUNCTION sync_search_document('series');
+
+CREATE TRIGGER episodes_search_document
+    AFTER INSERT OR UPDATE OF title, description, episode_number OR DELETE ON episodes
+    FOR EACH ROW EXECUTE FUNCTION sync_search_document('episode');
+
+-- Index existing titles
+INSERT INTO search_documents (content_type, content_id, title, description)
+SELECT 'movie', id, title, description FROM movies
+UNION ALL
+SELECT 'series', id, title, description FROM series
+UNION ALL
+SELECT 'episode', id, COALESCE(title, 'Episode ' || episode_number), description FROM episodes
+ON CONFLICT DO NOTHING;
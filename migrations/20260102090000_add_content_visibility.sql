@@ -0,0 +1,29 @@
+-- Publishing: whether a title is shown to viewers, separate from its transcode status.
+-- publish_at / unpublish_at schedule a change of visibility; the scheduler worker
+-- applies it and clears the timestamp.
+
+ALTER TABLE movies
+    ADD COLUMN visibility VARCHAR(20) NOT NULL DEFAULT 'unpublished'
+        CHECK (visibility IN ('published', 'unpublished')),
+    ADD COLUMN publish_at TIMESTAMPTZ,
+    ADD COLUMN unpublish_at TIMESTAMPTZ;
+
+ALTER TABLE series
+    ADD COLUMN visibility VARCHAR(20) NOT NULL DEFAULT 'unpublished'
+        CHECK (visibility IN ('published', 'unpublished')),
+    ADD COLUMN publish_at TIMESTAMPTZ,
+    ADD COLUMN unpublish_at TIMESTAMPTZ;
+
+-- Keep what viewers could already play: ready movies and series with a ready episode
+UPDATE movies SET visibility = 'published' WHERE status = 'READY';
+UPDATE series s SET visibility = 'published'
+WHERE EXISTS (
+    SELECT 1 FROM seasons sn JOIN episodes e ON e.season_id = sn.id
+    WHERE sn.series_id = s.id AND e.status = 'READY'
+);
+
+-- Due schedules are looked up on every scheduler tick
+CREATE INDEX idx_movies_publish_at ON movies(publish_at) WHERE publish_at IS NOT NULL;
+CREATE INDEX idx_movies_unpublish_at ON movies(unpublish_at) WHERE unpublish_at IS NOT NULL;
+CREATE INDEX idx_series_publish_at ON series(publish_at) WHERE publish_at IS NOT NULL;
+CREATE INDEX idx_series_unpublish_at ON series(unpublish_at) WHERE unpublish_at IS NOT NULL;
@@ -0,0 +1,24 @@
+-- Erasing an account redacts the recorded rows and addresses of its audit entries.
+-- The trigger lets exactly that update through: before, after and ip_address set
+-- to NULL with every other column unchanged. Anything else stays append-only.
+
+CREATE OR REPLACE FUNCTION audit_logs_append_only() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'UPDATE'
+        AND NEW.before IS NULL
+        AND NEW.after IS NULL
+        AND NEW.ip_address IS NULL
+        AND NEW.id = OLD.id
+        AND NEW.actor_id IS NOT DISTINCT FROM OLD.actor_id
+        AND NEW.actor_role IS NOT DISTINCT FROM OLD.actor_role
+        AND NEW.action = OLD.action
+        AND NEW.target_type = OLD.target_type
+        AND NEW.target_id IS NOT DISTINCT FROM OLD.target_id
+        AND NEW.request_id IS NOT DISTINCT FROM OLD.request_id
+        AND NEW.created_at = OLD.created_at
+    THEN
+        RETURN NEW;
+    END IF;
+    RAISE EXCEPTION 'audit_logs is append-only';
+END;
+$$ LANGUAGE plpgsql;
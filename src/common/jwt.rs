@@ -0,0 +1,167 @@
+use anyhow::{anyhow, Result};
+use argon2::password_hash::rand_core::OsRng;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use ed25519_dalek::pkcs8::{DecodePrivateKey, DecodePublicKey, EncodePrivateKey};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use jsonwebtoken::jwk::{
+    AlgorithmParameters, CommonParameters, EllipticCurve, Jwk, JwkSet, KeyAlgorithm,
+    OctetKeyPairParameters, OctetKeyPairType, PublicKeyUse,
+};
+use jsonwebtoken::{decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+struct VerificationKey {
+    decoding: DecodingKey,
+    jwk: Jwk,
+}
+
+struct Inner {
+    active_kid: String,
+    signing: EncodingKey,
+    verification: HashMap<String, VerificationKey>,
+}
+
+/// Ed25519 (EdDSA) keys used to sign and verify access tokens.
+///
+/// Keys are read from `JWT_KEYS_DIR`: every `<kid>.pem` file is a key whose file
+/// stem becomes its `kid`. Private keys (PKCS#8) can sign and verify, public keys
+/// (SPKI) only verify, which lets a retired key keep validating tokens until they
+/// expire. `JWT_ACTIVE_KID` selects the private key used for new tokens.
+///
+/// Without any private key loading fails, unless `allow_ephemeral` is set: then a
+/// key is generated for this process only, so tokens do not survive a restart and
+/// are not accepted by other replicas.
+#[derive(Clone)]
+pub struct JwtKeys {
+    inner: Arc<Inner>,
+}
+
+impl JwtKeys {
+    pub fn load(dir: &str, active_kid: Option<&str>, allow_ephemeral: bool) -> Result<Self> {
+        let mut signing_keys: HashMap<String, SigningKey> = HashMap::new();
+        let mut verifying_keys: HashMap<String, VerifyingKey> = HashMap::new();
+
+        let path = Path::new(dir);
+        if path.is_dir() {
+            for entry in std::fs::read_dir(path)? {
+                let file = entry?.path();
+                if file.extension().and_then(|e| e.to_str()) != Some("pem") {
+                    continue;
+                }
+
+                let kid = file
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .ok_or_else(|| anyhow!("Invalid key file name: {}", file.display()))?
+                    .to_string();
+                let pem = std::fs::read_to_string(&file)?;
+
+                if pem.contains("PRIVATE KEY") {
+                    let key = SigningKey::from_pkcs8_pem(&pem)
+                        .map_err(|e| anyhow!("Invalid Ed25519 private key {}: {}", file.display(), e))?;
+                    verifying_keys.insert(kid.clone(), key.verifying_key());
+                    signing_keys.insert(kid, key);
+                } else {
+                    let key = VerifyingKey::from_public_key_pem(&pem)
+                        .map_err(|e| anyhow!("Invalid Ed25519 public key {}: {}", file.display(), e))?;
+                    verifying_keys.insert(kid, key);
+                }
+            }
+        }
+
+        if signing_keys.is_empty() {
+            if !allow_ephemeral {
+                return Err(anyhow!(
+                    "No JWT signing keys found in '{}'; add a private key or set JWT_ALLOW_EPHEMERAL_KEY=true for local development",
+                    dir
+                ));
+            }
+            // Local development: tokens signed with this key do not survive a restart
+            warn!("No JWT signing keys found in '{}', generating an ephemeral key", dir);
+            let key = SigningKey::generate(&mut OsRng);
+            let kid = format!("ephemeral-{}", &hex::encode(key.verifying_key().as_bytes())[..8]);
+            verifying_keys.insert(kid.clone(), key.verifying_key());
+            signing_keys.insert(kid, key);
+        }
+
+        let active_kid = match active_kid {
+            Some(kid) => kid.to_string(),
+            None if signing_keys.len() == 1 => signing_keys.keys().next().cloned().unwrap_or_default(),
+            None => return Err(anyhow!("JWT_ACTIVE_KID must be set when several signing keys exist")),
+        };
+
+        let signing_key = signing_keys
+            .get(&active_kid)
+            .ok_or_else(|| anyhow!("No private key found for active kid '{}'", active_kid))?;
+        let signing_der = signing_key
+            .to_pkcs8_der()
+            .map_err(|e| anyhow!("Failed to encode signing key: {}", e))?;
+
+        let mut verification = HashMap::new();
+        for (kid, key) in verifying_keys {
+            let x = URL_SAFE_NO_PAD.encode(key.as_bytes());
+            let decoding = DecodingKey::from_ed_components(&x)?;
+            let jwk = Jwk {
+                common: CommonParameters {
+                    public_key_use: Some(PublicKeyUse::Signature),
+                    key_algorithm: Some(KeyAlgorithm::EdDSA),
+                    key_id: Some(kid.clone()),
+                    ..Default::default()
+                },
+                algorithm: AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                    key_type: OctetKeyPairType::OctetKeyPair,
+                    curve: EllipticCurve::Ed25519,
+                    x,
+                }),
+            };
+            verification.insert(kid, VerificationKey { decoding, jwk });
+        }
+
+        info!(
+            "🔑 Loaded {} JWT verification key(s), signing with kid '{}'",
+            verification.len(),
+            active_kid
+        );
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                signing: EncodingKey::from_ed_der(signing_der.as_bytes()),
+                active_kid,
+                verification,
+            }),
+        })
+    }
+
+    /// Signs claims with the active key and sets the `kid` header.
+    pub fn sign<T: Serialize>(&self, claims: &T) -> Result<String> {
+        let mut header = Header::new(Algorithm::EdDSA);
+        header.kid = Some(self.inner.active_kid.clone());
+
+        encode(&header, claims, &self.inner.signing).map_err(|e| anyhow!(e.to_string()))
+    }
+
+    /// Verifies a token against the key named by its `kid` header.
+    pub fn verify<T: DeserializeOwned>(&self, token: &str) -> Result<T> {
+        let header = decode_header(token)?;
+        let kid = header.kid.ok_or_else(|| anyhow!("Token has no kid"))?;
+        let key = self
+            .inner
+            .verification
+            .get(&kid)
+            .ok_or_else(|| anyhow!("Unknown signing key '{}'", kid))?;
+
+        let data = decode::<T>(token, &key.decoding, &Validation::new(Algorithm::EdDSA))?;
+        Ok(data.claims)
+    }
+
+    /// Public keys for `/.well-known/jwks.json`.
+    pub fn jwks(&self) -> JwkSet {
+        let mut keys: Vec<Jwk> = self.inner.verification.values().map(|k| k.jwk.clone()).collect();
+        keys.sort_by(|a, b| a.common.key_id.cmp(&b.common.key_id));
+        JwkSet { keys }
+    }
+}
@@ -1,7 +1,10 @@
 pub mod error;
+pub mod jwt;
 pub mod pagination;
+pub mod password;
 pub mod response;
 pub mod security;
+pub mod totp;
 pub mod upload;
 pub mod types;
 pub mod utils;
@@ -0,0 +1,357 @@
+use super::error::AppError;
+use axum::{
+    extract::{FromRequestParts, OriginalUri, Query},
+    http::{request::Parts, Uri},
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+pub const DEFAULT_PAGE_LIMIT: i64 = 20;
+pub const MAX_PAGE_LIMIT: i64 = 100;
+
+/// Paging parameters shared by list endpoints. Without `page` the list is paged
+/// by cursor, starting at the first item.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct PageParams {
+    /// Items per page, 1-100 (default 20)
+    pub limit: Option<i64>,
+    /// Opaque cursor from a `next` or `prev` link
+    pub cursor: Option<String>,
+    /// 1-based page number; switches to page mode, ignored together with `cursor`
+    pub page: Option<i64>,
+}
+
+#[derive(Debug, Clone)]
+pub enum PageMode {
+    Cursor(Option<String>),
+    Page(i64),
+}
+
+/// Position in a keyset-paged list: the item the page starts after (or before,
+/// when paging backwards). `scope` ties the cursor to the sort it was made for.
+///
+/// The item's sort value is kept in the cursor, so the next page follows on from
+/// where this one ended even if that item was changed or deleted in between.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cursor {
+    pub id: Uuid,
+    #[serde(rename = "k")]
+    pub key: SortKey,
+    #[serde(rename = "b", default)]
+    pub backward: bool,
+    #[serde(rename = "s")]
+    scope: String,
+}
+
+/// Sort value of the item a cursor points at.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SortKey {
+    #[serde(rename = "t")]
+    Text(String),
+    #[serde(rename = "i")]
+    Int(i64),
+    #[serde(rename = "f")]
+    Float(f64),
+    #[serde(rename = "d", with = "time::serde::iso8601")]
+    Time(OffsetDateTime),
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PageLinks {
+    pub next: Option<String>,
+    pub prev: Option<String>,
+}
+
+/// Response envelope of a paged list.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Items matching the filters, across all pages
+    pub total: i64,
+    pub limit: i64,
+    /// Current page number, only in page mode
+    pub page: Option<i64>,
+    pub links: PageLinks,
+}
+
+impl<T> Page<T> {
+    /// Same page with its items converted, e.g. rows into response DTOs.
+    pub fn with_items<U>(self, items: Vec<U>) -> Page<U> {
+        Page {
+            items,
+            total: self.total,
+            limit: self.limit,
+            page: self.page,
+            links: self.links,
+        }
+    }
+}
+
+/// Paging of the current request, with the request URI kept to build the
+/// `next`/`prev` links. Filters and sort stay in the links unchanged.
+#[derive(Debug, Clone)]
+pub struct Pagination {
+    pub limit: i64,
+    pub mode: PageMode,
+    uri: Uri,
+}
+
+impl<S> FromRequestParts<S> for Pagination
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(params) = Query::<PageParams>::from_request_parts(parts, state)
+            .await
+            .map_err(|e| AppError::validation(e.body_text()))?;
+
+        let limit = params.limit.unwrap_or(DEFAULT_PAGE_LIMIT);
+        if !(1..=MAX_PAGE_LIMIT).contains(&limit) {
+            return Err(AppError::validation(format!("limit must be between 1 and {}", MAX_PAGE_LIMIT)));
+        }
+
+        let mode = match (params.cursor, params.page) {
+            (Some(cursor), _) => PageMode::Cursor(Some(cursor)),
+            (None, Some(page)) if page < 1 => return Err(AppError::validation("page must be at least 1")),
+            (None, Some(page)) => PageMode::Page(page),
+            (None, None) => PageMode::Cursor(None),
+        };
+
+        let uri = parts.extensions.get::<OriginalUri>().map_or_else(|| parts.uri.clone(), |u| u.0.clone());
+        Ok(Self { limit, mode, uri })
+    }
+}
+
+impl Pagination {
+    /// Decodes the request's cursor. `scope` identifies the sort of the list; a
+    /// cursor made for another sort is rejected instead of skipping items.
+    pub fn cursor(&self, scope: &str) -> Result<Option<Cursor>, AppError> {
+        let PageMode::Cursor(Some(token)) = &self.mode else {
+            return Ok(None);
+        };
+
+        let cursor: Cursor = URL_SAFE_NO_PAD
+            .decode(token)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .ok_or_else(|| AppError::validation("Invalid cursor"))?;
+
+        if cursor.scope != scope {
+            return Err(AppError::validation("Cursor does not match the requested sort"));
+        }
+        Ok(Some(cursor))
+    }
+
+    /// Rows to fetch: in cursor mode one more than the limit, to tell whether
+    /// another page follows.
+    pub fn fetch_limit(&self) -> i64 {
+        match self.mode {
+            PageMode::Cursor(_) => self.limit + 1,
+            PageMode::Page(_) => self.limit,
+        }
+    }
+
+    pub fn offset(&self) -> i64 {
+        match self.mode {
+            PageMode::Cursor(_) => 0,
+            PageMode::Page(page) => (page - 1) * self.limit,
+        }
+    }
+
+    /// Builds the page from rows fetched with `fetch_limit` and `offset`. Rows of a
+    /// backward cursor come in reverse order and are flipped back here. `position`
+    /// gives the id and sort value a cursor to a row is made of.
+    pub fn paginate<T>(
+        &self,
+        mut rows: Vec<T>,
+        total: i64,
+        scope: &str,
+        position: impl Fn(&T) -> (Uuid, SortKey),
+    ) -> Page<T> {
+        let (page, next, prev) = match &self.mode {
+            PageMode::Page(page) => {
+                let next = (self.offset() + (rows.len() as i64) < total).then(|| self.link("page", (page + 1).to_string()));
+                let prev = (*page > 1).then(|| self.link("page", (page - 1).to_string()));
+                (Some(*page), next, prev)
+            }
+            PageMode::Cursor(token) => {
+                let backward = self.cursor(scope).ok().flatten().is_some_and(|c| c.backward);
+                let has_more = rows.len() as i64 > self.limit;
+                rows.truncate(self.limit as usize);
+                if backward {
+                    rows.reverse();
+                }
+
+                // Coming from a cursor means there are items on the side we came from
+                let (has_next, has_prev) = match (token.is_some(), backward) {
+                    (false, _) => (has_more, false),
+                    (true, false) => (has_more, true),
+                    (true, true) => (true, has_more),
+                };
+                let next = rows.last().filter(|_| has_next).map(|last| self.cursor_link(position(last), false, scope));
+                let prev = rows.first().filter(|_| has_prev).map(|first| self.cursor_link(position(first), true, scope));
+                (None, next, prev)
+            }
+        };
+
+        Page {
+            items: rows,
+            total,
+            limit: self.limit,
+            page,
+            links: PageLinks { next, prev },
+        }
+    }
+
+    fn cursor_link(&self, (id, key): (Uuid, SortKey), backward: bool, scope: &str) -> String {
+        let cursor = Cursor { id, key, backward, scope: scope.to_string() };
+        let token = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&cursor).unwrap_or_default());
+        self.link("cursor", token)
+    }
+
+    /// The request URI with its `cursor`/`page` replaced.
+    fn link(&self, key: &str, value: String) -> String {
+        let pairs = url::form_urlencoded::parse(self.uri.query().unwrap_or_default().as_bytes())
+            .filter(|(k, _)| k != "cursor" && k != "page");
+        let query = url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(pairs)
+            .append_pair(key, &value)
+            .finish();
+        format!("{}?{}", self.uri.path(), query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+
+    const SCOPE: &str = "views:desc";
+
+    async fn pagination(uri: &str) -> Result<Pagination, AppError> {
+        let (mut parts, _) = Request::builder().uri(uri).body(()).unwrap().into_parts();
+        Pagination::from_request_parts(&mut parts, &()).await
+    }
+
+    fn query_value(link: &str, key: &str) -> Option<String> {
+        let (_, query) = link.split_once('?')?;
+        url::form_urlencoded::parse(query.as_bytes())
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.into_owned())
+    }
+
+    /// Rows as (id, views), sorted by views descending.
+    fn rows(views: &[i64]) -> Vec<(Uuid, i64)> {
+        views.iter().map(|v| (Uuid::new_v4(), *v)).collect()
+    }
+
+    fn position(row: &(Uuid, i64)) -> (Uuid, SortKey) {
+        (row.0, SortKey::Int(row.1))
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_limit_and_page() {
+        assert!(pagination("/movies?limit=0").await.is_err());
+        assert!(pagination(&format!("/movies?limit={}", MAX_PAGE_LIMIT + 1)).await.is_err());
+        assert!(pagination("/movies?page=0").await.is_err());
+        assert_eq!(pagination("/movies").await.unwrap().limit, DEFAULT_PAGE_LIMIT);
+    }
+
+    #[tokio::test]
+    async fn first_page_links_to_the_next_with_the_last_item_as_cursor() {
+        let first = pagination("/movies?sort=views&limit=2").await.unwrap();
+        assert_eq!(first.fetch_limit(), 3);
+
+        let fetched = rows(&[30, 20, 10]);
+        let page = first.paginate(fetched.clone(), 3, SCOPE, position);
+        assert_eq!(page.items, fetched[..2]);
+        assert!(page.links.prev.is_none());
+
+        let next = page.links.next.expect("next link");
+        assert_eq!(query_value(&next, "sort").as_deref(), Some("views"));
+        assert_eq!(query_value(&next, "limit").as_deref(), Some("2"));
+
+        let cursor = pagination(&next).await.unwrap().cursor(SCOPE).unwrap().expect("cursor");
+        assert_eq!(cursor.id, fetched[1].0);
+        assert_eq!(cursor.key, SortKey::Int(20));
+        assert!(!cursor.backward);
+    }
+
+    #[tokio::test]
+    async fn last_page_has_no_next_link() {
+        let first = pagination("/movies?limit=2").await.unwrap();
+        let page = first.paginate(rows(&[30, 20]), 2, SCOPE, position);
+        assert!(page.links.next.is_none());
+        assert!(page.links.prev.is_none());
+    }
+
+    #[tokio::test]
+    async fn backward_cursor_flips_rows_and_links_both_ways() {
+        let first = pagination("/movies?limit=2").await.unwrap();
+        let page = first.paginate(rows(&[50, 40, 30]), 5, SCOPE, position);
+        let next = pagination(&page.links.next.unwrap()).await.unwrap();
+
+        let second = next.paginate(rows(&[30, 20, 10]), 5, SCOPE, position);
+        let prev = pagination(&second.links.prev.expect("prev link")).await.unwrap();
+        let cursor = prev.cursor(SCOPE).unwrap().expect("cursor");
+        assert!(cursor.backward);
+        assert_eq!(cursor.key, SortKey::Int(30));
+
+        // Paging backwards the query reads in reverse order, nearest item first
+        let fetched = rows(&[40, 50]);
+        let back = prev.paginate(fetched.clone(), 5, SCOPE, position);
+        assert_eq!(back.items, vec![fetched[1], fetched[0]]);
+        assert!(back.links.next.is_some());
+        assert!(back.links.prev.is_none());
+    }
+
+    #[tokio::test]
+    async fn cursor_keeps_time_and_float_keys_exact() {
+        let created_at = OffsetDateTime::from_unix_timestamp_nanos(1_767_225_600_123_456_000).unwrap();
+        for key in [SortKey::Time(created_at), SortKey::Float(7.3), SortKey::Text("Ünïcode Title".to_string())] {
+            let first = pagination("/movies?limit=1").await.unwrap();
+            let id = Uuid::new_v4();
+            let page = first.paginate(vec![1, 2], 2, SCOPE, |_| (id, key.clone()));
+
+            let cursor = pagination(&page.links.next.unwrap()).await.unwrap().cursor(SCOPE).unwrap().unwrap();
+            assert_eq!(cursor.id, id);
+            assert_eq!(cursor.key, key);
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_cursor_of_another_sort_or_malformed() {
+        let first = pagination("/movies?limit=1").await.unwrap();
+        let page = first.paginate(rows(&[2, 1]), 2, SCOPE, position);
+        let next = pagination(&page.links.next.unwrap()).await.unwrap();
+
+        assert!(matches!(next.cursor("views:asc"), Err(AppError::Validation(_))));
+        assert!(matches!(
+            pagination("/movies?cursor=not-a-cursor").await.unwrap().cursor(SCOPE),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn page_mode_uses_offset_and_page_links() {
+        let second = pagination("/movies?page=2&limit=10&genre=drama").await.unwrap();
+        assert_eq!(second.fetch_limit(), 10);
+        assert_eq!(second.offset(), 10);
+        assert!(second.cursor(SCOPE).unwrap().is_none());
+
+        let page = second.paginate(rows(&[1; 10]), 25, SCOPE, position);
+        assert_eq!(page.page, Some(2));
+        let next = page.links.next.expect("next link");
+        assert_eq!(query_value(&next, "page").as_deref(), Some("3"));
+        assert_eq!(query_value(&next, "genre").as_deref(), Some("drama"));
+        assert_eq!(query_value(&page.links.prev.unwrap(), "page").as_deref(), Some("1"));
+
+        let third = pagination(&next).await.unwrap();
+        assert!(third.paginate(rows(&[1; 5]), 25, SCOPE, position).links.next.is_none());
+    }
+}
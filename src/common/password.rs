@@ -0,0 +1,235 @@
+use super::error::AppError;
+use anyhow::{anyhow, Result};
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Algorithm, Argon2, KeyId, Params, ParamsBuilder, Version,
+};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::sync::{Arc, OnceLock};
+
+const COMMON_PASSWORDS: &str = include_str!("common_passwords.txt");
+
+pub const MAX_PASSWORD_LENGTH: usize = 128;
+
+fn common_passwords() -> &'static HashSet<&'static str> {
+    static LIST: OnceLock<HashSet<&'static str>> = OnceLock::new();
+    LIST.get_or_init(|| {
+        COMMON_PASSWORDS
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .collect()
+    })
+}
+
+/// Argon2id hashing with the cost parameters from config and an optional pepper.
+///
+/// A peppered hash records the pepper's id (the first bytes of its SHA-256) in the
+/// `keyid` parameter, so hashes made before the pepper was introduced still verify
+/// and are picked up by `needs_rehash`. Changing the pepper invalidates every
+/// password hashed with the old one.
+#[derive(Clone)]
+pub struct Passwords {
+    params: Params,
+    pepper: Option<Arc<[u8]>>,
+    pepper_id: Option<KeyId>,
+    min_length: usize,
+}
+
+impl Passwords {
+    pub fn new(
+        memory_kib: u32,
+        iterations: u32,
+        parallelism: u32,
+        pepper: Option<&str>,
+        min_length: usize,
+    ) -> Result<Self> {
+        let pepper_id = pepper
+            .map(|p| KeyId::new(&Sha256::digest(p.as_bytes())[..Params::MAX_KEYID_LEN]))
+            .transpose()
+            .map_err(|e| anyhow!("Invalid pepper: {}", e))?;
+
+        let mut builder = ParamsBuilder::new();
+        builder.m_cost(memory_kib).t_cost(iterations).p_cost(parallelism);
+        if let Some(id) = pepper_id {
+            builder.keyid(id);
+        }
+        let params = builder.build().map_err(|e| anyhow!("Invalid Argon2 parameters: {}", e))?;
+
+        Ok(Self {
+            params,
+            pepper: pepper.map(|p| Arc::from(p.as_bytes())),
+            pepper_id,
+            min_length,
+        })
+    }
+
+    fn argon2(&self, peppered: bool) -> Result<Argon2<'_>> {
+        match (&self.pepper, peppered) {
+            (Some(pepper), true) => Argon2::new_with_secret(pepper, Algorithm::Argon2id, Version::V0x13, self.params.clone())
+                .map_err(|e| anyhow!(e.to_string())),
+            (None, true) => Err(anyhow!("Password hash needs a pepper but PASSWORD_PEPPER is not set")),
+            (_, false) => Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, self.params.clone())),
+        }
+    }
+
+    pub fn hash(&self, password: &str) -> Result<String> {
+        let salt = SaltString::generate(&mut OsRng);
+        let password_hash = self
+            .argon2(self.pepper.is_some())?
+            .hash_password(password.as_bytes(), &salt)
+            .map_err(|e| anyhow!(e.to_string()))?
+            .to_string();
+        Ok(password_hash)
+    }
+
+    /// Checks a password against a stored hash. The hash's own cost parameters are
+    /// used, so hashes made with older settings keep working. A wrong password is
+    /// `Ok(false)`; errors mean the hash itself cannot be checked.
+    pub fn verify(&self, password: &str, hash: &str) -> Result<bool> {
+        let parsed_hash = PasswordHash::new(hash).map_err(|e| anyhow!(e.to_string()))?;
+        let keyid = Params::try_from(&parsed_hash)
+            .map_err(|e| anyhow!(e.to_string()))?
+            .keyid()
+            .to_vec();
+        let peppered = !keyid.is_empty();
+
+        if peppered && self.pepper_id.map(|id| id.as_bytes().to_vec()) != Some(keyid) {
+            tracing::warn!("Password hash was made with a different pepper");
+            return Ok(false);
+        }
+
+        match self.argon2(peppered)?.verify_password(password.as_bytes(), &parsed_hash) {
+            Ok(()) => Ok(true),
+            Err(argon2::password_hash::Error::Password) => Ok(false),
+            Err(e) => Err(anyhow!(e.to_string())),
+        }
+    }
+
+    /// The hash was made with other cost parameters, another algorithm or without the
+    /// current pepper, and should be replaced after the next successful login.
+    pub fn needs_rehash(&self, hash: &str) -> bool {
+        let Ok(parsed_hash) = PasswordHash::new(hash) else {
+            return true;
+        };
+        let Ok(params) = Params::try_from(&parsed_hash) else {
+            return true;
+        };
+
+        parsed_hash.algorithm.as_str() != "argon2id"
+            || parsed_hash.version != Some(Version::V0x13.into())
+            || params.m_cost() != self.params.m_cost()
+            || params.t_cost() != self.params.t_cost()
+            || params.p_cost() != self.params.p_cost()
+            || params.keyid() != self.params.keyid()
+    }
+
+    /// Rejects passwords that are too short or too long, on the bundled list of
+    /// common and breached passwords, or that contain the user's name or email.
+    pub fn check_policy(&self, password: &str, personal: &[&str]) -> Result<(), AppError> {
+        let length = password.chars().count();
+        if length < self.min_length {
+            return Err(AppError::validation(format!("Password must be at least {} characters", self.min_length)));
+        }
+        if length > MAX_PASSWORD_LENGTH {
+            return Err(AppError::validation(format!("Password must be at most {} characters", MAX_PASSWORD_LENGTH)));
+        }
+
+        let lowered = password.trim().to_lowercase();
+        if common_passwords().contains(lowered.as_str()) {
+            return Err(AppError::validation("Password is too common, choose a less predictable one"));
+        }
+
+        if password.chars().all(|c| c == password.chars().next().unwrap_or_default()) {
+            return Err(AppError::validation("Password must not repeat a single character"));
+        }
+
+        for value in personal {
+            // Compare against the name part of an email address, not the domain
+            let value = value.split('@').next().unwrap_or_default().trim().to_lowercase();
+            if value.chars().count() >= 3 && lowered.contains(&value) {
+                return Err(AppError::validation("Password must not contain your username or email"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Minimal Argon2 cost so the tests run fast
+    fn passwords(pepper: Option<&str>) -> Passwords {
+        Passwords::new(256, 1, 1, pepper, 8).unwrap()
+    }
+
+    #[test]
+    fn verify_distinguishes_wrong_password_from_bad_hash() {
+        let passwords = passwords(None);
+        let hash = passwords.hash("correct horse").unwrap();
+
+        assert!(passwords.verify("correct horse", &hash).unwrap());
+        assert!(!passwords.verify("wrong horse", &hash).unwrap());
+        assert!(passwords.verify("correct horse", "not a hash").is_err());
+    }
+
+    #[test]
+    fn peppered_hashes_need_the_same_pepper() {
+        let peppered = passwords(Some("pepper-one"));
+        let hash = peppered.hash("correct horse").unwrap();
+
+        assert!(peppered.verify("correct horse", &hash).unwrap());
+        assert!(!passwords(Some("pepper-two")).verify("correct horse", &hash).unwrap());
+        assert!(!passwords(None).verify("correct horse", &hash).unwrap());
+
+        // Hashes made before the pepper was introduced keep working
+        let unpeppered = passwords(None).hash("correct horse").unwrap();
+        assert!(peppered.verify("correct horse", &unpeppered).unwrap());
+    }
+
+    #[test]
+    fn needs_rehash_when_cost_or_pepper_changed() {
+        let current = passwords(Some("pepper"));
+        let hash = current.hash("correct horse").unwrap();
+        assert!(!current.needs_rehash(&hash));
+
+        let older_cost = Passwords::new(128, 1, 1, Some("pepper"), 8).unwrap().hash("correct horse").unwrap();
+        assert!(current.needs_rehash(&older_cost));
+
+        let without_pepper = passwords(None).hash("correct horse").unwrap();
+        assert!(current.needs_rehash(&without_pepper));
+        assert!(current.needs_rehash("$2b$12$R9h/cIPz0gi.URNNX3kh2OPST9/PgBkqquzi.Ss7KIUgO2t0jWMUW"));
+        assert!(current.needs_rehash("garbage"));
+    }
+
+    #[test]
+    fn check_policy_enforces_length() {
+        let passwords = passwords(None);
+        assert!(passwords.check_policy("Sh0rt!", &[]).is_err());
+        assert!(passwords.check_policy(&"x1".repeat(MAX_PASSWORD_LENGTH / 2 + 1), &[]).is_err());
+        assert!(passwords.check_policy("tangerine-orbit-42", &[]).is_ok());
+    }
+
+    #[test]
+    fn check_policy_rejects_common_and_repeated_passwords() {
+        let passwords = passwords(None);
+        assert!(passwords.check_policy("password", &[]).is_err());
+        assert!(passwords.check_policy("PASSWORD", &[]).is_err());
+        assert!(passwords.check_policy("aaaaaaaaaa", &[]).is_err());
+    }
+
+    #[test]
+    fn check_policy_rejects_personal_values() {
+        let passwords = passwords(None);
+        let personal = ["jdoe", "jane.doe@example.com", "Jane Doe"];
+
+        assert!(passwords.check_policy("my-JDOE-secret", &personal).is_err());
+        assert!(passwords.check_policy("jane.doe-rocks", &personal).is_err());
+        // Only the name part of the email counts, and short values are ignored
+        assert!(passwords.check_policy("example.com-orbit", &personal).is_ok());
+        assert!(passwords.check_policy("tangerine-jd-orbit", &["jd"]).is_ok());
+    }
+}
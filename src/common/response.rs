@@ -24,14 +24,6 @@ where
             data: Some(data),
         }
     }
-
-    pub fn error(message: &str) -> Self {
-        Self {
-            status: "error".to_string(),
-            message: message.to_string(),
-            data: None,
-        }
-    }
 }
 
 pub struct ApiSuccess<T>(pub T, pub StatusCode);
@@ -45,13 +37,3 @@ where
         (status, Json(response)).into_response()
     }
 }
-
-pub struct ApiError(pub String, pub StatusCode);
-
-impl IntoResponse for ApiError {
-    fn into_response(self) -> Response {
-        let (message, status) = (self.0, self.1);
-        let response = ApiResponse::<()>::error(&message);
-        (status, Json(response)).into_response()
-    }
-}
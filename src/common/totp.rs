@@ -0,0 +1,97 @@
+use anyhow::{anyhow, Result};
+use jsonwebtoken::get_current_timestamp;
+use totp_rs::{Algorithm, Secret, TOTP};
+
+/// Length of a time step in seconds (RFC 6238 default, what authenticator apps expect).
+pub const STEP_SECS: u64 = 30;
+
+/// Builds a TOTP for a base32 secret: SHA-1, 6 digits, 30 second steps.
+/// Clock skew is handled by `verify`, which needs to know the matching step.
+fn build(secret: &str, issuer: &str, account: &str) -> Result<TOTP> {
+    let bytes = Secret::Encoded(secret.to_string())
+        .to_bytes()
+        .map_err(|e| anyhow!("Invalid TOTP secret: {:?}", e))?;
+
+    TOTP::new(
+        Algorithm::SHA1,
+        6,
+        0,
+        STEP_SECS,
+        bytes,
+        Some(issuer.to_string()),
+        account.to_string(),
+    )
+    .map_err(|e| anyhow!("Invalid TOTP parameters: {}", e))
+}
+
+/// Generates a new random 160-bit secret, base32 encoded.
+pub fn generate_secret() -> String {
+    Secret::generate_secret().to_encoded().to_string()
+}
+
+/// `otpauth://` URI to render as a QR code in the client.
+pub fn provisioning_uri(secret: &str, issuer: &str, account: &str) -> Result<String> {
+    Ok(build(secret, issuer, account)?.get_url())
+}
+
+/// Checks a code against the current time. Returns the time step the code
+/// belongs to, so callers can reject a code that was already used.
+pub fn verify(secret: &str, code: &str) -> Result<Option<u64>> {
+    verify_at(secret, code, get_current_timestamp())
+}
+
+fn verify_at(secret: &str, code: &str, now: u64) -> Result<Option<u64>> {
+    let totp = build(secret, "", "")?;
+
+    // Accept one step of clock skew in either direction
+    let current_step = now / STEP_SECS;
+    for step in [current_step - 1, current_step, current_step + 1] {
+        if totp.check(code, step * STEP_SECS) {
+            return Ok(Some(step));
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_767_225_615;
+
+    fn code_at(secret: &str, time: u64) -> String {
+        build(secret, "", "").unwrap().generate(time)
+    }
+
+    #[test]
+    fn accepts_one_step_of_clock_skew() {
+        let secret = generate_secret();
+        let step = NOW / STEP_SECS;
+
+        assert_eq!(verify_at(&secret, &code_at(&secret, NOW), NOW).unwrap(), Some(step));
+        assert_eq!(verify_at(&secret, &code_at(&secret, NOW - STEP_SECS), NOW).unwrap(), Some(step - 1));
+        assert_eq!(verify_at(&secret, &code_at(&secret, NOW + STEP_SECS), NOW).unwrap(), Some(step + 1));
+    }
+
+    #[test]
+    fn rejects_codes_outside_the_window_or_of_another_secret() {
+        let secret = generate_secret();
+        assert_eq!(verify_at(&secret, &code_at(&secret, NOW - 2 * STEP_SECS), NOW).unwrap(), None);
+        assert_eq!(verify_at(&secret, &code_at(&secret, NOW + 2 * STEP_SECS), NOW).unwrap(), None);
+        assert_eq!(verify_at(&secret, &code_at(&generate_secret(), NOW), NOW).unwrap(), None);
+        assert_eq!(verify_at(&secret, "not a code", NOW).unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_invalid_secrets() {
+        assert!(verify_at("not base32!", "123456", NOW).is_err());
+    }
+
+    #[test]
+    fn provisioning_uri_names_issuer_and_account() {
+        let uri = provisioning_uri(&generate_secret(), "HiuraMovie", "jane@example.com").unwrap();
+        assert!(uri.starts_with("otpauth://totp/HiuraMovie:jane%40example.com?"));
+        assert!(uri.contains("issuer=HiuraMovie"));
+    }
+}
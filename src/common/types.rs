@@ -0,0 +1,117 @@
+use crate::state::AppState;
+use axum::{
+    extract::{ConnectInfo, FromRequestParts},
+    http::{header, request::Parts, HeaderMap},
+};
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+
+/// Information about the calling client, taken from the socket address and the
+/// request headers.
+#[derive(Debug, Clone, Default)]
+pub struct ClientContext {
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl ClientContext {
+    /// Address of the client. Forwarding headers can be set by anyone, so they are
+    /// only read when the peer is one of `trusted_proxies`; then the nearest address
+    /// in X-Forwarded-For that is not a trusted proxy is the client (each proxy
+    /// appends the address it received the request from), else X-Real-IP.
+    fn client_ip(headers: &HeaderMap, peer: Option<IpAddr>, trusted_proxies: &[IpAddr]) -> Option<IpAddr> {
+        let peer = peer?;
+        if !trusted_proxies.contains(&peer) {
+            return Some(peer);
+        }
+
+        let forwarded: Vec<IpAddr> = headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .filter_map(|v| v.trim().parse().ok())
+            .collect();
+
+        forwarded
+            .into_iter()
+            .rev()
+            .find(|ip| !trusted_proxies.contains(ip))
+            .or_else(|| {
+                headers
+                    .get("x-real-ip")
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.trim().parse().ok())
+            })
+            .or(Some(peer))
+    }
+}
+
+impl FromRequestParts<AppState> for ClientContext {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let peer = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+        let ip_address = Self::client_ip(&parts.headers, peer, &state.config.trusted_proxies).map(|ip| ip.to_string());
+
+        let user_agent = parts
+            .headers
+            .get(header::USER_AGENT)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string());
+
+        Ok(Self { ip_address, user_agent })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROXY: &str = "10.0.0.1";
+    const CLIENT: &str = "203.0.113.7";
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(*name, value.parse().unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn ignores_forwarding_headers_from_untrusted_peers() {
+        let spoofed = headers(&[("x-forwarded-for", "198.51.100.1"), ("x-real-ip", "198.51.100.2")]);
+        assert_eq!(ClientContext::client_ip(&spoofed, Some(ip(CLIENT)), &[ip(PROXY)]), Some(ip(CLIENT)));
+        assert_eq!(ClientContext::client_ip(&spoofed, Some(ip(CLIENT)), &[]), Some(ip(CLIENT)));
+        assert_eq!(ClientContext::client_ip(&spoofed, None, &[ip(PROXY)]), None);
+    }
+
+    #[test]
+    fn takes_the_nearest_untrusted_forwarded_address() {
+        let trusted = [ip(PROXY), ip("10.0.0.2")];
+        // The client can prepend anything; only what the trusted proxies appended counts
+        let forwarded = headers(&[("x-forwarded-for", &format!("198.51.100.1, {}, 10.0.0.2", CLIENT))]);
+        assert_eq!(ClientContext::client_ip(&forwarded, Some(ip(PROXY)), &trusted), Some(ip(CLIENT)));
+
+        let split = headers(&[("x-forwarded-for", "198.51.100.1"), ("x-forwarded-for", CLIENT)]);
+        assert_eq!(ClientContext::client_ip(&split, Some(ip(PROXY)), &trusted), Some(ip(CLIENT)));
+    }
+
+    #[test]
+    fn falls_back_to_real_ip_then_the_proxy() {
+        let trusted = [ip(PROXY)];
+        let real_ip = headers(&[("x-forwarded-for", PROXY), ("x-real-ip", CLIENT)]);
+        assert_eq!(ClientContext::client_ip(&real_ip, Some(ip(PROXY)), &trusted), Some(ip(CLIENT)));
+
+        let garbage = headers(&[("x-forwarded-for", "unknown")]);
+        assert_eq!(ClientContext::client_ip(&garbage, Some(ip(PROXY)), &trusted), Some(ip(PROXY)));
+    }
+}
@@ -92,7 +92,7 @@ impl<'a> MultipartUploader<'a> {
 
 pub async fn stream_to_s3(
     storage: &StorageService,
-    mut field: Field<'_>,
+    field: Field<'_>,
     key: String,
 ) -> Result<String> {
     let content_type = field.content_type().unwrap_or("application/octet-stream").to_string();
@@ -102,7 +102,46 @@ pub async fn stream_to_s3(
         return Err(anyhow!("Invalid content type: only video/* and image/* allowed"));
     }
 
-    let mut uploader = MultipartUploader::new(storage, key.clone(), &content_type).await?;
+    upload_field(storage, field, key, &content_type, Bytes::new(), None).await
+}
+
+/// Same as `stream_to_s3`, for callers that inspected the start of the file themselves:
+/// `head` holds the bytes already read from `field` and is uploaded first, the object is
+/// stored as `content_type`, and the upload is aborted once more than `max_bytes` were received.
+pub async fn stream_to_s3_limited(
+    storage: &StorageService,
+    field: Field<'_>,
+    key: String,
+    content_type: &str,
+    head: Bytes,
+    max_bytes: usize,
+) -> Result<String> {
+    upload_field(storage, field, key, content_type, head, Some(max_bytes)).await
+}
+
+async fn upload_field(
+    storage: &StorageService,
+    mut field: Field<'_>,
+    key: String,
+    content_type: &str,
+    head: Bytes,
+    max_bytes: Option<usize>,
+) -> Result<String> {
+    let mut received = head.len();
+    if let Some(max) = max_bytes
+        && received > max
+    {
+        return Err(anyhow!("File is too large, the limit is {} bytes", max));
+    }
+
+    let mut uploader = MultipartUploader::new(storage, key.clone(), content_type).await?;
+    if !head.is_empty()
+        && let Err(e) = uploader.write_chunk(head).await
+    {
+        error!("Upload error: {}", e);
+        uploader.abort().await?;
+        return Err(e);
+    }
 
     while let Some(chunk) = field.next().await {
         let chunk = match chunk {
@@ -114,6 +153,14 @@ pub async fn stream_to_s3(
             }
         };
 
+        received += chunk.len();
+        if let Some(max) = max_bytes
+            && received > max
+        {
+            uploader.abort().await?;
+            return Err(anyhow!("File is too large, the limit is {} bytes", max));
+        }
+
         if let Err(e) = uploader.write_chunk(chunk).await {
             error!("Upload error: {}", e);
             uploader.abort().await?;
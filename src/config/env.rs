@@ -8,10 +8,42 @@ pub enum EnvKey {
     MinioUrl,
     MinioBucket,
     MinioBucketThumbnails,
+    MinioBucketExports,
     MinioAccessKey,
     MinioSecretKey,
-    JwtSecret,
+    JwtKeysDir,
+    JwtActiveKid,
+    JwtAllowEphemeralKey,
     RabbitMqUrl,
+    AppUrl,
+    MailDriver,
+    MailOutboxDir,
+    RequireVerifiedEmailLogin,
+    RequireVerifiedEmailStream,
+    TotpIssuer,
+    RequireAdminMfa,
+    OidcIssuerUrl,
+    OidcClientId,
+    OidcClientSecret,
+    OidcRedirectUrl,
+    OidcScopes,
+    ApiKeyDailyQuota,
+    LoginMaxFailures,
+    LoginMaxFailuresPerIp,
+    LoginLockoutMinutes,
+    TrustedProxies,
+    MaxProfilesPerAccount,
+    KidsMaxMaturityLevel,
+    AnonymousMaxMaturityLevel,
+    MaturityRatingSystem,
+    DataExportTtlHours,
+    Argon2MemoryKib,
+    Argon2Iterations,
+    Argon2Parallelism,
+    PasswordPepper,
+    PasswordMinLength,
+    ImpersonationTtlMinutes,
+    PublishScheduleIntervalSecs,
 }
 
 impl EnvKey {
@@ -23,10 +55,42 @@ impl EnvKey {
             EnvKey::MinioUrl => "MINIO_ENDPOINT",
             EnvKey::MinioBucket => "MINIO_BUCKET_VIDEOS",
             EnvKey::MinioBucketThumbnails => "MINIO_BUCKET_THUMBNAILS",
+            EnvKey::MinioBucketExports => "MINIO_BUCKET_EXPORTS",
             EnvKey::MinioAccessKey => "AWS_ACCESS_KEY_ID",
             EnvKey::MinioSecretKey => "AWS_SECRET_ACCESS_KEY",
-            EnvKey::JwtSecret => "JWT_SECRET",
+            EnvKey::JwtKeysDir => "JWT_KEYS_DIR",
+            EnvKey::JwtActiveKid => "JWT_ACTIVE_KID",
+            EnvKey::JwtAllowEphemeralKey => "JWT_ALLOW_EPHEMERAL_KEY",
             EnvKey::RabbitMqUrl => "RABBITMQ_URL",
+            EnvKey::AppUrl => "APP_URL",
+            EnvKey::MailDriver => "MAIL_DRIVER",
+            EnvKey::MailOutboxDir => "MAIL_OUTBOX_DIR",
+            EnvKey::RequireVerifiedEmailLogin => "REQUIRE_VERIFIED_EMAIL_LOGIN",
+            EnvKey::RequireVerifiedEmailStream => "REQUIRE_VERIFIED_EMAIL_STREAM",
+            EnvKey::TotpIssuer => "TOTP_ISSUER",
+            EnvKey::RequireAdminMfa => "REQUIRE_ADMIN_MFA",
+            EnvKey::OidcIssuerUrl => "OIDC_ISSUER_URL",
+            EnvKey::OidcClientId => "OIDC_CLIENT_ID",
+            EnvKey::OidcClientSecret => "OIDC_CLIENT_SECRET",
+            EnvKey::OidcRedirectUrl => "OIDC_REDIRECT_URL",
+            EnvKey::OidcScopes => "OIDC_SCOPES",
+            EnvKey::ApiKeyDailyQuota => "API_KEY_DAILY_QUOTA",
+            EnvKey::LoginMaxFailures => "LOGIN_MAX_FAILURES",
+            EnvKey::LoginMaxFailuresPerIp => "LOGIN_MAX_FAILURES_PER_IP",
+            EnvKey::LoginLockoutMinutes => "LOGIN_LOCKOUT_MINUTES",
+            EnvKey::TrustedProxies => "TRUSTED_PROXIES",
+            EnvKey::MaxProfilesPerAccount => "MAX_PROFILES_PER_ACCOUNT",
+            EnvKey::KidsMaxMaturityLevel => "KIDS_MAX_MATURITY_LEVEL",
+            EnvKey::AnonymousMaxMaturityLevel => "ANONYMOUS_MAX_MATURITY_LEVEL",
+            EnvKey::MaturityRatingSystem => "MATURITY_RATING_SYSTEM",
+            EnvKey::DataExportTtlHours => "DATA_EXPORT_TTL_HOURS",
+            EnvKey::Argon2MemoryKib => "ARGON2_MEMORY_KIB",
+            EnvKey::Argon2Iterations => "ARGON2_ITERATIONS",
+            EnvKey::Argon2Parallelism => "ARGON2_PARALLELISM",
+            EnvKey::PasswordPepper => "PASSWORD_PEPPER",
+            EnvKey::PasswordMinLength => "PASSWORD_MIN_LENGTH",
+            EnvKey::ImpersonationTtlMinutes => "IMPERSONATION_TTL_MINUTES",
+            EnvKey::PublishScheduleIntervalSecs => "PUBLISH_SCHEDULE_INTERVAL_SECS",
         }
     }
 }
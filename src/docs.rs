@@ -7,8 +7,65 @@ use crate::modules::auth::handler::*;
     paths(
         crate::modules::auth::handler::register,
         crate::modules::auth::handler::login,
+        crate::modules::auth::handler::login_mfa,
+        crate::modules::auth::handler::oidc_login,
+        crate::modules::auth::handler::oidc_callback,
         crate::modules::auth::handler::logout,
         crate::modules::auth::handler::refresh,
+        crate::modules::auth::handler::device_code,
+        crate::modules::auth::handler::device_token,
+        crate::modules::auth::handler::get_device,
+        crate::modules::auth::handler::approve_device,
+        crate::modules::auth::handler::verify_email,
+        crate::modules::auth::handler::resend_verification,
+        crate::modules::auth::handler::forgot_password,
+        crate::modules::auth::handler::reset_password,
+        crate::modules::auth::handler::setup_totp,
+        crate::modules::auth::handler::enable_totp,
+        crate::modules::auth::handler::disable_totp,
+        crate::modules::auth::handler::regenerate_recovery_codes,
+        crate::modules::auth::handler::list_lockouts,
+        crate::modules::auth::handler::unlock_login,
+        crate::modules::auth::handler::list_sessions,
+        crate::modules::auth::handler::revoke_session,
+        crate::modules::auth::handler::revoke_other_sessions,
+        crate::modules::auth::handler::jwks,
+        crate::modules::user::handler::get_profile,
+        crate::modules::user::handler::update_profile,
+        crate::modules::user::handler::upload_avatar,
+        crate::modules::user::handler::get_avatar,
+        crate::modules::user::handler::get_preferences,
+        crate::modules::user::handler::update_preferences,
+        crate::modules::user::handler::change_password,
+        crate::modules::jobs::handler::request_export,
+        crate::modules::jobs::handler::get_export,
+        crate::modules::jobs::handler::download_export,
+        crate::modules::jobs::handler::delete_account,
+        crate::modules::user::handler::assign_role,
+        crate::modules::user::handler::list_users,
+        crate::modules::user::handler::get_user,
+        crate::modules::user::handler::suspend_user,
+        crate::modules::user::handler::unsuspend_user,
+        crate::modules::user::handler::force_logout,
+        crate::modules::user::handler::delete_user,
+        crate::modules::user::handler::impersonate_user,
+        crate::modules::profile::handler::list_profiles,
+        crate::modules::profile::handler::get_active_profile,
+        crate::modules::profile::handler::create_profile,
+        crate::modules::profile::handler::edit_profile,
+        crate::modules::profile::handler::delete_profile,
+        crate::modules::profile::handler::select_profile,
+        crate::modules::rating::handler::list_maturity_ratings,
+        crate::modules::rating::handler::create_maturity_rating,
+        crate::modules::role::handler::list_roles,
+        crate::modules::role::handler::list_permissions,
+        crate::modules::role::handler::create_role,
+        crate::modules::role::handler::update_role,
+        crate::modules::role::handler::delete_role,
+        crate::modules::audit::handler::list_audit_logs,
+        crate::modules::api_key::handler::create_api_key,
+        crate::modules::api_key::handler::list_api_keys,
+        crate::modules::api_key::handler::revoke_api_key,
         crate::modules::genre::handler::list_genres,
         crate::modules::genre::handler::create_genre,
         crate::modules::genre::handler::get_genre,
@@ -26,8 +83,10 @@ use crate::modules::auth::handler::*;
         crate::modules::content::handler::create_episode,
         // Update & Delete
         crate::modules::content::handler::update_movie,
+        crate::modules::content::handler::update_movie_visibility,
         crate::modules::content::handler::delete_movie,
         crate::modules::content::handler::update_series,
+        crate::modules::content::handler::update_series_visibility,
         crate::modules::content::handler::delete_series,
         crate::modules::content::handler::update_season,
         crate::modules::content::handler::delete_season,
@@ -35,14 +94,69 @@ use crate::modules::auth::handler::*;
         crate::modules::content::handler::delete_episode,
         // Streaming
         crate::modules::content::stream_handler::stream_movie,
+        // Search
+        crate::modules::search::handler::search,
+        crate::modules::search::handler::suggest,
     ),
     components(
         schemas(
             crate::common::response::ApiResponse<String>,
+            crate::common::error::ProblemDetails,
+            crate::common::pagination::PageLinks,
             crate::modules::auth::dto::LoginRequest,
             crate::modules::auth::dto::RegisterRequest,
             crate::modules::auth::dto::AuthResponse,
             crate::modules::auth::dto::UserResponse,
+            crate::modules::auth::dto::SessionResponse,
+            crate::modules::auth::dto::VerifyEmailRequest,
+            crate::modules::auth::dto::ResendVerificationRequest,
+            crate::modules::auth::dto::ForgotPasswordRequest,
+            crate::modules::auth::dto::ResetPasswordRequest,
+            crate::modules::auth::dto::UnlockLoginRequest,
+            crate::modules::auth::model::LoginLockoutEvent,
+            crate::modules::auth::dto::MfaChallengeResponse,
+            crate::modules::auth::dto::MfaLoginRequest,
+            crate::modules::auth::dto::MfaCodeRequest,
+            crate::modules::auth::dto::TotpSetupResponse,
+            crate::modules::auth::dto::RecoveryCodesResponse,
+            crate::modules::auth::dto::DeviceCodeRequest,
+            crate::modules::auth::dto::DeviceCodeResponse,
+            crate::modules::auth::dto::DeviceTokenRequest,
+            crate::modules::auth::dto::DeviceApprovalRequest,
+            crate::modules::auth::dto::DeviceAuthorizationResponse,
+            // User
+            crate::modules::user::dto::ChangePasswordRequest,
+            crate::modules::user::dto::UpdateProfileRequest,
+            crate::modules::user::dto::UpdatePreferencesRequest,
+            crate::modules::user::dto::AdminUserResponse,
+            crate::modules::user::dto::AdminUserPage,
+            crate::modules::user::dto::SuspendUserRequest,
+            crate::modules::user::dto::AdminActionRequest,
+            crate::modules::user::dto::ImpersonateRequest,
+            crate::modules::user::dto::ImpersonationResponse,
+            crate::modules::jobs::dto::AccountJobResponse,
+            crate::modules::jobs::dto::DeleteAccountRequest,
+            crate::modules::profile::dto::CreateViewerProfileRequest,
+            crate::modules::profile::dto::UpdateViewerProfileRequest,
+            crate::modules::profile::dto::SelectProfileRequest,
+            crate::modules::profile::dto::ProfileResponse,
+            crate::modules::profile::dto::SelectProfileResponse,
+            crate::modules::rating::model::MaturityRating,
+            crate::modules::rating::dto::CreateMaturityRatingRequest,
+            crate::modules::user::model::UserPreferences,
+            // Roles
+            crate::modules::role::model::Role,
+            crate::modules::role::model::Permission,
+            crate::modules::role::dto::CreateRoleRequest,
+            crate::modules::role::dto::UpdateRoleRequest,
+            crate::modules::role::dto::AssignRoleRequest,
+            // Audit
+            crate::modules::audit::model::AuditLog,
+            crate::modules::audit::dto::AuditLogPage,
+            // API keys
+            crate::modules::api_key::dto::CreateApiKeyRequest,
+            crate::modules::api_key::dto::ApiKeyResponse,
+            crate::modules::api_key::dto::CreatedApiKeyResponse,
             // Genre
             crate::modules::genre::dto::CreateGenreRequest,
             crate::modules::genre::dto::UpdateGenreRequest,
@@ -51,6 +165,7 @@ use crate::modules::auth::handler::*;
             // Content
             crate::modules::content::dto::CreateMovieRequest,
             crate::modules::content::dto::UpdateMovieRequest,
+            crate::modules::content::dto::UpdateVisibilityRequest,
             crate::modules::content::dto::MovieResponse,
             crate::modules::content::dto::CreateSeriesRequest,
             crate::modules::content::dto::UpdateSeriesRequest,
@@ -66,12 +181,26 @@ use crate::modules::auth::handler::*;
             crate::modules::content::model::Season,
             crate::modules::content::model::Episode,
             crate::modules::content::model::ContentStatus,
+            // Search
+            crate::modules::search::dto::SearchHit,
+            crate::modules::search::dto::SearchResponse,
+            crate::modules::search::dto::Suggestion,
+            crate::modules::search::dto::SuggestResponse,
         )
     ),
     tags(
         (name = "Auth", description = "Authentication endpoints"),
+        (name = "User", description = "Account endpoints for the logged-in user"),
+        (name = "Roles", description = "Roles, permissions and role assignment"),
+        (name = "Account Data", description = "Personal data export and account deletion"),
+        (name = "Profiles", description = "Viewer profiles sharing one account"),
+        (name = "Maturity Ratings", description = "Rating systems and certifications used by parental controls"),
+        (name = "Users", description = "Admin user management: search, suspension, forced logout, deletion and impersonation"),
+        (name = "Audit", description = "Audit log of admin changes"),
+        (name = "API Keys", description = "Long-lived keys for scripts and integrations"),
         (name = "Genre", description = "Genre management endpoints"),
-        (name = "Content", description = "Movie and Series management endpoints")
+        (name = "Content", description = "Movie and Series management endpoints"),
+        (name = "Search", description = "Search across movies, series and episodes")
     ),
     security(
         ("bearer_auth" = [])
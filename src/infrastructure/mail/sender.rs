@@ -0,0 +1,78 @@
+use anyhow::Result;
+use futures_util::future::BoxFuture;
+use std::path::PathBuf;
+use std::sync::Arc;
+use time::OffsetDateTime;
+use tokio::fs;
+use tracing::info;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct MailMessage {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// Outgoing mail transport. Implement this for a real provider (SMTP, SES, ...);
+/// the log and file senders below are meant for local development.
+pub trait MailSender: Send + Sync {
+    fn send<'a>(&'a self, message: &'a MailMessage) -> BoxFuture<'a, Result<()>>;
+}
+
+/// Writes every message to the application log.
+pub struct LogMailSender;
+
+impl MailSender for LogMailSender {
+    fn send<'a>(&'a self, message: &'a MailMessage) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            info!(
+                "📧 Mail to {} | {}\n{}",
+                message.to, message.subject, message.body
+            );
+            Ok(())
+        })
+    }
+}
+
+/// Writes every message as a `.eml`-style text file into a local outbox directory.
+pub struct FileMailSender {
+    outbox_dir: PathBuf,
+}
+
+impl FileMailSender {
+    pub fn new(outbox_dir: &str) -> Self {
+        Self {
+            outbox_dir: PathBuf::from(outbox_dir),
+        }
+    }
+}
+
+impl MailSender for FileMailSender {
+    fn send<'a>(&'a self, message: &'a MailMessage) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            fs::create_dir_all(&self.outbox_dir).await?;
+
+            let now = OffsetDateTime::now_utc();
+            let path = self
+                .outbox_dir
+                .join(format!("{}-{}.eml", now.unix_timestamp(), Uuid::new_v4().as_simple()));
+            let contents = format!(
+                "To: {}\nSubject: {}\nDate: {}\n\n{}\n",
+                message.to, message.subject, now, message.body
+            );
+
+            fs::write(&path, contents).await?;
+            info!("📧 Mail to {} written to {}", message.to, path.display());
+            Ok(())
+        })
+    }
+}
+
+/// Builds the sender selected by `MAIL_DRIVER` ("log" or "file").
+pub fn from_config(driver: &str, outbox_dir: &str) -> Arc<dyn MailSender> {
+    match driver {
+        "file" => Arc::new(FileMailSender::new(outbox_dir)),
+        _ => Arc::new(LogMailSender),
+    }
+}
@@ -2,4 +2,6 @@ pub mod db;
 pub mod redis;
 pub mod storage;
 pub mod queue;
+pub mod mail;
+pub mod oidc;
 pub mod observability;
@@ -0,0 +1,195 @@
+use anyhow::{anyhow, Result};
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+use tokio::sync::OnceCell;
+use tracing::info;
+use url::Url;
+
+/// Subset of the provider metadata from `/.well-known/openid-configuration`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProviderMetadata {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub jwks_uri: String,
+    pub userinfo_endpoint: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    id_token: String,
+}
+
+/// Claims of a verified ID token (merged with userinfo where the token lacks them).
+#[derive(Debug, Clone, Deserialize)]
+pub struct IdTokenClaims {
+    pub sub: String,
+    pub email: Option<String>,
+    #[serde(default)]
+    pub email_verified: bool,
+    pub name: Option<String>,
+    pub preferred_username: Option<String>,
+    pub nonce: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UserInfo {
+    sub: String,
+    email: Option<String>,
+    #[serde(default)]
+    email_verified: bool,
+    name: Option<String>,
+    preferred_username: Option<String>,
+}
+
+/// Authorization code + PKCE client for a single OpenID Connect provider.
+///
+/// Provider metadata is discovered on first use and cached, so the server can
+/// start before the identity provider is reachable. The JWKS is fetched for
+/// every ID token, which picks up key rotation without extra bookkeeping.
+#[derive(Clone)]
+pub struct OidcClient {
+    http: reqwest::Client,
+    issuer_url: String,
+    client_id: String,
+    client_secret: Option<String>,
+    redirect_url: String,
+    scopes: String,
+    metadata: std::sync::Arc<OnceCell<ProviderMetadata>>,
+}
+
+impl OidcClient {
+    pub fn new(
+        issuer_url: &str,
+        client_id: &str,
+        client_secret: Option<&str>,
+        redirect_url: &str,
+        scopes: &str,
+    ) -> Self {
+        info!("🔐 OIDC login enabled for issuer {}", issuer_url);
+        Self {
+            http: reqwest::Client::new(),
+            issuer_url: issuer_url.trim_end_matches('/').to_string(),
+            client_id: client_id.to_string(),
+            client_secret: client_secret.map(|s| s.to_string()),
+            redirect_url: redirect_url.to_string(),
+            scopes: scopes.to_string(),
+            metadata: Default::default(),
+        }
+    }
+
+    /// Stable identifier of the provider, used to key linked identities.
+    pub fn provider(&self) -> &str {
+        &self.issuer_url
+    }
+
+    async fn metadata(&self) -> Result<&ProviderMetadata> {
+        self.metadata
+            .get_or_try_init(|| async {
+                let url = format!("{}/.well-known/openid-configuration", self.issuer_url);
+                let metadata: ProviderMetadata = self.http.get(&url).send().await?.error_for_status()?.json().await?;
+
+                if metadata.issuer.trim_end_matches('/') != self.issuer_url {
+                    return Err(anyhow!("OIDC issuer mismatch: expected {}, got {}", self.issuer_url, metadata.issuer));
+                }
+                Ok(metadata)
+            })
+            .await
+    }
+
+    /// URL of the provider's login page for this request.
+    pub async fn authorization_url(&self, state: &str, nonce: &str, code_challenge: &str) -> Result<String> {
+        let metadata = self.metadata().await?;
+        let mut url = Url::parse(&metadata.authorization_endpoint)?;
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &self.client_id)
+            .append_pair("redirect_uri", &self.redirect_url)
+            .append_pair("scope", &self.scopes)
+            .append_pair("state", state)
+            .append_pair("nonce", nonce)
+            .append_pair("code_challenge", code_challenge)
+            .append_pair("code_challenge_method", "S256");
+        Ok(url.to_string())
+    }
+
+    /// Exchanges the authorization code and returns the verified ID token claims.
+    pub async fn exchange_code(&self, code: &str, code_verifier: &str, nonce: &str) -> Result<IdTokenClaims> {
+        let metadata = self.metadata().await?;
+
+        let mut form = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", self.redirect_url.as_str()),
+            ("client_id", self.client_id.as_str()),
+            ("code_verifier", code_verifier),
+        ];
+        if let Some(secret) = &self.client_secret {
+            form.push(("client_secret", secret.as_str()));
+        }
+
+        let tokens: TokenResponse = self
+            .http
+            .post(&metadata.token_endpoint)
+            .form(&form)
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| anyhow!("OIDC token exchange failed: {}", e))?
+            .json()
+            .await?;
+
+        let mut claims = self.verify_id_token(metadata, &tokens.id_token).await?;
+        if claims.nonce.as_deref() != Some(nonce) {
+            return Err(anyhow!("OIDC nonce mismatch"));
+        }
+
+        // Some providers only put profile claims in the userinfo response
+        if claims.email.is_none()
+            && let Some(endpoint) = &metadata.userinfo_endpoint
+        {
+            let info: UserInfo = self
+                .http
+                .get(endpoint)
+                .bearer_auth(&tokens.access_token)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+
+            if info.sub == claims.sub {
+                claims.email = info.email;
+                claims.email_verified = info.email_verified;
+                claims.name = claims.name.or(info.name);
+                claims.preferred_username = claims.preferred_username.or(info.preferred_username);
+            }
+        }
+
+        Ok(claims)
+    }
+
+    async fn verify_id_token(&self, metadata: &ProviderMetadata, id_token: &str) -> Result<IdTokenClaims> {
+        let header = decode_header(id_token)?;
+        if matches!(header.alg, Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512) {
+            return Err(anyhow!("Unsupported ID token algorithm {:?}", header.alg));
+        }
+
+        let jwks: JwkSet = self.http.get(&metadata.jwks_uri).send().await?.error_for_status()?.json().await?;
+        let jwk = match &header.kid {
+            Some(kid) => jwks.find(kid),
+            None if jwks.keys.len() == 1 => jwks.keys.first(),
+            None => None,
+        }
+        .ok_or_else(|| anyhow!("No matching key for ID token"))?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&metadata.issuer]);
+        validation.set_audience(&[&self.client_id]);
+
+        let data = decode::<IdTokenClaims>(id_token, &DecodingKey::from_jwk(jwk)?, &validation)?;
+        Ok(data.claims)
+    }
+}
@@ -12,6 +12,8 @@ mod routes;
 mod state;
 mod workers;
 
+use common::jwt::JwtKeys;
+use common::password::Passwords;
 use config::settings::AppConfig;
 use infrastructure::db::pool::connect_to_db;
 use infrastructure::redis::client::RedisService;
@@ -68,6 +70,7 @@ async fn main() {
     let buckets = vec![
         &config.minio_bucket,
         &config.minio_bucket_thumbnails,
+        &config.minio_bucket_exports,
     ];
     
     for bucket in buckets {
@@ -81,16 +84,71 @@ async fn main() {
         .await
         .expect("Failed to connect to RabbitMQ");
 
-    // 6. Create App State
-    let state = AppState::new(config.clone(), db_pool, redis_service, storage_service, queue_service);
+    // 6. Load JWT signing/verification keys and password hashing settings
+    let jwt_keys = JwtKeys::load(&config.jwt_keys_dir, config.jwt_active_kid.as_deref(), config.jwt_allow_ephemeral_key)
+        .expect("Failed to load JWT keys");
 
-    // 7. Start Workers
+    let passwords = Passwords::new(
+        config.argon2_memory_kib,
+        config.argon2_iterations,
+        config.argon2_parallelism,
+        config.password_pepper.as_deref(),
+        config.password_min_length,
+    )
+    .expect("Invalid password hashing settings");
+
+    // 7. Mail transport
+    let mailer = infrastructure::mail::sender::from_config(&config.mail_driver, &config.mail_outbox_dir);
+
+    // 8. Social login (optional)
+    let oidc = config.oidc_issuer_url.as_deref().map(|issuer| {
+        infrastructure::oidc::client::OidcClient::new(
+            issuer,
+            &config.oidc_client_id,
+            config.oidc_client_secret.as_deref(),
+            &config.oidc_redirect_url,
+            &config.oidc_scopes,
+        )
+    });
+
+    // 9. Create App State
+    let state = AppState::new(
+        config.clone(),
+        db_pool,
+        redis_service,
+        storage_service,
+        queue_service,
+        jwt_keys,
+        passwords,
+        mailer,
+        oidc,
+    );
+
+    // 10. Start Workers
     let worker_state = state.clone();
     tokio::spawn(async move {
         workers::transcoder::start_transcoder_worker(worker_state).await;
     });
 
-    // 8. Start Server
+    let account_worker_state = state.clone();
+    tokio::spawn(async move {
+        workers::account::start_account_worker(account_worker_state).await;
+    });
+
+    let scheduler_state = state.clone();
+    tokio::spawn(async move {
+        workers::scheduler::start_publish_scheduler(scheduler_state).await;
+    });
+
+    let suggest_state = state.clone();
+    tokio::spawn(async move {
+        match modules::search::service::SearchService::rebuild_suggestions(&suggest_state).await {
+            Ok(count) => info!("🔎 Indexed {} search suggestions", count),
+            Err(e) => tracing::error!("Failed to rebuild search suggestions: {}", e),
+        }
+    });
+
+    // 11. Start Server
     let app = app::create_app(state).await;
     
     let addr = format!("0.0.0.0:{}", config.server_port);
@@ -98,5 +156,10 @@ async fn main() {
     
     info!("✅ Server running on http://{}", addr);
     
-    axum::serve(listener, app).await.unwrap();
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .await
+    .unwrap();
 }
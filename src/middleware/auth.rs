@@ -1,68 +1,259 @@
+use crate::modules::audit::model::{AuditContext, AuditEntry};
+use crate::modules::audit::service::AuditService;
 use crate::modules::auth::dto::TokenClaims;
+use crate::modules::auth::repository::AuthRepository;
+use crate::modules::api_key::model::{ApiKeyContext, API_KEY_PREFIX};
+use crate::modules::api_key::repository::ApiKeyRepository;
+use crate::modules::role::service::RoleService;
+use crate::modules::user::repository::UserRepository;
+use crate::common::security;
 use crate::state::AppState;
-use crate::common::response::ApiError;
+use crate::common::error::AppError;
 use axum::{
-    extract::{Request, State},
-    http::{header, StatusCode},
+    extract::{Extension, FromRequestParts, OriginalUri, Request, State},
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode, Uri},
     middleware::Next,
     response::{IntoResponse, Response},
 };
-use jsonwebtoken::{decode, DecodingKey, Validation};
+use jsonwebtoken::get_current_timestamp;
 use redis::AsyncCommands;
 
+/// Set on every response to a request made with an impersonation token.
+pub const IMPERSONATED_BY_HEADER: &str = "x-impersonated-by";
+
 pub async fn auth_middleware(
     State(state): State<AppState>,
     mut req: Request,
     next: Next,
-) -> Result<Response, ApiError> {
+) -> Result<Response, AppError> {
+    let claims = authenticate(&state, req.headers()).await?;
+
+    if claims.act.is_some() {
+        return Ok(impersonated_request(&state, claims, req, next).await);
+    }
+
+    // Inject claims into request extensions
+    req.extensions_mut().insert(claims);
+
+    Ok(next.run(req).await)
+}
+
+/// Runs a request made with an impersonation token. Only reads are let through;
+/// every request, allowed or not, is audited under the admin's id, and the
+/// response carries `x-impersonated-by` so clients can show that it is not the user.
+async fn impersonated_request(state: &AppState, claims: TokenClaims, mut req: Request, next: Next) -> Response {
+    req.extensions_mut().insert(claims.clone());
+    let method = req.method().clone();
+    let uri = req.extensions().get::<OriginalUri>().map_or_else(|| req.uri().clone(), |u| u.0.clone());
+
+    let (mut parts, body) = req.into_parts();
+    let Ok(audit) = AuditContext::from_request_parts(&mut parts, state).await;
+    let req = Request::from_parts(parts, body);
+
+    let mut response = if is_read_only(&method) {
+        next.run(req).await
+    } else {
+        AppError::forbidden("Impersonation sessions are read-only").into_response()
+    };
+
+    record_impersonated_request(state, &audit, &claims, &method, &uri, Some(response.status())).await;
+    if let Some(act) = &claims.act
+        && let Ok(value) = HeaderValue::from_str(&act.sub.to_string())
+    {
+        response.headers_mut().insert(IMPERSONATED_BY_HEADER, value);
+    }
+    response
+}
+
+pub fn is_read_only(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+
+/// Audit entry for one request made while impersonating `claims.sub`.
+pub async fn record_impersonated_request(
+    state: &AppState,
+    audit: &AuditContext,
+    claims: &TokenClaims,
+    method: &Method,
+    uri: &Uri,
+    status: Option<StatusCode>,
+) {
+    let entry = AuditEntry::new("impersonation.request", "user", Some(claims.sub)).after(&serde_json::json!({
+        "method": method.as_str(),
+        "path": uri.path_and_query().map_or(uri.path(), |p| p.as_str()),
+        "status": status.map(|s| s.as_u16()),
+        "session_id": claims.sid,
+    }));
+    AuditService::record(state, audit, entry).await;
+}
+
+/// Guards the stream routes. When `REQUIRE_VERIFIED_EMAIL_STREAM` is enabled the caller
+/// must be logged in with a verified email address; otherwise streaming stays public.
+pub async fn verified_email_stream_guard(
+    State(state): State<AppState>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    if !state.config.require_verified_email_stream {
+        return Ok(next.run(req).await);
+    }
+
+    let claims = authenticate(&state, req.headers()).await?;
+    let impersonated = claims.act.is_some();
+
+    let user = AuthRepository::find_user_by_id(&state.db, claims.sub)
+        .await?
+        .ok_or_else(|| AppError::unauthorized("User not found"))?;
+
+    if user.email_verified_at.is_none() {
+        return Err(AppError::forbidden("Email address not verified"));
+    }
+
+    if impersonated {
+        return Ok(impersonated_request(&state, claims, req, next).await);
+    }
+
+    req.extensions_mut().insert(claims);
+
+    Ok(next.run(req).await)
+}
+
+/// Rejects requests made with an API key or an impersonation token. Used for account
+/// management (sessions, passwords, 2FA, keys), which must stay behind the user's own login.
+pub async fn session_only(
+    Extension(claims): Extension<TokenClaims>,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    if claims.api_key.is_some() {
+        return Err(AppError::forbidden("Not available with an API key"));
+    }
+    if claims.act.is_some() {
+        return Err(AppError::forbidden("Not available while impersonating"));
+    }
+
+    Ok(next.run(req).await)
+}
+
+/// Requires API keys to carry `scope`; JWT logins are not restricted by scopes.
+///
+/// `.route_layer(middleware::from_fn(|req, next| require_scope(SCOPE_CONTENT_WRITE, req, next)))`
+pub async fn require_scope(scope: &'static str, req: Request, next: Next) -> Result<Response, AppError> {
+    let claims = req
+        .extensions()
+        .get::<TokenClaims>()
+        .ok_or_else(|| AppError::unauthorized("Missing claims"))?;
+
+    if let Some(api_key) = &claims.api_key
+        && !api_key.scopes.iter().any(|s| s == scope)
+    {
+        return Err(AppError::forbidden(format!("API key lacks the '{}' scope", scope)));
+    }
+
+    Ok(next.run(req).await)
+}
+
+/// Validates the Bearer access token (or API key) of a request and returns its claims.
+pub async fn authenticate(state: &AppState, headers: &HeaderMap) -> Result<TokenClaims, AppError> {
     // 1. Extract token from header
-    let token = req
-        .headers()
+    let token = headers
         .get(header::AUTHORIZATION)
         .and_then(|auth_header| auth_header.to_str().ok())
-        .and_then(|auth_value| {
-            if auth_value.starts_with("Bearer ") {
-                Some(auth_value[7..].to_owned())
-            } else {
-                None
-            }
-        });
+        .and_then(|auth_value| auth_value.strip_prefix("Bearer "))
+        .or_else(|| headers.get("x-api-key").and_then(|v| v.to_str().ok()))
+        .map(|token| token.to_owned());
 
     let token = match token {
         Some(t) => t,
-        None => return Err(ApiError("Unauthorized: Missing or invalid token".to_string(), StatusCode::UNAUTHORIZED)),
+        None => return Err(AppError::unauthorized("Missing or invalid token")),
     };
 
+    if token.starts_with(API_KEY_PREFIX) {
+        return authenticate_api_key(state, &token).await;
+    }
+
     // 2. Check if token is blocked in Redis
-    let mut redis = state
-        .redis
-        .get_conn()
-        .await
-        .map_err(|_| ApiError("Internal Server Error: Redis unavailable".to_string(), StatusCode::INTERNAL_SERVER_ERROR))?;
+    let mut redis = state.redis.get_conn().await?;
 
-    let is_blocked: bool = redis
-        .exists(format!("blocked_token:{}", token))
-        .await
-        .map_err(|_| ApiError("Internal Server Error: Redis error".to_string(), StatusCode::INTERNAL_SERVER_ERROR))?;
+    let is_blocked: bool = redis.exists(format!("blocked_token:{}", token)).await?;
 
     if is_blocked {
-        return Err(ApiError("Unauthorized: Token is blocked/revoked".to_string(), StatusCode::UNAUTHORIZED));
+        return Err(AppError::unauthorized("Token is blocked/revoked"));
     }
 
-    // 3. Verify JWT
-    // Use secret from config
-    let secret = &state.config.jwt_secret;
-    
-    let claims = decode::<TokenClaims>(
-        &token,
-        &DecodingKey::from_secret(secret.as_bytes()),
-        &Validation::default(),
-    )
-    .map_err(|_| ApiError("Unauthorized: Invalid token signature".to_string(), StatusCode::UNAUTHORIZED))?
-    .claims;
-
-    // 4. Inject claims into request extensions
-    req.extensions_mut().insert(claims);
+    // 3. Verify JWT against the key named by its `kid`
+    let claims = state
+        .jwt
+        .verify::<TokenClaims>(&token)
+        .map_err(|_| AppError::unauthorized("Invalid token signature"))?;
 
-    Ok(next.run(req).await)
+    // 4. Reject tokens whose session was revoked (logout, "log out other devices")
+    let session_alive = AuthRepository::session_exists(&mut redis, claims.sid).await?;
+
+    if !session_alive {
+        return Err(AppError::unauthorized("Session has been revoked"));
+    }
+
+    // 5. Reject suspended accounts; suspending also revokes sessions, this covers the race in between
+    let suspended = UserRepository::is_suspended(&mut redis, claims.sub).await?;
+
+    if suspended {
+        return Err(AppError::forbidden("Account is suspended"));
+    }
+
+    Ok(claims)
+}
+
+/// Resolves an API key to claims for its owner and counts the request against the key's quota.
+/// Requests rejected for an exhausted quota are not counted.
+async fn authenticate_api_key(state: &AppState, key: &str) -> Result<TokenClaims, AppError> {
+    let api_key = ApiKeyRepository::find_active_by_hash(&state.db, &security::hash_token(key))
+        .await?
+        .ok_or_else(|| AppError::unauthorized("Invalid, expired or revoked API key"))?;
+
+    let user = AuthRepository::find_user_by_id(&state.db, api_key.user_id)
+        .await?
+        .ok_or_else(|| AppError::unauthorized("User not found"))?;
+
+    if user.is_suspended() {
+        return Err(AppError::forbidden("Account is suspended"));
+    }
+
+    let mut redis = state.redis.get_conn().await?;
+
+    // Count first and compare the result, so concurrent requests can't all pass a check made before any
+    // of them was counted. A rejected request is taken back off, so only accepted requests count. Keys
+    // without a quota are counted too, the figure is shown as `usage_today` in the key list.
+    let usage = ApiKeyRepository::increment_daily_usage(&mut redis, api_key.id).await?;
+    if let Some(quota) = api_key.daily_quota
+        && usage > quota as u64
+    {
+        ApiKeyRepository::decrement_daily_usage(&mut redis, api_key.id).await?;
+        return Err(AppError::rate_limited("API key daily quota exceeded", None));
+    }
+
+    ApiKeyRepository::record_usage(&state.db, api_key.id).await?;
+
+    let (permissions, mfa_required) = RoleService::resolve_permissions(state, &user.role, api_key.mfa_verified)
+        .await?;
+
+    let now = get_current_timestamp() as usize;
+    Ok(TokenClaims {
+        sub: user.id,
+        role: user.role,
+        sid: api_key.id,
+        mfa: api_key.mfa_verified,
+        permissions,
+        mfa_required,
+        profile_id: None,
+        act: None,
+        exp: api_key
+            .expires_at
+            .map(|t| t.unix_timestamp() as usize)
+            .unwrap_or(usize::MAX),
+        iat: now,
+        api_key: Some(ApiKeyContext {
+            scopes: api_key.scopes,
+        }),
+    })
 }
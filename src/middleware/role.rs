@@ -1,19 +1,30 @@
 use crate::modules::auth::dto::TokenClaims;
-use crate::common::response::ApiError;
+use crate::common::error::AppError;
 use axum::{
-    extract::{Request, Extension},
-    http::StatusCode,
+    extract::Request,
     middleware::Next,
-    response::{IntoResponse, Response},
+    response::Response,
 };
 
-pub async fn admin_guard(
-    Extension(claims): Extension<TokenClaims>,
+/// Rejects requests whose token does not carry `permission`.
+/// Permissions are resolved from the role when the token is issued (see `modules::role`).
+pub async fn require_permission(
+    permission: &'static str,
     req: Request,
     next: Next,
-) -> Result<Response, ApiError> {
-    if claims.role != "ADMIN" {
-        return Err(ApiError("Forbidden: Admin access required".to_string(), StatusCode::FORBIDDEN));
+) -> Result<Response, AppError> {
+    let claims = req
+        .extensions()
+        .get::<TokenClaims>()
+        .ok_or_else(|| AppError::unauthorized("Missing claims"))?;
+
+    // Accounts that need 2FA may still log in without it, but only to enroll
+    if claims.mfa_required {
+        return Err(AppError::forbidden("Two-factor authentication is required for this account"));
+    }
+
+    if !claims.permissions.iter().any(|p| p == permission) {
+        return Err(AppError::forbidden(format!("Missing permission '{}'", permission)));
     }
 
     Ok(next.run(req).await)
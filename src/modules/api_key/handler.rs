@@ -0,0 +1,101 @@
+use super::dto::{ApiKeyResponse, CreateApiKeyRequest, CreatedApiKeyResponse};
+use super::service::ApiKeyService;
+use crate::common::response::{ApiResponse, ApiSuccess};
+use crate::modules::auth::dto::TokenClaims;
+use crate::modules::audit::model::{AuditContext, AuditEntry};
+use crate::modules::audit::service::AuditService;
+use crate::state::AppState;
+use axum::{
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+/// Create an API key
+#[utoipa::path(
+    post,
+    path = "/api/v1/api-keys",
+    request_body = CreateApiKeyRequest,
+    responses(
+        (status = 201, description = "API key created; the key is only shown once", body = ApiResponse<CreatedApiKeyResponse>),
+        (status = 400, description = "Invalid scopes or limit reached"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "API Keys"
+)]
+pub async fn create_api_key(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    audit: AuditContext,
+    Json(payload): Json<CreateApiKeyRequest>,
+) -> impl IntoResponse {
+    match ApiKeyService::create(state.clone(), claims.sub, claims.mfa, payload).await {
+        Ok(key) => {
+            // Only the public part of the key, never the secret
+            let entry = AuditEntry::new("api_key.create", "api_key", Some(key.api_key.id)).after(&key.api_key);
+            AuditService::record(&state, &audit, entry).await;
+            ApiSuccess(ApiResponse::success(key, "API key created"), StatusCode::CREATED).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// List API keys of the current user
+#[utoipa::path(
+    get,
+    path = "/api/v1/api-keys",
+    responses(
+        (status = 200, description = "API keys with usage", body = ApiResponse<Vec<ApiKeyResponse>>),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "API Keys"
+)]
+pub async fn list_api_keys(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+) -> impl IntoResponse {
+    match ApiKeyService::list(state, claims.sub).await {
+        Ok(keys) => ApiSuccess(ApiResponse::success(keys, "API keys retrieved"), StatusCode::OK).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Revoke an API key
+#[utoipa::path(
+    delete,
+    path = "/api/v1/api-keys/{id}",
+    params(
+        ("id" = Uuid, Path, description = "API key ID")
+    ),
+    responses(
+        (status = 200, description = "API key revoked", body = ApiResponse<String>),
+        (status = 404, description = "API key not found"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "API Keys"
+)]
+pub async fn revoke_api_key(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    audit: AuditContext,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match ApiKeyService::revoke(state.clone(), claims.sub, id).await {
+        Ok(_) => {
+            AuditService::record(&state, &audit, AuditEntry::new("api_key.revoke", "api_key", Some(id))).await;
+            ApiSuccess(ApiResponse::success((), "API key revoked"), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
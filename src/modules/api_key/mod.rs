@@ -0,0 +1,22 @@
+use axum::Router;
+use axum::routing::{delete, get};
+use crate::state::AppState;
+use axum::middleware;
+
+pub mod dto;
+pub mod handler;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+pub fn router(state: AppState) -> axum::Router<AppState> {
+    // Keys are managed from a login session; a key cannot mint or revoke keys
+    Router::new()
+        .route("/", get(handler::list_api_keys).post(handler::create_api_key))
+        .route("/{id}", delete(handler::revoke_api_key))
+        .route_layer(middleware::from_fn(crate::middleware::auth::session_only))
+        .route_layer(middleware::from_fn_with_state(
+            state,
+            crate::middleware::auth::auth_middleware
+        ))
+}
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Every key starts with this, which is how the auth middleware tells keys from JWTs.
+pub const API_KEY_PREFIX: &str = "hmk_";
+
+pub const SCOPE_PROFILE_READ: &str = "profile:read";
+pub const SCOPE_CONTENT_WRITE: &str = "content:write";
+pub const SCOPE_GENRES_WRITE: &str = "genres:write";
+
+pub const SCOPES: &[&str] = &[SCOPE_PROFILE_READ, SCOPE_CONTENT_WRITE, SCOPE_GENRES_WRITE];
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub key_prefix: String,
+    pub scopes: Vec<String>,
+    pub daily_quota: Option<i32>,
+    pub mfa_verified: bool,
+    pub usage_count: i64,
+    #[serde(with = "time::serde::iso8601::option")]
+    pub last_used_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::iso8601::option")]
+    pub expires_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::iso8601::option")]
+    pub revoked_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::iso8601")]
+    pub created_at: OffsetDateTime,
+}
+
+/// Attached to the request claims when the caller authenticated with an API key.
+/// The key id is the `sid` of those claims.
+#[derive(Debug, Clone)]
+pub struct ApiKeyContext {
+    pub scopes: Vec<String>,
+}
@@ -0,0 +1,137 @@
+use super::model::ApiKey;
+use anyhow::Result;
+use redis::aio::MultiplexedConnection;
+use redis::AsyncCommands;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+pub struct ApiKeyRepository;
+
+impl ApiKeyRepository {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        pool: &PgPool,
+        user_id: Uuid,
+        name: &str,
+        key_prefix: &str,
+        key_hash: &str,
+        scopes: &[String],
+        daily_quota: Option<i32>,
+        mfa_verified: bool,
+        expires_at: Option<OffsetDateTime>,
+    ) -> Result<ApiKey> {
+        let key = sqlx::query_as!(
+            ApiKey,
+            r#"
+            INSERT INTO api_keys (user_id, name, key_prefix, key_hash, scopes, daily_quota, mfa_verified, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id, user_id, name, key_prefix, scopes, daily_quota, mfa_verified,
+                      usage_count, last_used_at, expires_at, revoked_at, created_at
+            "#,
+            user_id,
+            name,
+            key_prefix,
+            key_hash,
+            scopes,
+            daily_quota,
+            mfa_verified,
+            expires_at
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(key)
+    }
+
+    pub async fn list_by_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<ApiKey>> {
+        let keys = sqlx::query_as!(
+            ApiKey,
+            r#"
+            SELECT id, user_id, name, key_prefix, scopes, daily_quota, mfa_verified,
+                   usage_count, last_used_at, expires_at, revoked_at, created_at
+            FROM api_keys
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            "#,
+            user_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(keys)
+    }
+
+    /// Finds a key that is neither revoked nor expired.
+    pub async fn find_active_by_hash(pool: &PgPool, key_hash: &str) -> Result<Option<ApiKey>> {
+        let key = sqlx::query_as!(
+            ApiKey,
+            r#"
+            SELECT id, user_id, name, key_prefix, scopes, daily_quota, mfa_verified,
+                   usage_count, last_used_at, expires_at, revoked_at, created_at
+            FROM api_keys
+            WHERE key_hash = $1
+              AND revoked_at IS NULL
+              AND (expires_at IS NULL OR expires_at > NOW())
+            "#,
+            key_hash
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(key)
+    }
+
+    /// Revokes a key of the given user. Returns false if there was no such active key.
+    pub async fn revoke(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<bool> {
+        let result = sqlx::query!(
+            "UPDATE api_keys SET revoked_at = NOW() WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL",
+            id,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn record_usage(pool: &PgPool, id: Uuid) -> Result<()> {
+        sqlx::query!(
+            "UPDATE api_keys SET usage_count = usage_count + 1, last_used_at = NOW() WHERE id = $1",
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Counts a request against today's (UTC) quota window and returns the new count.
+    pub async fn increment_daily_usage(redis: &mut MultiplexedConnection, id: Uuid) -> Result<u64> {
+        let key = Self::daily_usage_key(id);
+        let count: u64 = redis.incr(&key, 1).await?;
+        if count == 1 {
+            // Keep the counter a little longer than the day it covers
+            let _: () = redis.expire(&key, 2 * 24 * 60 * 60).await?;
+        }
+        Ok(count)
+    }
+
+    /// Takes back a request counted by `increment_daily_usage`, for requests rejected by the quota.
+    pub async fn decrement_daily_usage(redis: &mut MultiplexedConnection, id: Uuid) -> Result<()> {
+        let key = Self::daily_usage_key(id);
+        let _: i64 = redis.decr(&key, 1).await?;
+        // The day may have turned since the increment; don't leave a fresh counter without expiry
+        let _: () = redis.expire(&key, 2 * 24 * 60 * 60).await?;
+        Ok(())
+    }
+
+    pub async fn get_daily_usage(redis: &mut MultiplexedConnection, id: Uuid) -> Result<u64> {
+        let count: Option<u64> = redis.get(Self::daily_usage_key(id)).await?;
+        Ok(count.unwrap_or(0))
+    }
+
+    fn daily_usage_key(id: Uuid) -> String {
+        let today = OffsetDateTime::now_utc().date();
+        format!("api_key_usage:{}:{}", id, today)
+    }
+}
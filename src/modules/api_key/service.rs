@@ -0,0 +1,96 @@
+use super::dto::{ApiKeyResponse, CreateApiKeyRequest, CreatedApiKeyResponse};
+use super::model::{API_KEY_PREFIX, SCOPES};
+use super::repository::ApiKeyRepository;
+use crate::common::security;
+use crate::state::AppState;
+use crate::common::error::{AppError, AppResult};
+use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
+
+const MAX_KEYS_PER_USER: usize = 20;
+
+pub struct ApiKeyService;
+
+impl ApiKeyService {
+    /// Creates a key for the user. `mfa_verified` carries over from the creating
+    /// session, so a key never grants more than the login that made it.
+    pub async fn create(
+        state: AppState,
+        user_id: Uuid,
+        mfa_verified: bool,
+        req: CreateApiKeyRequest,
+    ) -> AppResult<CreatedApiKeyResponse> {
+        let name = req.name.trim();
+        if name.is_empty() {
+            return Err(AppError::validation("Name is required"));
+        }
+
+        if req.scopes.is_empty() {
+            return Err(AppError::validation("At least one scope is required"));
+        }
+        if let Some(unknown) = req.scopes.iter().find(|s| !SCOPES.contains(&s.as_str())) {
+            return Err(AppError::validation(format!("Unknown scope '{}', expected one of: {}", unknown, SCOPES.join(", "))));
+        }
+        let mut scopes = req.scopes.clone();
+        scopes.sort();
+        scopes.dedup();
+
+        let active = ApiKeyRepository::list_by_user(&state.db, user_id)
+            .await?
+            .into_iter()
+            .filter(|k| k.revoked_at.is_none())
+            .count();
+        if active >= MAX_KEYS_PER_USER {
+            return Err(AppError::conflict(format!("Limit of {} API keys reached, revoke an unused key first", MAX_KEYS_PER_USER)));
+        }
+
+        let daily_quota = req.daily_quota.unwrap_or(state.config.api_key_daily_quota);
+        let daily_quota = i32::try_from(daily_quota).map_err(|_| AppError::validation("Daily quota is too large"))?;
+        let expires_at = req
+            .expires_in_days
+            .map(|days| OffsetDateTime::now_utc() + Duration::days(days as i64));
+
+        let key = format!("{}{}", API_KEY_PREFIX, security::generate_token());
+        let key_prefix = &key[..API_KEY_PREFIX.len() + 8];
+
+        let api_key = ApiKeyRepository::create(
+            &state.db,
+            user_id,
+            name,
+            key_prefix,
+            &security::hash_token(&key),
+            &scopes,
+            Some(daily_quota),
+            mfa_verified,
+            expires_at,
+        )
+        .await?;
+
+        tracing::info!("Created API key {} ({}) for user {}", api_key.id, api_key.key_prefix, user_id);
+
+        Ok(CreatedApiKeyResponse {
+            key,
+            api_key: ApiKeyResponse::new(api_key, 0),
+        })
+    }
+
+    pub async fn list(state: AppState, user_id: Uuid) -> AppResult<Vec<ApiKeyResponse>> {
+        let keys = ApiKeyRepository::list_by_user(&state.db, user_id).await?;
+        let mut redis_conn = state.redis.get_conn().await?;
+
+        let mut responses = Vec::with_capacity(keys.len());
+        for key in keys {
+            let usage_today = ApiKeyRepository::get_daily_usage(&mut redis_conn, key.id).await?;
+            responses.push(ApiKeyResponse::new(key, usage_today));
+        }
+        Ok(responses)
+    }
+
+    pub async fn revoke(state: AppState, user_id: Uuid, id: Uuid) -> AppResult<()> {
+        if !ApiKeyRepository::revoke(&state.db, id, user_id).await? {
+            return Err(AppError::not_found("API key not found"));
+        }
+        tracing::info!("Revoked API key {} of user {}", id, user_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use super::model::AuditLog;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct AuditLogQuery {
+    /// Only changes made by this user
+    pub actor_id: Option<Uuid>,
+    /// Entity type, e.g. `movie`, `genre`, `user`
+    pub target_type: Option<String>,
+    /// Entity id, usually combined with `target_type`
+    pub target_id: Option<String>,
+    pub action: Option<String>,
+    /// Inclusive lower bound (RFC 3339)
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    #[param(value_type = Option<String>, format = DateTime)]
+    pub from: Option<OffsetDateTime>,
+    /// Exclusive upper bound (RFC 3339)
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    #[param(value_type = Option<String>, format = DateTime)]
+    pub to: Option<OffsetDateTime>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AuditLogPage {
+    pub items: Vec<AuditLog>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
@@ -0,0 +1,35 @@
+use super::dto::{AuditLogPage, AuditLogQuery};
+use super::service::AuditService;
+use crate::common::response::{ApiResponse, ApiSuccess};
+use crate::state::AppState;
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+
+/// Query the audit log, newest first
+#[utoipa::path(
+    get,
+    path = "/api/v1/audit-logs",
+    params(AuditLogQuery),
+    responses(
+        (status = 200, description = "Audit log entries", body = ApiResponse<AuditLogPage>),
+        (status = 400, description = "Invalid filter"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing permission audit.read")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Audit"
+)]
+pub async fn list_audit_logs(
+    State(state): State<AppState>,
+    Query(query): Query<AuditLogQuery>,
+) -> impl IntoResponse {
+    match AuditService::list(state, query).await {
+        Ok(page) => ApiSuccess(ApiResponse::success(page, "Audit log retrieved"), StatusCode::OK).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
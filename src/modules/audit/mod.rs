@@ -0,0 +1,24 @@
+use axum::Router;
+use axum::routing::get;
+use crate::state::AppState;
+use crate::modules::role::model::AUDIT_READ;
+use axum::middleware;
+
+pub mod dto;
+pub mod handler;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+pub fn router(state: AppState) -> axum::Router<AppState> {
+    Router::new()
+        .route("/", get(handler::list_audit_logs))
+        .route_layer(middleware::from_fn(|req, next| {
+            crate::middleware::role::require_permission(AUDIT_READ, req, next)
+        }))
+        .route_layer(middleware::from_fn(crate::middleware::auth::session_only))
+        .route_layer(middleware::from_fn_with_state(
+            state,
+            crate::middleware::auth::auth_middleware
+        ))
+}
@@ -0,0 +1,90 @@
+use crate::common::types::ClientContext;
+use crate::middleware::request_id::RequestId;
+use crate::modules::auth::dto::TokenClaims;
+use crate::state::AppState;
+use axum::{extract::FromRequestParts, http::request::Parts};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::convert::Infallible;
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema, Clone)]
+pub struct AuditLog {
+    pub id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub actor_role: Option<String>,
+    /// `<target>.<verb>`, e.g. `movie.delete`
+    pub action: String,
+    pub target_type: String,
+    pub target_id: Option<String>,
+    /// State before the change; for updates only the fields that changed
+    #[schema(value_type = Option<Object>)]
+    pub before: Option<serde_json::Value>,
+    /// State after the change; for updates only the fields that changed
+    #[schema(value_type = Option<Object>)]
+    pub after: Option<serde_json::Value>,
+    pub request_id: Option<String>,
+    pub ip_address: Option<String>,
+    #[serde(with = "time::serde::iso8601")]
+    pub created_at: OffsetDateTime,
+}
+
+/// Who made the current request, taken from the auth claims, request id and client info.
+#[derive(Debug, Clone, Default)]
+pub struct AuditContext {
+    pub actor_id: Option<Uuid>,
+    pub actor_role: Option<String>,
+    pub request_id: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+impl FromRequestParts<AppState> for AuditContext {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let client = ClientContext::from_request_parts(parts, state).await?;
+        let claims = parts.extensions.get::<TokenClaims>();
+
+        // During impersonation the admin is the actor, not the user they act as
+        Ok(Self {
+            actor_id: claims.map(|c| c.act.as_ref().map_or(c.sub, |a| a.sub)),
+            actor_role: claims.map(|c| c.act.as_ref().map_or(&c.role, |a| &a.role).clone()),
+            request_id: parts.extensions.get::<RequestId>().map(|r| r.0.clone()),
+            ip_address: client.ip_address,
+        })
+    }
+}
+
+/// One change to record, see `AuditService::record`.
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    pub action: String,
+    pub target_type: String,
+    pub target_id: Option<String>,
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+}
+
+impl AuditEntry {
+    pub fn new(action: &str, target_type: &str, target_id: Option<impl ToString>) -> Self {
+        Self {
+            action: action.to_string(),
+            target_type: target_type.to_string(),
+            target_id: target_id.map(|id| id.to_string()),
+            before: None,
+            after: None,
+        }
+    }
+
+    pub fn before<T: Serialize>(mut self, value: &T) -> Self {
+        self.before = serde_json::to_value(value).ok();
+        self
+    }
+
+    pub fn after<T: Serialize>(mut self, value: &T) -> Self {
+        self.after = serde_json::to_value(value).ok();
+        self
+    }
+}
@@ -0,0 +1,167 @@
+use super::model::{AuditContext, AuditEntry, AuditLog};
+use anyhow::Result;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+pub struct AuditRepository;
+
+/// Filters for `list`, all optional.
+pub struct AuditFilter<'a> {
+    pub actor_id: Option<Uuid>,
+    pub target_type: Option<&'a str>,
+    pub target_id: Option<&'a str>,
+    pub action: Option<&'a str>,
+    pub from: Option<OffsetDateTime>,
+    pub to: Option<OffsetDateTime>,
+}
+
+impl AuditRepository {
+    pub async fn insert(pool: &PgPool, ctx: &AuditContext, entry: &AuditEntry) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO audit_logs (actor_id, actor_role, action, target_type, target_id, before, after, request_id, ip_address)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+            ctx.actor_id,
+            ctx.actor_role,
+            entry.action,
+            entry.target_type,
+            entry.target_id,
+            entry.before,
+            entry.after,
+            ctx.request_id,
+            ctx.ip_address
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Clears `before`, `after` and `ip_address` on the entries made by or about a user, for account erasure.
+    pub async fn redact_user(pool: &PgPool, user_id: Uuid) -> Result<u64> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE audit_logs
+            SET before = NULL, after = NULL, ip_address = NULL
+            WHERE (actor_id = $1 OR (target_type = 'user' AND target_id = $2))
+              AND (before IS NOT NULL OR after IS NOT NULL OR ip_address IS NOT NULL)
+            "#,
+            user_id,
+            user_id.to_string()
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn list(pool: &PgPool, filter: &AuditFilter<'_>, limit: i64, offset: i64) -> Result<Vec<AuditLog>> {
+        let logs = sqlx::query_as!(
+            AuditLog,
+            r#"
+            SELECT id, actor_id, actor_role, action, target_type, target_id, before, after, request_id, ip_address, created_at
+            FROM audit_logs
+            WHERE ($1::UUID IS NULL OR actor_id = $1)
+              AND ($2::VARCHAR IS NULL OR target_type = $2)
+              AND ($3::VARCHAR IS NULL OR target_id = $3)
+              AND ($4::VARCHAR IS NULL OR action = $4)
+              AND ($5::TIMESTAMPTZ IS NULL OR created_at >= $5)
+              AND ($6::TIMESTAMPTZ IS NULL OR created_at < $6)
+            ORDER BY created_at DESC
+            LIMIT $7 OFFSET $8
+            "#,
+            filter.actor_id,
+            filter.target_type,
+            filter.target_id,
+            filter.action,
+            filter.from,
+            filter.to,
+            limit,
+            offset
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(logs)
+    }
+
+    pub async fn count(pool: &PgPool, filter: &AuditFilter<'_>) -> Result<i64> {
+        let row = sqlx::query!(
+            r#"
+            SELECT COUNT(*) as "count!"
+            FROM audit_logs
+            WHERE ($1::UUID IS NULL OR actor_id = $1)
+              AND ($2::VARCHAR IS NULL OR target_type = $2)
+              AND ($3::VARCHAR IS NULL OR target_id = $3)
+              AND ($4::VARCHAR IS NULL OR action = $4)
+              AND ($5::TIMESTAMPTZ IS NULL OR created_at >= $5)
+              AND ($6::TIMESTAMPTZ IS NULL OR created_at < $6)
+            "#,
+            filter.actor_id,
+            filter.target_type,
+            filter.target_id,
+            filter.action,
+            filter.from,
+            filter.to
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(row.count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(actor_id: Uuid) -> AuditContext {
+        AuditContext {
+            actor_id: Some(actor_id),
+            actor_role: Some("USER".to_string()),
+            request_id: None,
+            ip_address: Some("203.0.113.7".to_string()),
+        }
+    }
+
+    fn entry(target_id: Uuid) -> AuditEntry {
+        AuditEntry::new("user.profile_update", "user", Some(target_id))
+            .before(&serde_json::json!({ "email": "old@example.com" }))
+            .after(&serde_json::json!({ "email": "new@example.com" }))
+    }
+
+    async fn all(pool: &PgPool) -> Vec<AuditLog> {
+        let filter = AuditFilter { actor_id: None, target_type: None, target_id: None, action: None, from: None, to: None };
+        AuditRepository::list(pool, &filter, 100, 0).await.unwrap()
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn redact_user_clears_entries_by_and_about_the_user(pool: PgPool) {
+        let (erased, admin, other) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        AuditRepository::insert(&pool, &context(erased), &entry(erased)).await.unwrap();
+        AuditRepository::insert(&pool, &context(admin), &entry(erased)).await.unwrap();
+        AuditRepository::insert(&pool, &context(other), &entry(other)).await.unwrap();
+
+        assert_eq!(AuditRepository::redact_user(&pool, erased).await.unwrap(), 2);
+
+        for log in all(&pool).await {
+            let redacted = log.before.is_none() && log.after.is_none() && log.ip_address.is_none();
+            assert_eq!(redacted, log.target_id == Some(erased.to_string()));
+            assert_eq!(log.action, "user.profile_update");
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn other_updates_stay_rejected(pool: PgPool) {
+        let user = Uuid::new_v4();
+        AuditRepository::insert(&pool, &context(user), &entry(user)).await.unwrap();
+
+        let rewrite = sqlx::query("UPDATE audit_logs SET before = NULL, after = NULL, ip_address = NULL, action = 'x'")
+            .execute(&pool)
+            .await;
+        assert!(rewrite.is_err());
+        assert!(sqlx::query("DELETE FROM audit_logs").execute(&pool).await.is_err());
+    }
+}
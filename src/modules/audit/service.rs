@@ -0,0 +1,72 @@
+use super::dto::{AuditLogPage, AuditLogQuery};
+use super::model::{AuditContext, AuditEntry};
+use super::repository::{AuditFilter, AuditRepository};
+use crate::state::AppState;
+use crate::common::error::AppResult;
+use serde_json::{Map, Value};
+
+/// Fields that change on every write and would only add noise to a diff.
+const IGNORED_FIELDS: &[&str] = &["created_at", "updated_at"];
+
+pub struct AuditService;
+
+impl AuditService {
+    /// Appends an entry to the audit log. Called after the change succeeded;
+    /// a failed write is logged but does not fail the request, since the
+    /// change itself is already committed.
+    pub async fn record(state: &AppState, ctx: &AuditContext, entry: AuditEntry) {
+        let entry = Self::diff(entry);
+
+        if let Err(e) = AuditRepository::insert(&state.db, ctx, &entry).await {
+            tracing::error!(
+                "Failed to write audit log for {} {:?} by {:?}: {}",
+                entry.action,
+                entry.target_id,
+                ctx.actor_id,
+                e
+            );
+        }
+    }
+
+    pub async fn list(state: AppState, query: AuditLogQuery) -> AppResult<AuditLogPage> {
+        let limit = query.limit.unwrap_or(50).clamp(1, 200);
+        let offset = query.offset.unwrap_or(0).max(0);
+        let filter = AuditFilter {
+            actor_id: query.actor_id,
+            target_type: query.target_type.as_deref(),
+            target_id: query.target_id.as_deref(),
+            action: query.action.as_deref(),
+            from: query.from,
+            to: query.to,
+        };
+
+        let items = AuditRepository::list(&state.db, &filter, limit, offset).await?;
+        let total = AuditRepository::count(&state.db, &filter).await?;
+
+        Ok(AuditLogPage { items, total, limit, offset })
+    }
+
+    /// When both sides are objects, keeps only the top-level fields that differ.
+    fn diff(mut entry: AuditEntry) -> AuditEntry {
+        if let (Some(Value::Object(before)), Some(Value::Object(after))) = (&entry.before, &entry.after) {
+            let mut changed_before = Map::new();
+            let mut changed_after = Map::new();
+
+            for key in before.keys().chain(after.keys()) {
+                if IGNORED_FIELDS.contains(&key.as_str()) || changed_after.contains_key(key) {
+                    continue;
+                }
+                let old = before.get(key).unwrap_or(&Value::Null);
+                let new = after.get(key).unwrap_or(&Value::Null);
+                if old != new {
+                    changed_before.insert(key.clone(), old.clone());
+                    changed_after.insert(key.clone(), new.clone());
+                }
+            }
+
+            entry.before = Some(Value::Object(changed_before));
+            entry.after = Some(Value::Object(changed_after));
+        }
+        entry
+    }
+}
@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 use uuid::Uuid;
-use utoipa::ToSchema;
+use time::OffsetDateTime;
+use super::model::{Actor, User};
+use crate::modules::api_key::model::ApiKeyContext;
+use utoipa::{IntoParams, ToSchema};
 
 #[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct RegisterRequest {
@@ -9,7 +12,8 @@ pub struct RegisterRequest {
     pub username: String,
     #[validate(email(message = "Invalid email address"))]
     pub email: String,
-    #[validate(length(min = 6, message = "Password must be at least 6 characters"))]
+    /// At least `PASSWORD_MIN_LENGTH` characters, not a common password and not containing the username or email
+    #[validate(length(min = 1, max = 128, message = "Password must be 1-128 characters"))]
     pub password: String,
     #[validate(length(min = 1, message = "Full name is required"))]
     pub full_name: String,
@@ -37,12 +41,204 @@ pub struct UserResponse {
     pub username: String,
     pub full_name: String,
     pub role: String,
+    pub email_verified: bool,
+    pub mfa_enabled: bool,
+    /// Path of the avatar image, if one was uploaded
+    pub avatar_url: Option<String>,
+}
+
+impl From<User> for UserResponse {
+    fn from(user: User) -> Self {
+        Self {
+            email_verified: user.email_verified_at.is_some(),
+            mfa_enabled: user.totp_enabled_at.is_some(),
+            avatar_url: user.avatar_url.as_ref().map(|_| format!("/api/v1/users/{}/avatar", user.id)),
+            id: user.id,
+            email: user.email,
+            username: user.username,
+            full_name: user.full_name,
+            role: user.role,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct VerifyEmailRequest {
+    #[validate(length(min = 1, message = "Token is required"))]
+    pub token: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ResendVerificationRequest {
+    #[validate(email(message = "Invalid email address"))]
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ForgotPasswordRequest {
+    #[validate(email(message = "Invalid email address"))]
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ResetPasswordRequest {
+    #[validate(length(min = 1, message = "Token is required"))]
+    pub token: String,
+    /// At least `PASSWORD_MIN_LENGTH` characters, not a common password and not containing the username or email
+    #[validate(length(min = 1, max = 128, message = "Password must be 1-128 characters"))]
+    pub new_password: String,
+}
+
+/// Returned by login instead of tokens when the account has two-factor authentication enabled.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MfaChallengeResponse {
+    pub mfa_required: bool,
+    pub mfa_token: String,
+    pub expires_in: u64,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct MfaLoginRequest {
+    #[validate(length(min = 1, message = "MFA token is required"))]
+    pub mfa_token: String,
+    /// A 6-digit code from the authenticator app, or a recovery code
+    #[validate(length(min = 1, message = "Code is required"))]
+    pub code: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TotpSetupResponse {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct MfaCodeRequest {
+    #[validate(length(min = 1, message = "Code is required"))]
+    pub code: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RecoveryCodesResponse {
+    pub recovery_codes: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct LockoutEventsQuery {
+    /// Only events for this email address
+    pub email: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UnlockLoginRequest {
+    #[validate(email(message = "Invalid email address"))]
+    pub email: String,
+}
+
+/// Query string the identity provider redirects back with.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct OidcCallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
+
+/// Started by a TV or console that cannot show a login form.
+#[derive(Debug, Default, Deserialize, Validate, ToSchema)]
+pub struct DeviceCodeRequest {
+    /// Shown to the user when approving, e.g. "Living room TV"
+    #[validate(length(max = 64, message = "Client name must be at most 64 characters"))]
+    pub client_name: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DeviceCodeResponse {
+    /// Secret the device polls with; never shown to the user
+    pub device_code: String,
+    /// Short code the user enters on their phone, e.g. "BCDF-GHJK"
+    pub user_code: String,
+    pub verification_uri: String,
+    /// `verification_uri` with the user code filled in, for a QR code
+    pub verification_uri_complete: String,
+    pub expires_in: u64,
+    /// Minimum seconds between polls of the token endpoint
+    pub interval: u64,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct DeviceTokenRequest {
+    #[validate(length(min = 1, message = "Device code is required"))]
+    pub device_code: String,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct DeviceLookupQuery {
+    pub user_code: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct DeviceApprovalRequest {
+    #[validate(length(min = 1, message = "User code is required"))]
+    pub user_code: String,
+    /// False denies the request, and the device stops polling
+    pub approve: bool,
+}
+
+/// What the user sees before approving a device.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DeviceAuthorizationResponse {
+    pub user_code: String,
+    pub client_name: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    #[serde(with = "time::serde::iso8601")]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SessionResponse {
+    pub id: Uuid,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    #[serde(with = "time::serde::iso8601")]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::iso8601")]
+    #[schema(value_type = String, format = DateTime)]
+    pub last_used_at: OffsetDateTime,
+    pub current: bool,
+    /// Opened by support to see the account as the user does
+    pub impersonation: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TokenClaims {
     pub sub: Uuid,
     pub role: String,
+    pub sid: Uuid,
+    /// The session passed a second factor
+    #[serde(default)]
+    pub mfa: bool,
+    /// Permissions of the role, resolved when the token was issued
+    #[serde(default)]
+    pub permissions: Vec<String>,
+    /// The role requires 2FA and this session has not passed it; permissions are withheld
+    #[serde(default)]
+    pub mfa_required: bool,
+    /// Active viewer profile of the session; `None` means the default profile
+    #[serde(default)]
+    pub profile_id: Option<Uuid>,
+    /// Set on impersonation tokens: the admin acting as `sub`. Such tokens are read-only
+    /// and every request made with them is audited under the admin's id.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub act: Option<Actor>,
     pub exp: usize,
     pub iat: usize,
+    /// Set when the request was authenticated with an API key instead of a JWT
+    #[serde(skip)]
+    pub api_key: Option<ApiKeyContext>,
 }
@@ -1,14 +1,29 @@
-use super::dto::{LoginRequest, RegisterRequest, TokenClaims, AuthResponse, UserResponse};
-use super::service::AuthService;
+use super::dto::{
+    LoginRequest, RegisterRequest, TokenClaims, AuthResponse, UserResponse, SessionResponse, VerifyEmailRequest,
+    ResendVerificationRequest, ForgotPasswordRequest, ResetPasswordRequest, MfaLoginRequest, MfaCodeRequest,
+    TotpSetupResponse, RecoveryCodesResponse, OidcCallbackQuery, LockoutEventsQuery, UnlockLoginRequest,
+    DeviceCodeRequest, DeviceCodeResponse, DeviceTokenRequest, DeviceLookupQuery, DeviceApprovalRequest,
+    DeviceAuthorizationResponse,
+};
+use super::model::LoginLockoutEvent;
+use super::service::{AuthService, DeviceGrantError, LoginOutcome};
 use crate::state::AppState;
-use crate::common::response::{ApiResponse, ApiSuccess, ApiError};
+use crate::common::error::{AppError, ProblemDetails};
+use crate::common::response::{ApiResponse, ApiSuccess};
+use crate::common::types::ClientContext;
+use crate::modules::audit::model::{AuditContext, AuditEntry};
+use crate::modules::audit::service::AuditService;
 use axum::{
-    extract::{State, Extension},
-    http::{StatusCode, HeaderMap},
-    response::IntoResponse,
+    extract::{Path, Query, State, Extension},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Redirect},
     Json,
 };
 use tower_cookies::{Cookie, Cookies};
+use uuid::Uuid;
+use validator::Validate;
+
+const OIDC_STATE_COOKIE: &str = "oidc_state";
 
 /// Register a new user
 #[utoipa::path(
@@ -27,7 +42,7 @@ pub async fn register(
 ) -> impl IntoResponse {
     match AuthService::register(state, payload).await {
         Ok(user) => ApiSuccess(ApiResponse::success(user, "User registered successfully"), StatusCode::CREATED).into_response(),
-        Err(e) => ApiError(e.to_string(), StatusCode::BAD_REQUEST).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -38,32 +53,292 @@ pub async fn register(
     request_body = LoginRequest,
     responses(
         (status = 200, description = "Login successful", body = ApiResponse<AuthResponse>),
-        (status = 401, description = "Unauthorized")
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Account is suspended"),
+        (status = 429, description = "Too many failed attempts, see Retry-After")
     ),
     tag = "Auth"
 )]
 pub async fn login(
     State(state): State<AppState>,
     cookies: Cookies,
+    client: ClientContext,
     Json(payload): Json<LoginRequest>,
 ) -> impl IntoResponse {
-    match AuthService::login(state, payload).await {
+    match AuthService::login(state, payload, client).await {
+        Ok(LoginOutcome::Authenticated(response, refresh_token)) => {
+            set_refresh_cookie(&cookies, refresh_token);
+            ApiSuccess(ApiResponse::success(response, "Login successful"), StatusCode::OK).into_response()
+        }
+        Ok(LoginOutcome::MfaRequired(challenge)) => {
+            ApiSuccess(ApiResponse::success(challenge, "Two-factor authentication required"), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Complete a login with a TOTP or recovery code
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/login/mfa",
+    request_body = MfaLoginRequest,
+    responses(
+        (status = 200, description = "Login successful", body = ApiResponse<AuthResponse>),
+        (status = 401, description = "Invalid code or expired challenge"),
+        (status = 403, description = "Account is suspended")
+    ),
+    tag = "Auth"
+)]
+pub async fn login_mfa(
+    State(state): State<AppState>,
+    cookies: Cookies,
+    client: ClientContext,
+    Json(payload): Json<MfaLoginRequest>,
+) -> impl IntoResponse {
+    match AuthService::login_mfa(state, payload, client).await {
         Ok((response, refresh_token)) => {
-            let mut cookie = Cookie::new("refresh_token", refresh_token);
-            cookie.set_http_only(true);
-            cookie.set_path("/api/v1/auth"); // Allow access for refresh AND logout
-             cookie.set_secure(false); // Keep false for HTTP localhost
-            // Expiry 7 days
-             cookie.set_max_age(Some(time::Duration::days(7)));
+            set_refresh_cookie(&cookies, refresh_token);
+            ApiSuccess(ApiResponse::success(response, "Login successful"), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
 
+/// Start a social login with the configured OpenID Connect provider
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/oidc/login",
+    responses(
+        (status = 303, description = "Redirect to the identity provider"),
+        (status = 404, description = "Social login is not configured"),
+        (status = 502, description = "Identity provider unreachable")
+    ),
+    tag = "Auth"
+)]
+pub async fn oidc_login(
+    State(state): State<AppState>,
+    cookies: Cookies,
+) -> impl IntoResponse {
+    if state.oidc.is_none() {
+        return AppError::not_found("Social login is not configured").into_response();
+    }
+
+    match AuthService::oidc_authorize(state).await {
+        Ok((url, oauth_state)) => {
+            // Binds the flow to this browser, so a callback URL cannot be replayed elsewhere
+            let mut cookie = Cookie::new(OIDC_STATE_COOKIE, oauth_state);
+            cookie.set_http_only(true);
+            cookie.set_path("/api/v1/auth/oidc");
+            cookie.set_secure(false); // Keep false for HTTP localhost
+            cookie.set_same_site(tower_cookies::cookie::SameSite::Lax);
+            cookie.set_max_age(Some(time::Duration::minutes(10)));
             cookies.add(cookie);
-            
+
+            Redirect::to(&url).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Callback from the identity provider; logs in like `/login`
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/oidc/callback",
+    params(OidcCallbackQuery),
+    responses(
+        (status = 200, description = "Login successful, or a 2FA challenge", body = ApiResponse<AuthResponse>),
+        (status = 401, description = "Login rejected"),
+        (status = 403, description = "Account is suspended")
+    ),
+    tag = "Auth"
+)]
+pub async fn oidc_callback(
+    State(state): State<AppState>,
+    cookies: Cookies,
+    client: ClientContext,
+    Query(query): Query<OidcCallbackQuery>,
+) -> impl IntoResponse {
+    if let Some(error) = query.error {
+        let message = query.error_description.unwrap_or(error);
+        return AppError::unauthorized(format!("Login cancelled: {}", message)).into_response();
+    }
+
+    let (Some(code), Some(oauth_state)) = (query.code, query.state) else {
+        return AppError::validation("Missing code or state").into_response();
+    };
+
+    let cookie_state = cookies.get(OIDC_STATE_COOKIE).map(|c| c.value().to_string());
+    let mut cookie = Cookie::new(OIDC_STATE_COOKIE, "");
+    cookie.set_path("/api/v1/auth/oidc");
+    cookies.remove(cookie);
+
+    if cookie_state.as_deref() != Some(oauth_state.as_str()) {
+        return AppError::unauthorized("Login request does not match this browser").into_response();
+    }
+
+    match AuthService::oidc_callback(state, &code, &oauth_state, client).await {
+        Ok(LoginOutcome::Authenticated(response, refresh_token)) => {
+            set_refresh_cookie(&cookies, refresh_token);
+            ApiSuccess(ApiResponse::success(response, "Login successful"), StatusCode::OK).into_response()
+        }
+        Ok(LoginOutcome::MfaRequired(challenge)) => {
+            ApiSuccess(ApiResponse::success(challenge, "Two-factor authentication required"), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Start a device login for a TV or console; show the user code and poll `/auth/device/token`
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/device/code",
+    request_body = DeviceCodeRequest,
+    responses(
+        (status = 200, description = "Device and user code issued", body = ApiResponse<DeviceCodeResponse>),
+        (status = 400, description = "Bad Request")
+    ),
+    tag = "Auth"
+)]
+pub async fn device_code(
+    State(state): State<AppState>,
+    client: ClientContext,
+    payload: Option<Json<DeviceCodeRequest>>,
+) -> impl IntoResponse {
+    let payload = payload.map(|Json(p)| p).unwrap_or_default();
+    if let Err(e) = payload.validate() {
+        return AppError::from(e).into_response();
+    }
+
+    match AuthService::start_device_authorization(state, payload, client).await {
+        Ok(response) => ApiSuccess(ApiResponse::success(response, "Device code issued"), StatusCode::OK).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Poll for the tokens of a device login. The message is the RFC 8628 error code until the user decides.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/device/token",
+    request_body = DeviceTokenRequest,
+    responses(
+        (status = 200, description = "Login successful", body = ApiResponse<AuthResponse>),
+        (status = 400, description = "authorization_pending, or expired_token when the code expired or was used"),
+        (status = 403, description = "access_denied, or the account is suspended"),
+        (status = 429, description = "slow_down: polled faster than the interval, see Retry-After")
+    ),
+    tag = "Auth"
+)]
+pub async fn device_token(
+    State(state): State<AppState>,
+    cookies: Cookies,
+    Json(payload): Json<DeviceTokenRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = payload.validate() {
+        return AppError::from(e).into_response();
+    }
+
+    match AuthService::device_token(state, &payload.device_code).await {
+        Ok((response, refresh_token)) => {
+            set_refresh_cookie(&cookies, refresh_token);
             ApiSuccess(ApiResponse::success(response, "Login successful"), StatusCode::OK).into_response()
         }
-        Err(e) => ApiError(e.to_string(), StatusCode::UNAUTHORIZED).into_response(),
+        // RFC 8628 clients branch on the error code, so these keep theirs
+        Err(DeviceGrantError::Other(e)) => e.into_response(),
+        Err(e) => {
+            let (status, detail) = match &e {
+                DeviceGrantError::AuthorizationPending => (StatusCode::BAD_REQUEST, "The login has not been approved yet"),
+                DeviceGrantError::SlowDown { .. } => (StatusCode::TOO_MANY_REQUESTS, "Polling too fast, wait before retrying"),
+                DeviceGrantError::AccessDenied => (StatusCode::FORBIDDEN, "The login was denied"),
+                _ => (StatusCode::BAD_REQUEST, "The device code is invalid or expired"),
+            };
+            let mut response = ProblemDetails::new(status, &e.to_string(), detail).into_response();
+            if let DeviceGrantError::SlowDown { retry_after } = e {
+                response.headers_mut().insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+            }
+            response
+        }
     }
 }
 
+/// Show which device is asking to log in, before approving it
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/device",
+    params(DeviceLookupQuery),
+    responses(
+        (status = 200, description = "Pending device login", body = ApiResponse<DeviceAuthorizationResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Code is invalid or expired"),
+        (status = 429, description = "Too many invalid codes")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Auth"
+)]
+pub async fn get_device(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    Query(query): Query<DeviceLookupQuery>,
+) -> impl IntoResponse {
+    match AuthService::get_device_authorization(state, claims.sub, &query.user_code).await {
+        Ok(device) => ApiSuccess(ApiResponse::success(device, "Device login retrieved"), StatusCode::OK).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Approve or deny a device login with the current account
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/device/approve",
+    request_body = DeviceApprovalRequest,
+    responses(
+        (status = 200, description = "Decision saved; the device receives tokens on its next poll", body = ApiResponse<String>),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Code is invalid or expired"),
+        (status = 429, description = "Too many invalid codes")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Auth"
+)]
+pub async fn approve_device(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    audit: AuditContext,
+    Json(payload): Json<DeviceApprovalRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = payload.validate() {
+        return AppError::from(e).into_response();
+    }
+
+    match AuthService::decide_device_authorization(state.clone(), &claims, &payload).await {
+        Ok(()) => {
+            let (action, message) = if payload.approve {
+                ("device.approve", "Device approved")
+            } else {
+                ("device.deny", "Device denied")
+            };
+            AuditService::record(&state, &audit, AuditEntry::new(action, "user", Some(claims.sub))).await;
+            ApiSuccess(ApiResponse::success((), message), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+
+fn set_refresh_cookie(cookies: &Cookies, refresh_token: String) {
+    let mut cookie = Cookie::new("refresh_token", refresh_token);
+    cookie.set_http_only(true);
+    cookie.set_path("/api/v1/auth"); // Allow access for refresh AND logout
+    cookie.set_secure(false); // Keep false for HTTP localhost
+    // Expiry 7 days
+    cookie.set_max_age(Some(time::Duration::days(7)));
+
+    cookies.add(cookie);
+}
+
 /// Logout user
 #[utoipa::path(
     post,
@@ -82,6 +357,7 @@ pub async fn logout(
     State(state): State<AppState>,
     cookies: Cookies,
     Extension(claims): Extension<TokenClaims>,
+    audit: AuditContext,
     headers: HeaderMap,
 ) -> impl IntoResponse {
     // 1. Block Access Token
@@ -96,8 +372,9 @@ pub async fn logout(
         }
     }
 
-    // 2. Revoke Refresh Token
-    let _ = AuthService::logout(state, claims.sub).await;
+    // 2. Revoke this device's session (and its refresh token)
+    let _ = AuthService::logout(state.clone(), claims.sub, claims.sid).await;
+    AuditService::record(&state, &audit, AuditEntry::new("session.logout", "session", Some(claims.sid))).await;
 
     // 3. Clear Cookie
     let mut cookie = Cookie::new("refresh_token", "");
@@ -113,49 +390,29 @@ pub async fn logout(
     path = "/api/v1/auth/refresh",
     responses(
         (status = 200, description = "Token refreshed successfully", body = ApiResponse<AuthResponse>),
-        (status = 401, description = "Unauthorized")
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Account is suspended")
     ),
     tag = "Auth"
 )]
 pub async fn refresh(
     State(state): State<AppState>,
     cookies: Cookies,
+    client: ClientContext,
 ) -> impl IntoResponse {
     let refresh_token_cookie = cookies.get("refresh_token");
     
     let refresh_token = match refresh_token_cookie {
         Some(c) => c.value().to_string(),
-        None => return ApiError("Missing refresh token".to_string(), StatusCode::UNAUTHORIZED).into_response(),
+        None => return AppError::unauthorized("Missing refresh token").into_response(),
     };
 
-    tracing::info!("Refresh request received with token: {}", refresh_token); // Log the token!
-    
-    // Parse user_id from token "user_id:uuid"
-    let parts: Vec<&str> = refresh_token.split(':').collect();
-    if parts.len() != 2 {
-        tracing::error!("Invalid token format: {}", refresh_token);
-        return ApiError("Invalid token format".to_string(), StatusCode::UNAUTHORIZED).into_response();
-    }
-    
-    let user_id = match uuid::Uuid::parse_str(parts[0]) {
-        Ok(id) => id,
-        Err(_) => return ApiError("Invalid user ID in token".to_string(), StatusCode::UNAUTHORIZED).into_response(),
-    };
-
-    match AuthService::refresh_access(state, refresh_token, user_id).await {
+    match AuthService::refresh_access(state, refresh_token, client).await {
         Ok((response, new_refresh_token)) => {
-             let mut cookie = Cookie::new("refresh_token", new_refresh_token);
-            cookie.set_http_only(true);
-            cookie.set_path("/api/v1/auth"); // Allow access for refresh AND logout
-             cookie.set_secure(false); // Keep false for HTTP localhost
-            // Expiry 7 days
-             cookie.set_max_age(Some(time::Duration::days(7)));
-
-            cookies.add(cookie);
-
+            set_refresh_cookie(&cookies, new_refresh_token);
             ApiSuccess(ApiResponse::success(response, "Token refreshed"), StatusCode::OK).into_response()
         },
-        Err(e) => ApiError(e.to_string(), StatusCode::UNAUTHORIZED).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -178,16 +435,374 @@ pub async fn get_me(
 ) -> impl IntoResponse {
     match super::repository::AuthRepository::find_user_by_id(&state.db, claims.sub).await {
         Ok(Some(user)) => {
-            let user_response = UserResponse {
-                id: user.id,
-                email: user.email,
-                username: user.username,
-                full_name: user.full_name,
-                role: user.role.to_string(),
-            };
+            let user_response = UserResponse::from(user);
             ApiSuccess(ApiResponse::success(user_response, "User profile retrieved"), StatusCode::OK).into_response()
         }
-        Ok(None) => ApiError("User not found".to_string(), StatusCode::NOT_FOUND).into_response(),
-        Err(e) => ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response(),
+        Ok(None) => AppError::not_found("User not found").into_response(),
+        Err(e) => AppError::from(e).into_response(),
+    }
+}
+
+/// Confirm an email address with the token from the verification mail
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/verify-email",
+    request_body = VerifyEmailRequest,
+    responses(
+        (status = 200, description = "Email verified", body = ApiResponse<String>),
+        (status = 400, description = "Invalid or expired token")
+    ),
+    tag = "Auth"
+)]
+pub async fn verify_email(
+    State(state): State<AppState>,
+    Json(payload): Json<VerifyEmailRequest>,
+) -> impl IntoResponse {
+    match AuthService::verify_email(state, payload).await {
+        Ok(_) => ApiSuccess(ApiResponse::success((), "Email verified successfully"), StatusCode::OK).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Send a new verification email
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/verify-email/resend",
+    request_body = ResendVerificationRequest,
+    responses(
+        (status = 200, description = "Verification email sent if the account exists and is unverified", body = ApiResponse<String>)
+    ),
+    tag = "Auth"
+)]
+pub async fn resend_verification(
+    State(state): State<AppState>,
+    Json(payload): Json<ResendVerificationRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = AuthService::resend_verification(state, payload).await {
+        tracing::error!("Failed to resend verification email: {}", e);
+    }
+
+    ApiSuccess(
+        ApiResponse::success((), "If the account exists and is unverified, a verification email has been sent"),
+        StatusCode::OK,
+    )
+    .into_response()
+}
+
+/// Request a password reset email
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/forgot-password",
+    request_body = ForgotPasswordRequest,
+    responses(
+        (status = 200, description = "Reset email sent if the account exists", body = ApiResponse<String>)
+    ),
+    tag = "Auth"
+)]
+pub async fn forgot_password(
+    State(state): State<AppState>,
+    Json(payload): Json<ForgotPasswordRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = AuthService::forgot_password(state, payload).await {
+        tracing::error!("Failed to send password reset email: {}", e);
+    }
+
+    ApiSuccess(
+        ApiResponse::success((), "If the account exists, a password reset email has been sent"),
+        StatusCode::OK,
+    )
+    .into_response()
+}
+
+/// Set a new password with the token from the reset email
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/reset-password",
+    request_body = ResetPasswordRequest,
+    responses(
+        (status = 200, description = "Password reset, all sessions revoked", body = ApiResponse<String>),
+        (status = 400, description = "Invalid or expired token")
+    ),
+    tag = "Auth"
+)]
+pub async fn reset_password(
+    State(state): State<AppState>,
+    Json(payload): Json<ResetPasswordRequest>,
+) -> impl IntoResponse {
+    match AuthService::reset_password(state, payload).await {
+        Ok(_) => ApiSuccess(ApiResponse::success((), "Password reset successfully"), StatusCode::OK).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Start TOTP enrollment
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/mfa/totp/setup",
+    responses(
+        (status = 200, description = "Secret and otpauth URI for the authenticator app", body = ApiResponse<TotpSetupResponse>),
+        (status = 400, description = "Two-factor authentication already enabled"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Auth"
+)]
+pub async fn setup_totp(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    audit: AuditContext,
+) -> impl IntoResponse {
+    match AuthService::setup_totp(state.clone(), claims.sub).await {
+        Ok(setup) => {
+            AuditService::record(&state, &audit, AuditEntry::new("user.mfa_setup", "user", Some(claims.sub))).await;
+            ApiSuccess(ApiResponse::success(setup, "Scan the code and confirm it to enable two-factor authentication"), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Confirm TOTP enrollment and receive recovery codes
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/mfa/totp/enable",
+    request_body = MfaCodeRequest,
+    responses(
+        (status = 200, description = "Two-factor authentication enabled", body = ApiResponse<RecoveryCodesResponse>),
+        (status = 400, description = "Invalid code or no pending setup"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Auth"
+)]
+pub async fn enable_totp(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    audit: AuditContext,
+    Json(payload): Json<MfaCodeRequest>,
+) -> impl IntoResponse {
+    match AuthService::enable_totp(state.clone(), claims.sub, claims.sid, payload).await {
+        Ok(codes) => {
+            AuditService::record(&state, &audit, AuditEntry::new("user.mfa_enable", "user", Some(claims.sub))).await;
+            ApiSuccess(ApiResponse::success(codes, "Two-factor authentication enabled"), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Disable TOTP
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/mfa/totp/disable",
+    request_body = MfaCodeRequest,
+    responses(
+        (status = 200, description = "Two-factor authentication disabled", body = ApiResponse<String>),
+        (status = 400, description = "Invalid code or 2FA required for the role"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Auth"
+)]
+pub async fn disable_totp(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    audit: AuditContext,
+    Json(payload): Json<MfaCodeRequest>,
+) -> impl IntoResponse {
+    match AuthService::disable_totp(state.clone(), claims.sub, payload).await {
+        Ok(_) => {
+            AuditService::record(&state, &audit, AuditEntry::new("user.mfa_disable", "user", Some(claims.sub))).await;
+            ApiSuccess(ApiResponse::success((), "Two-factor authentication disabled"), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Replace the recovery codes
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/mfa/recovery-codes",
+    request_body = MfaCodeRequest,
+    responses(
+        (status = 200, description = "New recovery codes, the old ones no longer work", body = ApiResponse<RecoveryCodesResponse>),
+        (status = 400, description = "Invalid code"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Auth"
+)]
+pub async fn regenerate_recovery_codes(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    audit: AuditContext,
+    Json(payload): Json<MfaCodeRequest>,
+) -> impl IntoResponse {
+    match AuthService::regenerate_recovery_codes(state.clone(), claims.sub, payload).await {
+        Ok(codes) => {
+            AuditService::record(&state, &audit, AuditEntry::new("user.recovery_codes_regenerate", "user", Some(claims.sub))).await;
+            ApiSuccess(ApiResponse::success(codes, "Recovery codes regenerated"), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// List login lockout events (admin)
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/lockouts",
+    params(LockoutEventsQuery),
+    responses(
+        (status = 200, description = "Lockout and unlock events, newest first", body = ApiResponse<Vec<LoginLockoutEvent>>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Auth"
+)]
+pub async fn list_lockouts(
+    State(state): State<AppState>,
+    Query(query): Query<LockoutEventsQuery>,
+) -> impl IntoResponse {
+    match AuthService::list_lockout_events(state, query).await {
+        Ok(events) => ApiSuccess(ApiResponse::success(events, "Lockout events retrieved"), StatusCode::OK).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Lift a login lockout on an email address (admin)
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/lockouts/unlock",
+    request_body = UnlockLoginRequest,
+    responses(
+        (status = 200, description = "Lockout lifted", body = ApiResponse<String>),
+        (status = 404, description = "Email is not locked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Auth"
+)]
+pub async fn unlock_login(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    audit: AuditContext,
+    Json(payload): Json<UnlockLoginRequest>,
+) -> impl IntoResponse {
+    match AuthService::unlock_login(state.clone(), &payload.email, Some(claims.sub), "admin").await {
+        Ok(true) => {
+            AuditService::record(&state, &audit, AuditEntry::new("login.unlock", "email", Some(&payload.email))).await;
+            ApiSuccess(ApiResponse::success((), "Login unlocked"), StatusCode::OK).into_response()
+        }
+        Ok(false) => AppError::not_found("Email is not locked").into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// List active sessions (one per logged-in device)
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/sessions",
+    responses(
+        (status = 200, description = "Active sessions", body = ApiResponse<Vec<SessionResponse>>),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Auth"
+)]
+pub async fn list_sessions(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+) -> impl IntoResponse {
+    match AuthService::list_sessions(state, claims.sub, claims.sid).await {
+        Ok(sessions) => ApiSuccess(ApiResponse::success(sessions, "Sessions retrieved"), StatusCode::OK).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Revoke a single session
+#[utoipa::path(
+    delete,
+    path = "/api/v1/auth/sessions/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Session ID")
+    ),
+    responses(
+        (status = 200, description = "Session revoked", body = ApiResponse<String>),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Session not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Auth"
+)]
+pub async fn revoke_session(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    audit: AuditContext,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match AuthService::revoke_session(state.clone(), claims.sub, id).await {
+        Ok(_) => {
+            AuditService::record(&state, &audit, AuditEntry::new("session.revoke", "session", Some(id))).await;
+            ApiSuccess(ApiResponse::success((), "Session revoked"), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
     }
 }
+
+/// Revoke every session except the current one
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/sessions/revoke-others",
+    responses(
+        (status = 200, description = "Number of sessions revoked", body = ApiResponse<usize>),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Auth"
+)]
+pub async fn revoke_other_sessions(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    audit: AuditContext,
+) -> impl IntoResponse {
+    match AuthService::revoke_other_sessions(state.clone(), claims.sub, claims.sid).await {
+        Ok(revoked) => {
+            let entry = AuditEntry::new("session.revoke_others", "user", Some(claims.sub))
+                .after(&serde_json::json!({ "revoked": revoked }));
+            AuditService::record(&state, &audit, entry).await;
+            ApiSuccess(ApiResponse::success(revoked, "Other sessions revoked"), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Public keys used to verify access tokens (JWKS)
+#[utoipa::path(
+    get,
+    path = "/.well-known/jwks.json",
+    responses(
+        (status = 200, description = "JSON Web Key Set")
+    ),
+    tag = "Auth"
+)]
+pub async fn jwks(State(state): State<AppState>) -> impl IntoResponse {
+    Json(state.jwt.jwks())
+}
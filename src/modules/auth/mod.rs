@@ -1,6 +1,8 @@
 use axum::Router;
 use axum::routing::post;
 use crate::state::AppState;
+use crate::modules::api_key::model::SCOPE_PROFILE_READ;
+use crate::modules::role::model::USERS_MANAGE;
 use axum::middleware;
 
 pub mod dto;
@@ -13,11 +15,47 @@ pub fn router(state: AppState) -> axum::Router<AppState> {
     let public_routes = Router::new()
         .route("/register", post(handler::register))
         .route("/login", post(handler::login))
-        .route("/refresh", post(handler::refresh));
+        .route("/login/mfa", post(handler::login_mfa))
+        .route("/oidc/login", axum::routing::get(handler::oidc_login))
+        .route("/oidc/callback", axum::routing::get(handler::oidc_callback))
+        .route("/device/code", post(handler::device_code))
+        .route("/device/token", post(handler::device_token))
+        .route("/refresh", post(handler::refresh))
+        .route("/verify-email", post(handler::verify_email))
+        .route("/verify-email/resend", post(handler::resend_verification))
+        .route("/forgot-password", post(handler::forgot_password))
+        .route("/reset-password", post(handler::reset_password));
 
-    let protected_routes = Router::new()
-        .route("/logout", post(handler::logout))
+    let profile_routes = Router::new()
         .route("/me", axum::routing::get(handler::get_me))
+        .route_layer(middleware::from_fn(|req, next| {
+            crate::middleware::auth::require_scope(SCOPE_PROFILE_READ, req, next)
+        }));
+
+    let account_routes = Router::new()
+        .route("/logout", post(handler::logout))
+        .route("/mfa/totp/setup", post(handler::setup_totp))
+        .route("/mfa/totp/enable", post(handler::enable_totp))
+        .route("/mfa/totp/disable", post(handler::disable_totp))
+        .route("/mfa/recovery-codes", post(handler::regenerate_recovery_codes))
+        .route("/device", axum::routing::get(handler::get_device))
+        .route("/device/approve", post(handler::approve_device))
+        .route("/sessions", axum::routing::get(handler::list_sessions))
+        .route("/sessions/revoke-others", post(handler::revoke_other_sessions))
+        .route("/sessions/{id}", axum::routing::delete(handler::revoke_session))
+        .route_layer(middleware::from_fn(crate::middleware::auth::session_only));
+
+    let admin_routes = Router::new()
+        .route("/lockouts", axum::routing::get(handler::list_lockouts))
+        .route("/lockouts/unlock", post(handler::unlock_login))
+        .route_layer(middleware::from_fn(|req, next| {
+            crate::middleware::role::require_permission(USERS_MANAGE, req, next)
+        }))
+        .route_layer(middleware::from_fn(crate::middleware::auth::session_only));
+
+    let protected_routes = profile_routes
+        .merge(account_routes)
+        .merge(admin_routes)
         .route_layer(middleware::from_fn_with_state(
             state,
             crate::middleware::auth::auth_middleware
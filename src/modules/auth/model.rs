@@ -1,25 +1,10 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use utoipa::ToSchema;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
-#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, PartialEq)]
-#[sqlx(type_name = "user_role", rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum UserRole {
-    Admin,
-    User,
-}
-
-impl ToString for UserRole {
-    fn to_string(&self) -> String {
-        match self {
-            UserRole::Admin => "ADMIN".to_string(),
-            UserRole::User => "USER".to_string(),
-        }
-    }
-}
-
-#[derive(Debug, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
 pub struct User {
     pub id: Uuid,
     pub email: String,
@@ -27,9 +12,186 @@ pub struct User {
     #[serde(skip_serializing)]
     pub password_hash: String,
     pub full_name: String,
-    pub role: UserRole,
+    /// Name of a row in `roles` (see `modules::role`)
+    pub role: String,
+    #[serde(with = "time::serde::iso8601::option")]
+    pub email_verified_at: Option<OffsetDateTime>,
+    #[serde(skip_serializing)]
+    pub totp_secret: Option<String>,
+    #[serde(with = "time::serde::iso8601::option")]
+    pub totp_enabled_at: Option<OffsetDateTime>,
+    /// Object key of the avatar in the thumbnails bucket
+    pub avatar_url: Option<String>,
+    #[serde(with = "time::serde::iso8601::option")]
+    pub suspended_at: Option<OffsetDateTime>,
+    /// End of a temporary suspension; `None` while suspended means indefinitely
+    #[serde(with = "time::serde::iso8601::option")]
+    pub suspended_until: Option<OffsetDateTime>,
+    pub suspension_reason: Option<String>,
     #[serde(with = "time::serde::iso8601")]
     pub created_at: OffsetDateTime,
     #[serde(with = "time::serde::iso8601")]
     pub updated_at: OffsetDateTime,
 }
+
+impl User {
+    /// Suspended by an admin and the suspension has not run out yet.
+    pub fn is_suspended(&self) -> bool {
+        self.suspended_at.is_some()
+            && self.suspended_until.is_none_or(|until| until > OffsetDateTime::now_utc())
+    }
+}
+
+/// An external (OpenID Connect) identity linked to a local account.
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct UserIdentity {
+    /// Issuer URL of the provider
+    pub provider: String,
+    pub subject: String,
+    pub email: Option<String>,
+    #[serde(with = "time::serde::iso8601")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::iso8601")]
+    pub last_login_at: OffsetDateTime,
+}
+
+/// A lockout or unlock recorded by the login brute-force protection.
+#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct LoginLockoutEvent {
+    pub id: Uuid,
+    /// LOCKED or UNLOCKED
+    pub event: String,
+    /// EMAIL or IP
+    pub scope: String,
+    pub email: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_id: Option<Uuid>,
+    pub failed_attempts: i32,
+    #[serde(with = "time::serde::iso8601::option")]
+    pub locked_until: Option<OffsetDateTime>,
+    pub reason: Option<String>,
+    pub actor_id: Option<Uuid>,
+    #[serde(with = "time::serde::iso8601")]
+    pub created_at: OffsetDateTime,
+}
+
+/// What a row in `user_tokens` may be used for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TokenPurpose {
+    EmailVerification,
+    PasswordReset,
+}
+
+impl TokenPurpose {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenPurpose::EmailVerification => "EMAIL_VERIFICATION",
+            TokenPurpose::PasswordReset => "PASSWORD_RESET",
+        }
+    }
+}
+
+/// A login on a single device. Stored in Redis as JSON under `session:{id}`.
+///
+/// A session is also the refresh token family: every rotation replaces
+/// `refresh_token_hash` while the session id stays the same, so revoking the
+/// session revokes the whole chain.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Session {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub refresh_token_hash: String,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    #[serde(with = "time::serde::iso8601")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::iso8601")]
+    pub last_used_at: OffsetDateTime,
+    /// The login passed a second factor (TOTP or recovery code)
+    #[serde(default)]
+    pub mfa_verified: bool,
+    /// Viewer profile selected on this device; `None` uses the account's default profile
+    #[serde(default)]
+    pub profile_id: Option<Uuid>,
+    /// Set on sessions an admin opened to act as this user (support impersonation)
+    #[serde(default)]
+    pub impersonator: Option<Actor>,
+}
+
+/// State of a device authorization grant (RFC 8628), stored in Redis as JSON under
+/// `device_code:{hash}` until it expires or the device collects its tokens.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DeviceAuthorization {
+    pub user_code: String,
+    /// Name the device gave itself, shown to the user before approving
+    pub client_name: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    #[serde(with = "time::serde::iso8601")]
+    pub created_at: OffsetDateTime,
+    pub status: DeviceAuthorizationStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum DeviceAuthorizationStatus {
+    Pending,
+    /// Approved by `user_id`; `mfa_verified` is copied from the approving session
+    Approved { user_id: Uuid, mfa_verified: bool },
+    Denied,
+}
+
+/// The admin behind an impersonation session, carried in the token as the `act` claim (RFC 8693).
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct Actor {
+    pub sub: Uuid,
+    pub role: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_round_trips_through_its_stored_json() {
+        let session = Session {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            refresh_token_hash: "hash".to_string(),
+            ip_address: Some("203.0.113.7".to_string()),
+            user_agent: Some("HiuraMovie TV/1.0".to_string()),
+            created_at: OffsetDateTime::from_unix_timestamp(1_767_225_600).unwrap(),
+            last_used_at: OffsetDateTime::from_unix_timestamp(1_767_229_200).unwrap(),
+            mfa_verified: true,
+            profile_id: Some(Uuid::new_v4()),
+            impersonator: None,
+        };
+
+        let stored: Session = serde_json::from_str(&serde_json::to_string(&session).unwrap()).unwrap();
+        assert_eq!(stored.id, session.id);
+        assert_eq!(stored.refresh_token_hash, session.refresh_token_hash);
+        assert_eq!(stored.ip_address, session.ip_address);
+        assert_eq!(stored.user_agent, session.user_agent);
+        assert_eq!(stored.last_used_at, session.last_used_at);
+        assert!(stored.mfa_verified);
+        assert_eq!(stored.profile_id, session.profile_id);
+    }
+
+    #[test]
+    fn sessions_stored_before_later_fields_still_load() {
+        let stored = r#"{
+            "id": "7f9c24e5-2f5e-4b5a-9d4e-0c6a1b2c3d4e",
+            "user_id": "0b1f6f3e-8c2d-4e5f-a6b7-c8d9e0f1a2b3",
+            "refresh_token_hash": "hash",
+            "ip_address": null,
+            "user_agent": null,
+            "created_at": "+002026-01-01T00:00:00.000000000Z",
+            "last_used_at": "+002026-01-01T00:00:00.000000000Z"
+        }"#;
+
+        let session: Session = serde_json::from_str(stored).unwrap();
+        assert!(!session.mfa_verified);
+        assert!(session.profile_id.is_none());
+        assert!(session.impersonator.is_none());
+    }
+}
@@ -1,8 +1,10 @@
-use crate::modules::auth::model::{User, UserRole};
+use crate::modules::auth::model::{DeviceAuthorization, LoginLockoutEvent, Session, TokenPurpose, User, UserIdentity};
+use crate::modules::role::model::ROLE_USER;
 use anyhow::Result;
 use redis::aio::MultiplexedConnection;
 use redis::AsyncCommands;
-use sqlx::{PgPool, Postgres, Transaction};
+use sqlx::PgPool;
+use time::OffsetDateTime;
 use uuid::Uuid;
 
 pub struct AuthRepository;
@@ -20,13 +22,14 @@ impl AuthRepository {
             r#"
             INSERT INTO users (username, email, password_hash, full_name, role)
             VALUES ($1, $2, $3, $4, $5)
-            RETURNING id, username, email, full_name, role as "role: UserRole", password_hash, created_at, updated_at
+            RETURNING id, username, email, full_name, role, password_hash, email_verified_at, totp_secret, totp_enabled_at, avatar_url,
+                      suspended_at, suspended_until, suspension_reason, created_at, updated_at
             "#,
             username,
             email,
             password_hash,
             full_name,
-            UserRole::User as UserRole
+            ROLE_USER
         )
         .fetch_one(pool)
         .await?;
@@ -38,7 +41,8 @@ impl AuthRepository {
         let user = sqlx::query_as!(
             User,
             r#"
-            SELECT id, username, email, full_name, role as "role: UserRole", password_hash, created_at, updated_at
+            SELECT id, username, email, full_name, role, password_hash, email_verified_at, totp_secret, totp_enabled_at, avatar_url,
+                   suspended_at, suspended_until, suspension_reason, created_at, updated_at
             FROM users
             WHERE email = $1
             "#,
@@ -54,7 +58,8 @@ impl AuthRepository {
         let user = sqlx::query_as!(
             User,
             r#"
-            SELECT id, username, email, full_name, role as "role: UserRole", password_hash, created_at, updated_at
+            SELECT id, username, email, full_name, role, password_hash, email_verified_at, totp_secret, totp_enabled_at, avatar_url,
+                   suspended_at, suspended_until, suspension_reason, created_at, updated_at
             FROM users
             WHERE username = $1
             "#,
@@ -70,7 +75,8 @@ impl AuthRepository {
         let user = sqlx::query_as!(
             User,
             r#"
-            SELECT id, username, email, full_name, role as "role: UserRole", password_hash, created_at, updated_at
+            SELECT id, username, email, full_name, role, password_hash, email_verified_at, totp_secret, totp_enabled_at, avatar_url,
+                   suspended_at, suspended_until, suspension_reason, created_at, updated_at
             FROM users
             WHERE id = $1
             "#,
@@ -82,32 +88,697 @@ impl AuthRepository {
         Ok(user)
     }
 
-    pub async fn store_refresh_token(
+    pub async fn store_session(
+        redis: &mut MultiplexedConnection,
+        session: &Session,
+        ttl_seconds: u64,
+    ) -> Result<()> {
+        let payload = serde_json::to_string(session)?;
+        let _: () = redis.set_ex(format!("session:{}", session.id), payload, ttl_seconds).await?;
+        let _: () = redis
+            .set_ex(format!("refresh_token:{}", session.refresh_token_hash), session.id.to_string(), ttl_seconds)
+            .await?;
+
+        let index_key = format!("user_sessions:{}", session.user_id);
+        let _: () = redis.sadd(&index_key, session.id.to_string()).await?;
+        let _: () = redis.expire(&index_key, ttl_seconds as i64).await?;
+        Ok(())
+    }
+
+    /// Stores a short-lived impersonation session. It has no refresh token and joins the
+    /// user's session index without shortening the index expiry set by regular logins.
+    pub async fn store_impersonation_session(
+        redis: &mut MultiplexedConnection,
+        session: &Session,
+        ttl_seconds: u64,
+    ) -> Result<()> {
+        let payload = serde_json::to_string(session)?;
+        let _: () = redis.set_ex(format!("session:{}", session.id), payload, ttl_seconds).await?;
+
+        let index_key = format!("user_sessions:{}", session.user_id);
+        let _: () = redis.sadd(&index_key, session.id.to_string()).await?;
+        let index_ttl: i64 = redis.ttl(&index_key).await?;
+        if index_ttl < ttl_seconds as i64 {
+            let _: () = redis.expire(&index_key, ttl_seconds as i64).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn get_session(
+        redis: &mut MultiplexedConnection,
+        session_id: Uuid,
+    ) -> Result<Option<Session>> {
+        let payload: Option<String> = redis.get(format!("session:{}", session_id)).await?;
+        match payload {
+            Some(p) => Ok(Some(serde_json::from_str(&p)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Saves changes to a session without touching its expiry or refresh token.
+    pub async fn update_session(redis: &mut MultiplexedConnection, session: &Session) -> Result<()> {
+        let payload = serde_json::to_string(session)?;
+        let _: () = redis::cmd("SET")
+            .arg(format!("session:{}", session.id))
+            .arg(payload)
+            .arg("KEEPTTL")
+            .query_async(redis)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn session_exists(redis: &mut MultiplexedConnection, session_id: Uuid) -> Result<bool> {
+        let exists: bool = redis.exists(format!("session:{}", session_id)).await?;
+        Ok(exists)
+    }
+
+    pub async fn find_session_id_by_refresh_token(
+        redis: &mut MultiplexedConnection,
+        refresh_token_hash: &str,
+    ) -> Result<Option<Uuid>> {
+        let session_id: Option<String> = redis.get(format!("refresh_token:{}", refresh_token_hash)).await?;
+        Ok(session_id.and_then(|id| Uuid::parse_str(&id).ok()))
+    }
+
+    /// Looks up the family (session) of a refresh token that was already rotated away.
+    pub async fn find_session_id_by_rotated_token(
+        redis: &mut MultiplexedConnection,
+        refresh_token_hash: &str,
+    ) -> Result<Option<Uuid>> {
+        let session_id: Option<String> = redis.get(format!("rotated_refresh_token:{}", refresh_token_hash)).await?;
+        Ok(session_id.and_then(|id| Uuid::parse_str(&id).ok()))
+    }
+
+    /// Replaces the refresh token of an existing session (token rotation).
+    /// The old hash is remembered for the lifetime of the family so that reuse can be detected.
+    pub async fn rotate_session_token(
+        redis: &mut MultiplexedConnection,
+        session: &Session,
+        old_refresh_token_hash: &str,
+        ttl_seconds: u64,
+    ) -> Result<()> {
+        let _: () = redis.del(format!("refresh_token:{}", old_refresh_token_hash)).await?;
+        let _: () = redis
+            .set_ex(
+                format!("rotated_refresh_token:{}", old_refresh_token_hash),
+                session.id.to_string(),
+                ttl_seconds,
+            )
+            .await?;
+        Self::store_session(redis, session, ttl_seconds).await
+    }
+
+    /// Returns all live sessions of a user, pruning ids whose session already expired.
+    pub async fn list_sessions(redis: &mut MultiplexedConnection, user_id: Uuid) -> Result<Vec<Session>> {
+        let index_key = format!("user_sessions:{}", user_id);
+        let ids: Vec<String> = redis.smembers(&index_key).await?;
+
+        let mut sessions = Vec::with_capacity(ids.len());
+        for id in ids {
+            let session = match Uuid::parse_str(&id) {
+                Ok(session_id) => Self::get_session(redis, session_id).await?,
+                Err(_) => None,
+            };
+
+            match session {
+                Some(s) => sessions.push(s),
+                None => {
+                    let _: () = redis.srem(&index_key, &id).await?;
+                }
+            }
+        }
+
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.last_used_at));
+        Ok(sessions)
+    }
+
+    pub async fn delete_session(redis: &mut MultiplexedConnection, session: &Session) -> Result<()> {
+        let _: () = redis.del(format!("session:{}", session.id)).await?;
+        let _: () = redis.del(format!("refresh_token:{}", session.refresh_token_hash)).await?;
+        let _: () = redis
+            .srem(format!("user_sessions:{}", session.user_id), session.id.to_string())
+            .await?;
+        Ok(())
+    }
+
+    pub async fn update_password(pool: &PgPool, user_id: Uuid, password_hash: &str) -> Result<()> {
+        sqlx::query!(
+            "UPDATE users SET password_hash = $1, updated_at = NOW() WHERE id = $2",
+            password_hash,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn mark_email_verified(pool: &PgPool, user_id: Uuid) -> Result<()> {
+        sqlx::query!(
+            "UPDATE users SET email_verified_at = COALESCE(email_verified_at, NOW()), updated_at = NOW() WHERE id = $1",
+            user_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    // --- LOGIN THROTTLING ---
+    // `subject` is "email:{address}" or "ip:{address}"
+
+    /// Counts a failed login within the sliding window and returns the failures so far.
+    pub async fn increment_login_failures(
+        redis: &mut MultiplexedConnection,
+        subject: &str,
+        window_seconds: u64,
+    ) -> Result<u64> {
+        let key = format!("login_failures:{}", subject);
+        let failures: u64 = redis.incr(&key, 1).await?;
+        let _: () = redis.expire(&key, window_seconds as i64).await?;
+        Ok(failures)
+    }
+
+    /// Seconds left on a lockout or delay for the subject, if any.
+    pub async fn login_block_ttl(redis: &mut MultiplexedConnection, subject: &str) -> Result<Option<u64>> {
+        for key in [format!("login_lock:{}", subject), format!("login_delay:{}", subject)] {
+            let ttl: i64 = redis.ttl(&key).await?;
+            if ttl > 0 {
+                return Ok(Some(ttl as u64));
+            }
+        }
+        Ok(None)
+    }
+
+    pub async fn set_login_delay(redis: &mut MultiplexedConnection, subject: &str, seconds: u64) -> Result<()> {
+        let _: () = redis.set_ex(format!("login_delay:{}", subject), 1, seconds).await?;
+        Ok(())
+    }
+
+    pub async fn set_login_lock(redis: &mut MultiplexedConnection, subject: &str, seconds: u64) -> Result<()> {
+        let _: () = redis.set_ex(format!("login_lock:{}", subject), 1, seconds).await?;
+        Ok(())
+    }
+
+    /// Resets failures, delay and lockout of a subject. Returns true if it was locked.
+    pub async fn clear_login_failures(redis: &mut MultiplexedConnection, subject: &str) -> Result<bool> {
+        let was_locked: bool = redis.exists(format!("login_lock:{}", subject)).await?;
+        let _: () = redis
+            .del(&[
+                format!("login_failures:{}", subject),
+                format!("login_delay:{}", subject),
+                format!("login_lock:{}", subject),
+            ])
+            .await?;
+        Ok(was_locked)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn record_lockout_event(
+        pool: &PgPool,
+        event: &str,
+        scope: &str,
+        email: Option<&str>,
+        ip_address: Option<&str>,
+        user_id: Option<Uuid>,
+        failed_attempts: i32,
+        locked_until: Option<OffsetDateTime>,
+        reason: Option<&str>,
+        actor_id: Option<Uuid>,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO login_lockout_events
+                (event, scope, email, ip_address, user_id, failed_attempts, locked_until, reason, actor_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+            event,
+            scope,
+            email,
+            ip_address,
+            user_id,
+            failed_attempts,
+            locked_until,
+            reason,
+            actor_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Most recent lockout events, optionally for one email address.
+    pub async fn list_lockout_events(
+        pool: &PgPool,
+        email: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<LoginLockoutEvent>> {
+        let events = sqlx::query_as!(
+            LoginLockoutEvent,
+            r#"
+            SELECT id, event, scope, email, ip_address, user_id, failed_attempts, locked_until, reason, actor_id, created_at
+            FROM login_lockout_events
+            WHERE ($1::VARCHAR IS NULL OR email = $1)
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+            email,
+            limit,
+            offset
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(events)
+    }
+
+    /// Strips the email and IP address from the lockout history of a deleted account.
+    /// The events themselves stay, they describe attacks rather than the user.
+    pub async fn anonymize_lockout_events(pool: &PgPool, user_id: Uuid, email: &str) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE login_lockout_events
+            SET email = NULL, ip_address = NULL, user_id = NULL
+            WHERE user_id = $1 OR (scope = 'EMAIL' AND email = $2)
+            "#,
+            user_id,
+            email
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    // --- EXTERNAL IDENTITIES (OIDC) ---
+
+    pub async fn find_user_by_identity(pool: &PgPool, provider: &str, subject: &str) -> Result<Option<User>> {
+        let user = sqlx::query_as!(
+            User,
+            r#"
+            SELECT u.id, u.username, u.email, u.full_name, u.role, u.password_hash,
+                   u.email_verified_at, u.totp_secret, u.totp_enabled_at, u.avatar_url,
+                   u.suspended_at, u.suspended_until, u.suspension_reason, u.created_at, u.updated_at
+            FROM users u
+            JOIN user_identities i ON i.user_id = u.id
+            WHERE i.provider = $1 AND i.subject = $2
+            "#,
+            provider,
+            subject
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(user)
+    }
+
+    /// Links an external identity to a user, or refreshes it if already linked.
+    pub async fn upsert_identity(
+        pool: &PgPool,
+        user_id: Uuid,
+        provider: &str,
+        subject: &str,
+        email: Option<&str>,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO user_identities (user_id, provider, subject, email)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (provider, subject)
+            DO UPDATE SET email = EXCLUDED.email, last_login_at = NOW()
+            "#,
+            user_id,
+            provider,
+            subject,
+            email
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn list_identities(pool: &PgPool, user_id: Uuid) -> Result<Vec<UserIdentity>> {
+        let identities = sqlx::query_as!(
+            UserIdentity,
+            r#"
+            SELECT provider, subject, email, created_at, last_login_at
+            FROM user_identities
+            WHERE user_id = $1
+            ORDER BY created_at
+            "#,
+            user_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(identities)
+    }
+
+    /// Keeps the PKCE verifier and nonce of an authorization request until the callback.
+    pub async fn store_oidc_request(
+        redis: &mut MultiplexedConnection,
+        oauth_state: &str,
+        payload: &str,
+        ttl_seconds: u64,
+    ) -> Result<()> {
+        let _: () = redis.set_ex(format!("oidc_state:{}", oauth_state), payload, ttl_seconds).await?;
+        Ok(())
+    }
+
+    /// Returns and deletes a pending authorization request, so each state is used once.
+    pub async fn take_oidc_request(redis: &mut MultiplexedConnection, oauth_state: &str) -> Result<Option<String>> {
+        let payload: Option<String> = redis.get_del(format!("oidc_state:{}", oauth_state)).await?;
+        Ok(payload)
+    }
+
+    // --- DEVICE AUTHORIZATION ---
+
+    /// Stores a new device authorization under the hash of its device code, and the
+    /// user code pointing to it. Returns false without storing anything when the user
+    /// code is already in use, so the caller can draw another one.
+    pub async fn create_device_authorization(
+        redis: &mut MultiplexedConnection,
+        device_code_hash: &str,
+        authorization: &DeviceAuthorization,
+        ttl_seconds: u64,
+    ) -> Result<bool> {
+        let claimed: bool = redis::cmd("SET")
+            .arg(format!("device_user_code:{}", authorization.user_code))
+            .arg(device_code_hash)
+            .arg("NX")
+            .arg("EX")
+            .arg(ttl_seconds)
+            .query_async::<Option<String>>(redis)
+            .await?
+            .is_some();
+        if !claimed {
+            return Ok(false);
+        }
+
+        let payload = serde_json::to_string(authorization)?;
+        let _: () = redis.set_ex(format!("device_code:{}", device_code_hash), payload, ttl_seconds).await?;
+        Ok(true)
+    }
+
+    pub async fn get_device_authorization(
+        redis: &mut MultiplexedConnection,
+        device_code_hash: &str,
+    ) -> Result<Option<DeviceAuthorization>> {
+        let payload: Option<String> = redis.get(format!("device_code:{}", device_code_hash)).await?;
+        match payload {
+            Some(p) => Ok(Some(serde_json::from_str(&p)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn find_device_code_hash(redis: &mut MultiplexedConnection, user_code: &str) -> Result<Option<String>> {
+        let hash: Option<String> = redis.get(format!("device_user_code:{}", user_code)).await?;
+        Ok(hash)
+    }
+
+    /// Saves the approval decision without extending the authorization's expiry.
+    pub async fn update_device_authorization(
+        redis: &mut MultiplexedConnection,
+        device_code_hash: &str,
+        authorization: &DeviceAuthorization,
+    ) -> Result<()> {
+        let payload = serde_json::to_string(authorization)?;
+        let _: () = redis::cmd("SET")
+            .arg(format!("device_code:{}", device_code_hash))
+            .arg(payload)
+            .arg("XX")
+            .arg("KEEPTTL")
+            .query_async(redis)
+            .await?;
+        Ok(())
+    }
+
+    /// Returns and deletes a device authorization, so approved tokens are handed out once.
+    pub async fn take_device_authorization(
+        redis: &mut MultiplexedConnection,
+        device_code_hash: &str,
+    ) -> Result<Option<DeviceAuthorization>> {
+        let payload: Option<String> = redis.get_del(format!("device_code:{}", device_code_hash)).await?;
+        let Some(payload) = payload else {
+            return Ok(None);
+        };
+
+        let authorization: DeviceAuthorization = serde_json::from_str(&payload)?;
+        let _: () = redis.del(format!("device_user_code:{}", authorization.user_code)).await?;
+        Ok(Some(authorization))
+    }
+
+    /// Marks a poll of the device code. Returns false if the device polled again
+    /// within `interval_seconds`.
+    pub async fn record_device_poll(
+        redis: &mut MultiplexedConnection,
+        device_code_hash: &str,
+        interval_seconds: u64,
+    ) -> Result<bool> {
+        let result: Option<String> = redis::cmd("SET")
+            .arg(format!("device_poll:{}", device_code_hash))
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(interval_seconds)
+            .query_async(redis)
+            .await?;
+        Ok(result.is_some())
+    }
+
+    /// Counts a lookup of an unknown user code by `user_id` and returns the failures so far.
+    pub async fn record_device_code_failure(
+        redis: &mut MultiplexedConnection,
+        user_id: Uuid,
+        ttl_seconds: u64,
+    ) -> Result<u64> {
+        let key = format!("device_code_failures:{}", user_id);
+        let failures: u64 = redis.incr(&key, 1).await?;
+        let _: () = redis.expire(&key, ttl_seconds as i64).await?;
+        Ok(failures)
+    }
+
+    pub async fn device_code_failures(redis: &mut MultiplexedConnection, user_id: Uuid) -> Result<u64> {
+        let failures: Option<u64> = redis.get(format!("device_code_failures:{}", user_id)).await?;
+        Ok(failures.unwrap_or(0))
+    }
+
+    // --- TWO-FACTOR ---
+
+    /// Enables TOTP with the given secret, or disables it when `secret` is `None`.
+    pub async fn set_totp_secret(pool: &PgPool, user_id: Uuid, secret: Option<&str>) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE users
+            SET totp_secret = $1,
+                totp_enabled_at = CASE WHEN $1::VARCHAR IS NULL THEN NULL ELSE NOW() END,
+                updated_at = NOW()
+            WHERE id = $2
+            "#,
+            secret,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Replaces all recovery codes of a user with a new set of hashes.
+    pub async fn replace_recovery_codes(pool: &PgPool, user_id: Uuid, code_hashes: &[String]) -> Result<()> {
+        let mut tx = pool.begin().await?;
+
+        sqlx::query!("DELETE FROM user_recovery_codes WHERE user_id = $1", user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO user_recovery_codes (user_id, code_hash)
+            SELECT $1, UNNEST($2::VARCHAR[])
+            "#,
+            user_id,
+            code_hashes
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Marks an unused recovery code as used. Returns false if no such code exists.
+    pub async fn consume_recovery_code(pool: &PgPool, user_id: Uuid, code_hash: &str) -> Result<bool> {
+        let row = sqlx::query!(
+            r#"
+            UPDATE user_recovery_codes
+            SET used_at = NOW()
+            WHERE user_id = $1 AND code_hash = $2 AND used_at IS NULL
+            RETURNING id
+            "#,
+            user_id,
+            code_hash
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.is_some())
+    }
+
+    pub async fn delete_recovery_codes(pool: &PgPool, user_id: Uuid) -> Result<()> {
+        sqlx::query!("DELETE FROM user_recovery_codes WHERE user_id = $1", user_id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Keeps a not yet confirmed TOTP secret while the user sets up their authenticator.
+    pub async fn store_pending_totp_secret(
         redis: &mut MultiplexedConnection,
         user_id: Uuid,
-        refresh_token: &str,
-        ttl_seconds: usize,
+        secret: &str,
+        ttl_seconds: u64,
     ) -> Result<()> {
-        let key = format!("refresh_token:{}", user_id);
-        let _: () = redis.set_ex(key, refresh_token, ttl_seconds as u64).await?;
+        let _: () = redis.set_ex(format!("mfa_enrollment:{}", user_id), secret, ttl_seconds).await?;
         Ok(())
     }
 
-    pub async fn get_refresh_token(
+    pub async fn get_pending_totp_secret(redis: &mut MultiplexedConnection, user_id: Uuid) -> Result<Option<String>> {
+        let secret: Option<String> = redis.get(format!("mfa_enrollment:{}", user_id)).await?;
+        Ok(secret)
+    }
+
+    pub async fn delete_pending_totp_secret(redis: &mut MultiplexedConnection, user_id: Uuid) -> Result<()> {
+        let _: () = redis.del(format!("mfa_enrollment:{}", user_id)).await?;
+        Ok(())
+    }
+
+    /// Records that a TOTP time step was used. Returns false if it was already used,
+    /// so a code cannot be replayed within its validity window.
+    pub async fn mark_totp_step_used(
         redis: &mut MultiplexedConnection,
         user_id: Uuid,
-    ) -> Result<Option<String>> {
-        let key = format!("refresh_token:{}", user_id);
-        let token: Option<String> = redis.get(key).await?;
-        Ok(token)
+        step: u64,
+        ttl_seconds: u64,
+    ) -> Result<bool> {
+        let fresh: bool = redis::cmd("SET")
+            .arg(format!("totp_used:{}:{}", user_id, step))
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(ttl_seconds)
+            .query_async::<Option<String>>(redis)
+            .await?
+            .is_some();
+        Ok(fresh)
     }
 
-    pub async fn delete_refresh_token(
+    /// Stores the pending second login step. The challenge token is only stored hashed.
+    pub async fn store_mfa_challenge(
         redis: &mut MultiplexedConnection,
+        challenge_hash: &str,
         user_id: Uuid,
+        ttl_seconds: u64,
     ) -> Result<()> {
-        let key = format!("refresh_token:{}", user_id);
-        let _: () = redis.del(key).await?;
+        let _: () = redis
+            .set_ex(format!("mfa_challenge:{}", challenge_hash), user_id.to_string(), ttl_seconds)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn get_mfa_challenge(redis: &mut MultiplexedConnection, challenge_hash: &str) -> Result<Option<Uuid>> {
+        let user_id: Option<String> = redis.get(format!("mfa_challenge:{}", challenge_hash)).await?;
+        Ok(user_id.and_then(|id| Uuid::parse_str(&id).ok()))
+    }
+
+    /// Counts a failed code for a challenge and returns the number of failures so far.
+    pub async fn record_mfa_challenge_failure(
+        redis: &mut MultiplexedConnection,
+        challenge_hash: &str,
+        ttl_seconds: u64,
+    ) -> Result<u64> {
+        let key = format!("mfa_challenge_failures:{}", challenge_hash);
+        let failures: u64 = redis.incr(&key, 1).await?;
+        let _: () = redis.expire(&key, ttl_seconds as i64).await?;
+        Ok(failures)
+    }
+
+    pub async fn delete_mfa_challenge(redis: &mut MultiplexedConnection, challenge_hash: &str) -> Result<()> {
+        let _: () = redis.del(format!("mfa_challenge:{}", challenge_hash)).await?;
+        let _: () = redis.del(format!("mfa_challenge_failures:{}", challenge_hash)).await?;
+        Ok(())
+    }
+
+    // --- USER TOKENS (email verification, ...) ---
+
+    pub async fn create_user_token(
+        pool: &PgPool,
+        user_id: Uuid,
+        purpose: TokenPurpose,
+        token_hash: &str,
+        expires_at: OffsetDateTime,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO user_tokens (user_id, purpose, token_hash, expires_at)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            user_id,
+            purpose.as_str(),
+            token_hash,
+            expires_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Owner of a valid, unused token, without using it up.
+    pub async fn find_user_token_owner(
+        pool: &PgPool,
+        token_hash: &str,
+        purpose: TokenPurpose,
+    ) -> Result<Option<Uuid>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT user_id
+            FROM user_tokens
+            WHERE token_hash = $1 AND purpose = $2 AND used_at IS NULL AND expires_at > NOW()
+            "#,
+            token_hash,
+            purpose.as_str()
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| r.user_id))
+    }
+
+    /// Marks a valid token as used and returns its owner. Each token can be consumed once.
+    pub async fn consume_user_token(
+        pool: &PgPool,
+        token_hash: &str,
+        purpose: TokenPurpose,
+    ) -> Result<Option<Uuid>> {
+        let row = sqlx::query!(
+            r#"
+            UPDATE user_tokens
+            SET used_at = NOW()
+            WHERE token_hash = $1 AND purpose = $2 AND used_at IS NULL AND expires_at > NOW()
+            RETURNING user_id
+            "#,
+            token_hash,
+            purpose.as_str()
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| r.user_id))
+    }
+
+    /// Invalidates all outstanding tokens of a user for the given purpose.
+    pub async fn invalidate_user_tokens(pool: &PgPool, user_id: Uuid, purpose: TokenPurpose) -> Result<()> {
+        sqlx::query!(
+            "UPDATE user_tokens SET used_at = NOW() WHERE user_id = $1 AND purpose = $2 AND used_at IS NULL",
+            user_id,
+            purpose.as_str()
+        )
+        .execute(pool)
+        .await?;
         Ok(())
     }
 }
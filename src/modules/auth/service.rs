@@ -1,35 +1,115 @@
-use super::dto::{AuthResponse, LoginRequest, RegisterRequest, TokenClaims, UserResponse};
-use super::model::UserRole;
+use super::dto::{
+    AuthResponse, DeviceApprovalRequest, DeviceAuthorizationResponse, DeviceCodeRequest, DeviceCodeResponse, ForgotPasswordRequest, LockoutEventsQuery, LoginRequest, MfaChallengeResponse, MfaCodeRequest, MfaLoginRequest,
+    RecoveryCodesResponse, RegisterRequest, ResendVerificationRequest, ResetPasswordRequest, SessionResponse,
+    TokenClaims, TotpSetupResponse, UserResponse, VerifyEmailRequest,
+};
+use super::model::{DeviceAuthorization, DeviceAuthorizationStatus, LoginLockoutEvent, Session, TokenPurpose, User};
 use super::repository::AuthRepository;
 use crate::state::AppState;
-use crate::common::security;
-use anyhow::{anyhow, Result};
-use jsonwebtoken::{encode, get_current_timestamp, EncodingKey, Header};
+use crate::common::{security, totp};
+use crate::common::types::ClientContext;
+use crate::infrastructure::mail::sender::MailMessage;
+use crate::infrastructure::oidc::client::IdTokenClaims;
+use crate::modules::role::model::ROLE_ADMIN;
+use crate::modules::role::service::RoleService;
+use crate::common::error::{AppError, AppResult};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::get_current_timestamp;
+use rand::Rng;
 use redis::AsyncCommands;
-use time::Duration;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use time::{Duration, OffsetDateTime};
 use uuid::Uuid;
 
+pub const ACCESS_TOKEN_TTL_SECS: u64 = 15 * 60; // 15 minutes
+pub const REFRESH_TOKEN_TTL_SECS: u64 = 7 * 24 * 60 * 60; // 7 days
+const EMAIL_VERIFICATION_TTL_HOURS: i64 = 24;
+const PASSWORD_RESET_TTL_MINUTES: i64 = 60;
+const MFA_CHALLENGE_TTL_SECS: u64 = 5 * 60;
+const MFA_MAX_ATTEMPTS: u64 = 5;
+const TOTP_ENROLLMENT_TTL_SECS: u64 = 10 * 60;
+// A code is valid for at most three steps (one step skew either way)
+const TOTP_REPLAY_TTL_SECS: u64 = 3 * totp::STEP_SECS;
+const RECOVERY_CODE_COUNT: usize = 10;
+const OIDC_REQUEST_TTL_SECS: u64 = 10 * 60;
+const LOGIN_DELAY_AFTER_FAILURES: u64 = 3;
+const LOGIN_MAX_DELAY_SECS: u64 = 60;
+const DEVICE_CODE_TTL_SECS: u64 = 10 * 60;
+const DEVICE_POLL_INTERVAL_SECS: u64 = 5;
+const DEVICE_MAX_CODE_FAILURES: u64 = 10;
+// No vowels, so codes never spell words, and nothing that looks like a digit
+const USER_CODE_ALPHABET: &[u8] = b"BCDFGHJKLMNPQRSTVWXZ";
+
+/// Errors of the device token endpoint; the messages are the RFC 8628 error codes
+/// and are sent as the problem `code`.
+#[derive(Debug, thiserror::Error)]
+pub enum DeviceGrantError {
+    /// The user has not approved or denied the request yet
+    #[error("authorization_pending")]
+    AuthorizationPending,
+    /// The device polled faster than the advertised interval
+    #[error("slow_down")]
+    SlowDown { retry_after: u64 },
+    #[error("access_denied")]
+    AccessDenied,
+    /// Unknown, expired or already used device code
+    #[error("expired_token")]
+    ExpiredToken,
+    #[error(transparent)]
+    Other(#[from] AppError),
+}
+
+impl From<anyhow::Error> for DeviceGrantError {
+    fn from(e: anyhow::Error) -> Self {
+        Self::Other(e.into())
+    }
+}
+
+impl From<redis::RedisError> for DeviceGrantError {
+    fn from(e: redis::RedisError) -> Self {
+        Self::Other(e.into())
+    }
+}
+
+/// PKCE verifier and nonce of an authorization request, kept in Redis under its `state`.
+#[derive(Serialize, Deserialize)]
+struct OidcPendingRequest {
+    nonce: String,
+    code_verifier: String,
+}
+
+/// Result of the password step of a login.
+pub enum LoginOutcome {
+    /// Tokens were issued: the access token response and the raw refresh token.
+    Authenticated(AuthResponse, String),
+    /// The account has 2FA enabled; the client must call `/auth/login/mfa`.
+    MfaRequired(MfaChallengeResponse),
+}
+
 pub struct AuthService;
 
 impl AuthService {
-    pub async fn register(state: AppState, req: RegisterRequest) -> Result<UserResponse> {
+    pub async fn register(state: AppState, req: RegisterRequest) -> AppResult<UserResponse> {
         // Check if user exists
         if AuthRepository::find_user_by_email(&state.db, &req.email)
             .await?
             .is_some()
         {
-            return Err(anyhow!("Email already exists"));
+            return Err(AppError::conflict("Email already exists"));
         }
         
         if AuthRepository::find_user_by_username(&state.db, &req.username)
             .await?
             .is_some()
         {
-            return Err(anyhow!("Username already exists"));
+            return Err(AppError::conflict("Username already exists"));
         }
 
+        state.passwords.check_policy(&req.password, &[&req.username, &req.email, &req.full_name])?;
+
         // Hash password
-        let password_hash = security::hash_password(&req.password)?;
+        let password_hash = state.passwords.hash(&req.password)?;
 
         // Create user
         let user = AuthRepository::create_user(
@@ -41,74 +121,488 @@ impl AuthService {
         )
         .await?;
 
-        Ok(UserResponse {
-            id: user.id,
-            email: user.email,
-            username: user.username,
-            full_name: user.full_name,
-            role: user.role.to_string(),
-        })
+        // New accounts stay unverified until the emailed link is confirmed
+        if let Err(e) = Self::send_verification_email(&state, &user).await {
+            tracing::error!("Failed to send verification email to user {}: {}", user.id, e);
+        }
+
+        Ok(UserResponse::from(user))
+    }
+
+    // --- EMAIL VERIFICATION ---
+
+    /// Emails a fresh verification link to `user.email`; earlier links stop working.
+    pub async fn send_verification_email(state: &AppState, user: &User) -> AppResult<()> {
+        AuthRepository::invalidate_user_tokens(&state.db, user.id, TokenPurpose::EmailVerification).await?;
+
+        let token = security::generate_token();
+        let expires_at = OffsetDateTime::now_utc() + Duration::hours(EMAIL_VERIFICATION_TTL_HOURS);
+        AuthRepository::create_user_token(
+            &state.db,
+            user.id,
+            TokenPurpose::EmailVerification,
+            &security::hash_token(&token),
+            expires_at,
+        )
+        .await?;
+
+        let link = format!("{}/verify-email?token={}", state.config.app_url.trim_end_matches('/'), token);
+        let message = MailMessage {
+            to: user.email.clone(),
+            subject: "Confirm your HiuraMovie email address".to_string(),
+            body: format!(
+                "Hi {},\n\nPlease confirm your email address by opening the link below:\n{}\n\nThe link expires in {} hours.",
+                user.full_name, link, EMAIL_VERIFICATION_TTL_HOURS
+            ),
+        };
+
+        Ok(state.mailer.send(&message).await?)
     }
 
-    pub async fn login(state: AppState, req: LoginRequest) -> Result<(AuthResponse, String)> {
+    pub async fn verify_email(state: AppState, req: VerifyEmailRequest) -> AppResult<()> {
+        let user_id = AuthRepository::consume_user_token(
+            &state.db,
+            &security::hash_token(&req.token),
+            TokenPurpose::EmailVerification,
+        )
+        .await?
+        .ok_or_else(|| AppError::validation("Verification link is invalid or has expired"))?;
+
+        AuthRepository::mark_email_verified(&state.db, user_id).await?;
+        tracing::info!("Email verified for user {}", user_id);
+        Ok(())
+    }
+
+    /// Sends a fresh verification link. Unknown or already verified emails are ignored
+    /// silently so the endpoint cannot be used to discover registered addresses.
+    pub async fn resend_verification(state: AppState, req: ResendVerificationRequest) -> AppResult<()> {
+        let user = match AuthRepository::find_user_by_email(&state.db, &req.email).await? {
+            Some(u) if u.email_verified_at.is_none() => u,
+            _ => return Ok(()),
+        };
+
+        Self::send_verification_email(&state, &user).await
+    }
+
+    pub async fn login(
+        state: AppState,
+        req: LoginRequest,
+        client: ClientContext,
+    ) -> AppResult<LoginOutcome> {
         tracing::info!("Attempting login for email: {}", req.email);
+
+        Self::check_login_throttle(&state, &req.email, &client).await?;
         
-        let user = AuthRepository::find_user_by_email(&state.db, &req.email)
-            .await?
-            .ok_or_else(|| {
+        let user = match AuthRepository::find_user_by_email(&state.db, &req.email).await? {
+            Some(user) => user,
+            None => {
                 tracing::warn!("Login failed: Email {} not found", req.email);
-                anyhow!("Invalid credentials")
-            })?;
+                Self::record_login_failure(&state, &req.email, None, &client).await?;
+                return Err(AppError::unauthorized("Invalid credentials"));
+            }
+        };
 
         // Verify password
-        security::verify_password(&req.password, &user.password_hash)
-            .map_err(|_| anyhow!("Invalid credentials"))?;
+        if !state.passwords.verify(&req.password, &user.password_hash)? {
+            Self::record_login_failure(&state, &req.email, Some(user.id), &client).await?;
+            return Err(AppError::unauthorized("Invalid credentials"));
+        }
+
+        // Upgrade hashes made with older Argon2 settings while the plain password is at hand
+        if state.passwords.needs_rehash(&user.password_hash) {
+            match state.passwords.hash(&req.password) {
+                Ok(hash) => AuthRepository::update_password(&state.db, user.id, &hash).await?,
+                Err(e) => tracing::error!("Failed to rehash password of user {}: {}", user.id, e),
+            }
+        }
 
-        // Generate tokens
-        // Use secret from config
-        let access_token = Self::create_access_token(user.id, user.role.clone(), &state.config.jwt_secret)?;
-        // Format: user_id:random_uuid
-        let refresh_token = format!("{}:{}", user.id, Uuid::new_v4());
-        tracing::info!("Generated refresh token for user {}: {}", user.id, refresh_token);
+        let mut redis_conn = state.redis.get_conn().await?;
+        AuthRepository::clear_login_failures(&mut redis_conn, &Self::email_subject(&req.email)).await?;
+
+        Self::complete_login(&state, user, client).await
+    }
+
+    // --- BRUTE-FORCE PROTECTION ---
+
+    fn email_subject(email: &str) -> String {
+        format!("email:{}", email.trim().to_lowercase())
+    }
 
-        // Store refresh token in Redis (7 days)
+    /// Refuses the attempt while the email or the client IP is locked out or in a delay.
+    async fn check_login_throttle(state: &AppState, email: &str, client: &ClientContext) -> AppResult<()> {
         let mut redis_conn = state.redis.get_conn().await?;
-        let refresh_ttl = 7 * 24 * 60 * 60; // 7 days in seconds
-        AuthRepository::store_refresh_token(
+
+        let mut subjects = vec![Self::email_subject(email)];
+        if let Some(ip) = &client.ip_address {
+            subjects.push(format!("ip:{}", ip));
+        }
+
+        for subject in subjects {
+            if let Some(retry_after) = AuthRepository::login_block_ttl(&mut redis_conn, &subject).await? {
+                return Err(AppError::rate_limited(
+                    format!("Too many failed login attempts, try again in {} seconds", retry_after),
+                    Some(retry_after),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Wait before the next attempt on an email after `failures` failed ones:
+    /// none at first, then 1s, 2s, 4s, ... capped at `LOGIN_MAX_DELAY_SECS`.
+    fn login_delay_secs(failures: u64) -> Option<u64> {
+        failures
+            .checked_sub(LOGIN_DELAY_AFTER_FAILURES)
+            .map(|extra| (1u64 << extra.min(16)).min(LOGIN_MAX_DELAY_SECS))
+    }
+
+    /// Counts a failed attempt against the email and the client IP. Repeated failures
+    /// on an email add a growing delay, and both are locked once their limit is reached.
+    async fn record_login_failure(
+        state: &AppState,
+        email: &str,
+        user_id: Option<Uuid>,
+        client: &ClientContext,
+    ) -> AppResult<()> {
+        let mut redis_conn = state.redis.get_conn().await?;
+        let lockout_secs = state.config.login_lockout_minutes * 60;
+
+        let email_subject = Self::email_subject(email);
+        let failures = AuthRepository::increment_login_failures(&mut redis_conn, &email_subject, lockout_secs).await?;
+
+        if failures >= state.config.login_max_failures {
+            AuthRepository::set_login_lock(&mut redis_conn, &email_subject, lockout_secs).await?;
+            AuthRepository::record_lockout_event(
+                &state.db,
+                "LOCKED",
+                "EMAIL",
+                Some(&email.trim().to_lowercase()),
+                client.ip_address.as_deref(),
+                user_id,
+                failures as i32,
+                Some(OffsetDateTime::now_utc() + Duration::seconds(lockout_secs as i64)),
+                None,
+                None,
+            )
+            .await?;
+            tracing::warn!("Locked login for {} after {} failed attempts", email, failures);
+        } else if let Some(delay) = Self::login_delay_secs(failures) {
+            AuthRepository::set_login_delay(&mut redis_conn, &email_subject, delay).await?;
+        }
+
+        // One client guessing across many accounts
+        if let Some(ip) = &client.ip_address {
+            let ip_subject = format!("ip:{}", ip);
+            let failures = AuthRepository::increment_login_failures(&mut redis_conn, &ip_subject, lockout_secs).await?;
+
+            if failures >= state.config.login_max_failures_per_ip {
+                AuthRepository::set_login_lock(&mut redis_conn, &ip_subject, lockout_secs).await?;
+                AuthRepository::record_lockout_event(
+                    &state.db,
+                    "LOCKED",
+                    "IP",
+                    None,
+                    Some(ip),
+                    None,
+                    failures as i32,
+                    Some(OffsetDateTime::now_utc() + Duration::seconds(lockout_secs as i64)),
+                    None,
+                    None,
+                )
+                .await?;
+                tracing::warn!("Locked login for IP {} after {} failed attempts", ip, failures);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Lifts a lockout on an email address. Returns false if it was not locked.
+    pub async fn unlock_login(state: AppState, email: &str, actor_id: Option<Uuid>, reason: &str) -> AppResult<bool> {
+        let mut redis_conn = state.redis.get_conn().await?;
+        let was_locked = AuthRepository::clear_login_failures(&mut redis_conn, &Self::email_subject(email)).await?;
+
+        if was_locked {
+            let user_id = AuthRepository::find_user_by_email(&state.db, email).await?.map(|u| u.id);
+            AuthRepository::record_lockout_event(
+                &state.db,
+                "UNLOCKED",
+                "EMAIL",
+                Some(&email.trim().to_lowercase()),
+                None,
+                user_id,
+                0,
+                None,
+                Some(reason),
+                actor_id,
+            )
+            .await?;
+            tracing::info!("Unlocked login for {} ({})", email, reason);
+        }
+
+        Ok(was_locked)
+    }
+
+    pub async fn list_lockout_events(state: AppState, query: LockoutEventsQuery) -> AppResult<Vec<LoginLockoutEvent>> {
+        let limit = query.limit.unwrap_or(50).clamp(1, 200);
+        let offset = query.offset.unwrap_or(0).max(0);
+        let email = query.email.map(|e| e.trim().to_lowercase());
+
+        Ok(AuthRepository::list_lockout_events(&state.db, email.as_deref(), limit, offset).await?)
+    }
+
+    fn suspended() -> AppError {
+        AppError::forbidden("Account is suspended")
+    }
+
+    /// Runs the checks shared by every way of logging in once the user is identified,
+    /// then either issues tokens or asks for the second factor.
+    async fn complete_login(state: &AppState, user: User, client: ClientContext) -> AppResult<LoginOutcome> {
+        if user.is_suspended() {
+            return Err(Self::suspended());
+        }
+
+        if state.config.require_verified_email_login && user.email_verified_at.is_none() {
+            return Err(AppError::forbidden("Email address not verified"));
+        }
+
+        // With 2FA enabled the first factor alone only unlocks the second step
+        if user.totp_enabled_at.is_some() {
+            let mfa_token = security::generate_token();
+            let mut redis_conn = state.redis.get_conn().await?;
+            AuthRepository::store_mfa_challenge(
+                &mut redis_conn,
+                &security::hash_token(&mfa_token),
+                user.id,
+                MFA_CHALLENGE_TTL_SECS,
+            )
+            .await?;
+
+            return Ok(LoginOutcome::MfaRequired(MfaChallengeResponse {
+                mfa_required: true,
+                mfa_token,
+                expires_in: MFA_CHALLENGE_TTL_SECS,
+            }));
+        }
+
+        let (response, refresh_token) = Self::issue_session(state, user, client, false).await?;
+        Ok(LoginOutcome::Authenticated(response, refresh_token))
+    }
+
+    // --- SOCIAL LOGIN (OIDC) ---
+
+    /// Starts an authorization code + PKCE flow. Returns the provider URL to redirect
+    /// to and the `state` value, which the caller binds to the browser.
+    pub async fn oidc_authorize(state: AppState) -> AppResult<(String, String)> {
+        let oidc = state.oidc.as_ref().ok_or_else(|| AppError::not_found("Social login is not configured"))?;
+
+        let oauth_state = security::generate_token();
+        let request = OidcPendingRequest {
+            nonce: security::generate_token(),
+            code_verifier: security::generate_token(),
+        };
+        let code_challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(request.code_verifier.as_bytes()));
+
+        let url = oidc.authorization_url(&oauth_state, &request.nonce, &code_challenge).await?;
+
+        let mut redis_conn = state.redis.get_conn().await?;
+        AuthRepository::store_oidc_request(
             &mut redis_conn,
-            user.id,
-            &refresh_token,
-            refresh_ttl as usize,
+            &oauth_state,
+            &serde_json::to_string(&request)?,
+            OIDC_REQUEST_TTL_SECS,
         )
         .await?;
-        
 
-        let user_response = UserResponse {
-            id: user.id,
-            email: user.email,
-            username: user.username,
-            full_name: user.full_name,
-            role: user.role.to_string(),
+        Ok((url, oauth_state))
+    }
+
+    /// Finishes the flow: exchanges the code, then logs in the linked user, links an
+    /// existing account with the same verified email, or creates a new account.
+    pub async fn oidc_callback(
+        state: AppState,
+        code: &str,
+        oauth_state: &str,
+        client: ClientContext,
+    ) -> AppResult<LoginOutcome> {
+        let oidc = state.oidc.as_ref().ok_or_else(|| AppError::not_found("Social login is not configured"))?;
+
+        let mut redis_conn = state.redis.get_conn().await?;
+        let payload = AuthRepository::take_oidc_request(&mut redis_conn, oauth_state)
+            .await?
+            .ok_or_else(|| AppError::unauthorized("Login request expired or invalid, please try again"))?;
+        let request: OidcPendingRequest = serde_json::from_str(&payload)?;
+
+        let claims = oidc.exchange_code(code, &request.code_verifier, &request.nonce).await?;
+        let provider = oidc.provider();
+
+        let user = match AuthRepository::find_user_by_identity(&state.db, provider, &claims.sub).await? {
+            Some(user) => user,
+            None => {
+                let email = claims
+                    .email
+                    .clone()
+                    .ok_or_else(|| AppError::unauthorized("The identity provider did not share an email address"))?;
+
+                match AuthRepository::find_user_by_email(&state.db, &email).await? {
+                    // Only link when both sides vouch for the address, otherwise whoever
+                    // registered it first could take over the other account
+                    Some(existing) => {
+                        if !claims.email_verified || existing.email_verified_at.is_none() {
+                            return Err(AppError::conflict(
+                                "An account with this email already exists, log in with your password first",
+                            ));
+                        }
+                        tracing::info!("Linking OIDC identity {} to existing user {}", claims.sub, existing.id);
+                        existing
+                    }
+                    None => Self::create_oidc_user(&state, &claims, &email).await?,
+                }
+            }
+        };
+
+        AuthRepository::upsert_identity(&state.db, user.id, provider, &claims.sub, claims.email.as_deref()).await?;
+        Self::complete_login(&state, user, client).await
+    }
+
+    async fn create_oidc_user(state: &AppState, claims: &IdTokenClaims, email: &str) -> AppResult<User> {
+        let base: String = claims
+            .preferred_username
+            .as_deref()
+            .unwrap_or_else(|| email.split('@').next().unwrap_or_default())
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '.')
+            .take(40)
+            .collect();
+        let base = if base.len() < 3 { "user".to_string() } else { base };
+
+        let mut username = base.clone();
+        while AuthRepository::find_user_by_username(&state.db, &username).await?.is_some() {
+            username = format!("{}_{}", base, &security::generate_token()[..6]);
+        }
+
+        // No usable password: the account logs in through the provider or a password reset
+        let password_hash = state.passwords.hash(&security::generate_token())?;
+        let full_name = claims.name.clone().unwrap_or_else(|| username.clone());
+
+        let user = AuthRepository::create_user(&state.db, &username, email, &password_hash, &full_name).await?;
+        tracing::info!("Created user {} from OIDC identity {}", user.id, claims.sub);
+
+        if claims.email_verified {
+            AuthRepository::mark_email_verified(&state.db, user.id).await?;
+            return AuthRepository::find_user_by_id(&state.db, user.id)
+                .await?
+                .ok_or_else(|| AppError::not_found("User not found"));
+        }
+
+        if let Err(e) = Self::send_verification_email(state, &user).await {
+            tracing::error!("Failed to send verification email to user {}: {}", user.id, e);
+        }
+        Ok(user)
+    }
+
+    /// Second login step: exchanges the MFA challenge and a TOTP or recovery code for tokens.
+    pub async fn login_mfa(
+        state: AppState,
+        req: MfaLoginRequest,
+        client: ClientContext,
+    ) -> AppResult<(AuthResponse, String)> {
+        let challenge_hash = security::hash_token(&req.mfa_token);
+        let mut redis_conn = state.redis.get_conn().await?;
+
+        let user_id = AuthRepository::get_mfa_challenge(&mut redis_conn, &challenge_hash)
+            .await?
+            .ok_or_else(|| AppError::unauthorized("MFA challenge expired or invalid"))?;
+
+        let user = AuthRepository::find_user_by_id(&state.db, user_id)
+            .await?
+            .ok_or_else(|| AppError::unauthorized("User not found"))?;
+
+        if user.is_suspended() {
+            AuthRepository::delete_mfa_challenge(&mut redis_conn, &challenge_hash).await?;
+            return Err(Self::suspended());
+        }
+
+        if let Err(e) = Self::verify_second_factor(&state, &user, &req.code).await {
+            let failures = AuthRepository::record_mfa_challenge_failure(
+                &mut redis_conn,
+                &challenge_hash,
+                MFA_CHALLENGE_TTL_SECS,
+            )
+            .await?;
+
+            // Guessing is capped per challenge; the password has to be entered again afterwards
+            if failures >= MFA_MAX_ATTEMPTS {
+                AuthRepository::delete_mfa_challenge(&mut redis_conn, &challenge_hash).await?;
+                tracing::warn!("Too many invalid MFA codes for user {}, challenge discarded", user.id);
+                return Err(AppError::rate_limited("Too many invalid codes, please log in again", None));
+            }
+            return Err(e);
+        }
+
+        AuthRepository::delete_mfa_challenge(&mut redis_conn, &challenge_hash).await?;
+        Self::issue_session(&state, user, client, true).await
+    }
+
+    /// Opens a new session for a user whose credentials were checked, and signs the first access token.
+    async fn issue_session(
+        state: &AppState,
+        user: User,
+        client: ClientContext,
+        mfa_verified: bool,
+    ) -> AppResult<(AuthResponse, String)> {
+        // Every login opens a new session, so other devices stay signed in
+        // The session is also the refresh token family; only the token hash is stored
+        let refresh_token = security::generate_token();
+        let now = OffsetDateTime::now_utc();
+        let session = Session {
+            id: Uuid::new_v4(),
+            user_id: user.id,
+            refresh_token_hash: security::hash_token(&refresh_token),
+            ip_address: client.ip_address,
+            user_agent: client.user_agent,
+            created_at: now,
+            last_used_at: now,
+            mfa_verified,
+            profile_id: None,
+            impersonator: None,
         };
 
+        // Store session in Redis (7 days)
+        let mut redis_conn = state.redis.get_conn().await?;
+        AuthRepository::store_session(&mut redis_conn, &session, REFRESH_TOKEN_TTL_SECS).await?;
+        tracing::info!("Created session {} for user {}", session.id, user.id);
+
+        // Signed with the active key (see common::jwt)
+        let access_token = Self::create_access_token(state, &user, &session).await?;
+
+        let user_response = UserResponse::from(user);
+
         Ok((
             AuthResponse {
                 access_token,
-                access_token_expires_in: 15 * 60,
-                refresh_token_expires_in: refresh_ttl,
+                access_token_expires_in: ACCESS_TOKEN_TTL_SECS,
+                refresh_token_expires_in: REFRESH_TOKEN_TTL_SECS,
                 user: user_response,
             },
             refresh_token,
         ))
     }
     
-    pub async fn logout(state: AppState, user_id: Uuid) -> Result<()> {
+    /// Ends the session the access token was issued for; other devices stay logged in.
+    pub async fn logout(state: AppState, user_id: Uuid, session_id: Uuid) -> AppResult<()> {
         let mut redis_conn = state.redis.get_conn().await?;
-        AuthRepository::delete_refresh_token(&mut redis_conn, user_id).await?;
+        let session = AuthRepository::get_session(&mut redis_conn, session_id)
+            .await?
+            .filter(|s| s.user_id == user_id);
+
+        if let Some(session) = session {
+            AuthRepository::delete_session(&mut redis_conn, &session).await?;
+        }
         Ok(())
     }
 
-    pub async fn block_token(state: AppState, token: String, ttl: usize) -> Result<()> {
+    pub async fn block_token(state: AppState, token: String, ttl: usize) -> AppResult<()> {
         let mut redis_conn = state.redis.get_conn().await?;
         let key = format!("blocked_token:{}", token);
         // Use set_ex to blocking token with expiration
@@ -116,85 +610,597 @@ impl AuthService {
         Ok(())
     }
     
-    pub async fn refresh_access(state: AppState, refresh_token: String, user_id: Uuid) -> Result<(AuthResponse, String)> {
+    pub async fn refresh_access(
+        state: AppState,
+        refresh_token: String,
+        client: ClientContext,
+    ) -> AppResult<(AuthResponse, String)> {
         let mut redis_conn = state.redis.get_conn().await?;
+        let token_hash = security::hash_token(&refresh_token);
         
-        // Verify token in Redis
-        let stored_token = AuthRepository::get_refresh_token(&mut redis_conn, user_id).await?;
-        if let Some(token) = stored_token {
-            if token != refresh_token {
-                tracing::warn!("Refresh token reuse detected for user {}", user_id);
-                // Optional: Revoke usage if reuse detected (though logically we just reject here)
-                return Err(anyhow!("Invalid refresh token"));
+        // Resolve the session (token family) this refresh token belongs to
+        let session_id = match AuthRepository::find_session_id_by_refresh_token(&mut redis_conn, &token_hash).await? {
+            Some(id) => id,
+            None => {
+                // A token that was already rotated away is being replayed: either the legitimate
+                // client or an attacker holds a stolen copy. Revoke the whole family.
+                if let Some(family_id) = AuthRepository::find_session_id_by_rotated_token(&mut redis_conn, &token_hash).await? {
+                    if let Some(session) = AuthRepository::get_session(&mut redis_conn, family_id).await? {
+                        // Deleting the session also invalidates its access tokens (see auth_middleware)
+                        AuthRepository::delete_session(&mut redis_conn, &session).await?;
+                        tracing::warn!(
+                            "Refresh token reuse detected for user {}, revoked session {}",
+                            session.user_id,
+                            session.id
+                        );
+                    }
+                    return Err(AppError::unauthorized("Refresh token reuse detected, session revoked"));
+                }
+
+                return Err(AppError::unauthorized("Refresh token expired or invalid"));
             }
-        } else {
-            return Err(anyhow!("Refresh token expired or invalid"));
+        };
+
+        let mut session = AuthRepository::get_session(&mut redis_conn, session_id)
+            .await?
+            .ok_or_else(|| AppError::unauthorized("Refresh token expired or invalid"))?;
+
+        if session.refresh_token_hash != token_hash {
+            tracing::warn!("Refresh token mismatch for session {}", session.id);
+            return Err(AppError::unauthorized("Invalid refresh token"));
         }
         
         // Get user info
-             let user = sqlx::query_as!(
-            crate::modules::auth::model::User,
-            r#"
-            SELECT id, username, email, full_name, role as "role: UserRole", password_hash, created_at, updated_at
-            FROM users
-            WHERE id = $1
-            "#,
-            user_id
-        )
-        .fetch_optional(&state.db)
-        .await?
-        .ok_or(anyhow!("User not found"))?;
+        let user = AuthRepository::find_user_by_id(&state.db, session.user_id)
+            .await?
+            .ok_or_else(|| AppError::unauthorized("User not found"))?;
+
+        // Suspending revokes sessions, but a session may have been opened concurrently
+        if user.is_suspended() {
+            AuthRepository::delete_session(&mut redis_conn, &session).await?;
+            return Err(Self::suspended());
+        }
 
-        // Rotate Token
-        let new_refresh_token = format!("{}:{}", user.id, Uuid::new_v4());
-        tracing::info!("Rotated refresh token for user {}: {}", user.id, new_refresh_token);
+        // Rotate Token (same family, new token)
+        let new_refresh_token = security::generate_token();
+        session.refresh_token_hash = security::hash_token(&new_refresh_token);
+        session.last_used_at = OffsetDateTime::now_utc();
+        if client.ip_address.is_some() {
+            session.ip_address = client.ip_address;
+        }
+        if client.user_agent.is_some() {
+            session.user_agent = client.user_agent;
+        }
 
-        let refresh_ttl = 7 * 24 * 60 * 60;
-        AuthRepository::store_refresh_token(
+        AuthRepository::rotate_session_token(
             &mut redis_conn,
-            user.id,
-            &new_refresh_token,
-            refresh_ttl,
+            &session,
+            &token_hash,
+            REFRESH_TOKEN_TTL_SECS,
         )
         .await?;
+        tracing::info!("Rotated refresh token for session {} of user {}", session.id, user.id);
 
-        // Use secret from config
-        let access_token = Self::create_access_token(user.id, user.role.clone(), &state.config.jwt_secret)?;
+        // Signed with the active key (see common::jwt)
+        let access_token = Self::create_access_token(&state, &user, &session).await?;
         
-        let user_response = UserResponse {
-            id: user.id,
-            email: user.email,
-            username: user.username,
-            full_name: user.full_name,
-            role: user.role.to_string(),
-        };
+        let user_response = UserResponse::from(user);
 
         Ok((
             AuthResponse {
                 access_token,
-                access_token_expires_in: 15 * 60,
-                refresh_token_expires_in: refresh_ttl as u64,
+                access_token_expires_in: ACCESS_TOKEN_TTL_SECS,
+                refresh_token_expires_in: REFRESH_TOKEN_TTL_SECS,
                 user: user_response,
             },
             new_refresh_token, // Return new token
         ))
     }
 
-    fn create_access_token(user_id: Uuid, role: UserRole, secret: &str) -> Result<String> {
-        let expiration = get_current_timestamp() as usize + 15 * 60; // 15 minutes
+    // --- PASSWORD RESET ---
+
+    /// Emails a password reset link. Unknown emails are ignored silently so the
+    /// endpoint cannot be used to discover registered addresses.
+    pub async fn forgot_password(state: AppState, req: ForgotPasswordRequest) -> AppResult<()> {
+        let user = match AuthRepository::find_user_by_email(&state.db, &req.email).await? {
+            Some(u) => u,
+            None => return Ok(()),
+        };
+
+        AuthRepository::invalidate_user_tokens(&state.db, user.id, TokenPurpose::PasswordReset).await?;
+
+        let token = security::generate_token();
+        let expires_at = OffsetDateTime::now_utc() + Duration::minutes(PASSWORD_RESET_TTL_MINUTES);
+        AuthRepository::create_user_token(
+            &state.db,
+            user.id,
+            TokenPurpose::PasswordReset,
+            &security::hash_token(&token),
+            expires_at,
+        )
+        .await?;
+
+        let link = format!("{}/reset-password?token={}", state.config.app_url.trim_end_matches('/'), token);
+        let message = MailMessage {
+            to: user.email.clone(),
+            subject: "Reset your HiuraMovie password".to_string(),
+            body: format!(
+                "Hi {},\n\nSomeone asked to reset the password of your account. Open the link below to choose a new one:\n{}\n\nThe link expires in {} minutes. If you did not ask for this, you can ignore this email.",
+                user.full_name, link, PASSWORD_RESET_TTL_MINUTES
+            ),
+        };
+
+        Ok(state.mailer.send(&message).await?)
+    }
+
+    pub async fn reset_password(state: AppState, req: ResetPasswordRequest) -> AppResult<()> {
+        let token_hash = security::hash_token(&req.token);
+        let invalid_link = || AppError::validation("Reset link is invalid or has expired");
+
+        // Checked before the link is used up, so a rejected password can be retried
+        let owner_id = AuthRepository::find_user_token_owner(&state.db, &token_hash, TokenPurpose::PasswordReset)
+            .await?
+            .ok_or_else(invalid_link)?;
+        let owner = AuthRepository::find_user_by_id(&state.db, owner_id)
+            .await?
+            .ok_or_else(invalid_link)?;
+        state.passwords.check_policy(&req.new_password, &[&owner.username, &owner.email, &owner.full_name])?;
+
+        let user_id = AuthRepository::consume_user_token(&state.db, &token_hash, TokenPurpose::PasswordReset)
+            .await?
+            .ok_or_else(invalid_link)?;
+
+        let password_hash = state.passwords.hash(&req.new_password)?;
+        AuthRepository::update_password(&state.db, user_id, &password_hash).await?;
+
+        // The reset link proves ownership of the mailbox
+        AuthRepository::mark_email_verified(&state.db, user_id).await?;
+
+        // Proving mailbox ownership also lifts a lockout on the account
+        Self::unlock_login(state.clone(), &owner.email, None, "password_reset").await?;
+
+        // Whoever knew the old password must not stay logged in
+        let revoked = Self::revoke_all_sessions(state, user_id).await?;
+        tracing::info!("Password reset for user {}, revoked {} sessions", user_id, revoked);
+        Ok(())
+    }
+
+    // --- TWO-FACTOR ---
+
+    /// Starts TOTP enrollment. The secret only becomes active once `enable_totp`
+    /// confirms the user's authenticator produces valid codes.
+    pub async fn setup_totp(state: AppState, user_id: Uuid) -> AppResult<TotpSetupResponse> {
+        let user = AuthRepository::find_user_by_id(&state.db, user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("User not found"))?;
+
+        if user.totp_enabled_at.is_some() {
+            return Err(AppError::conflict("Two-factor authentication is already enabled"));
+        }
+
+        let secret = totp::generate_secret();
+        let otpauth_uri = totp::provisioning_uri(&secret, &state.config.totp_issuer, &user.email)?;
+
+        let mut redis_conn = state.redis.get_conn().await?;
+        AuthRepository::store_pending_totp_secret(&mut redis_conn, user.id, &secret, TOTP_ENROLLMENT_TTL_SECS)
+            .await?;
+
+        Ok(TotpSetupResponse { secret, otpauth_uri })
+    }
+
+    /// Confirms enrollment with a code from the authenticator and returns fresh recovery codes.
+    /// The current session counts as having passed 2FA from now on.
+    pub async fn enable_totp(
+        state: AppState,
+        user_id: Uuid,
+        session_id: Uuid,
+        req: MfaCodeRequest,
+    ) -> AppResult<RecoveryCodesResponse> {
+        let mut redis_conn = state.redis.get_conn().await?;
+        let secret = AuthRepository::get_pending_totp_secret(&mut redis_conn, user_id)
+            .await?
+            .ok_or_else(|| AppError::validation("No pending two-factor setup, start the setup again"))?;
+
+        let step = totp::verify(&secret, req.code.trim())?.ok_or_else(|| AppError::unauthorized("Invalid code"))?;
+        AuthRepository::mark_totp_step_used(&mut redis_conn, user_id, step, TOTP_REPLAY_TTL_SECS).await?;
+
+        AuthRepository::set_totp_secret(&state.db, user_id, Some(&secret)).await?;
+        AuthRepository::delete_pending_totp_secret(&mut redis_conn, user_id).await?;
+
+        if let Some(mut session) = AuthRepository::get_session(&mut redis_conn, session_id)
+            .await?
+            .filter(|s| s.user_id == user_id)
+        {
+            let ttl: i64 = redis_conn.ttl(format!("session:{}", session.id)).await?;
+            session.mfa_verified = true;
+            AuthRepository::store_session(&mut redis_conn, &session, ttl.max(1) as u64).await?;
+        }
+
+        tracing::info!("Two-factor authentication enabled for user {}", user_id);
+        Self::generate_recovery_codes(&state, user_id).await
+    }
+
+    /// Turns 2FA off after checking a current code. Not allowed where the role requires 2FA.
+    pub async fn disable_totp(state: AppState, user_id: Uuid, req: MfaCodeRequest) -> AppResult<()> {
+        let user = AuthRepository::find_user_by_id(&state.db, user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("User not found"))?;
+
+        if user.totp_enabled_at.is_none() {
+            return Err(AppError::validation("Two-factor authentication is not enabled"));
+        }
+
+        if state.config.require_admin_mfa && user.role == ROLE_ADMIN {
+            return Err(AppError::forbidden("Two-factor authentication is required for admin accounts"));
+        }
+
+        Self::verify_second_factor(&state, &user, &req.code).await?;
+
+        AuthRepository::set_totp_secret(&state.db, user.id, None).await?;
+        AuthRepository::delete_recovery_codes(&state.db, user.id).await?;
+
+        tracing::info!("Two-factor authentication disabled for user {}", user.id);
+        Ok(())
+    }
+
+    /// Replaces the recovery codes after checking a current code. Old codes stop working.
+    pub async fn regenerate_recovery_codes(
+        state: AppState,
+        user_id: Uuid,
+        req: MfaCodeRequest,
+    ) -> AppResult<RecoveryCodesResponse> {
+        let user = AuthRepository::find_user_by_id(&state.db, user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("User not found"))?;
+
+        if user.totp_enabled_at.is_none() {
+            return Err(AppError::validation("Two-factor authentication is not enabled"));
+        }
+
+        Self::verify_second_factor(&state, &user, &req.code).await?;
+        Self::generate_recovery_codes(&state, user.id).await
+    }
+
+    /// Accepts either a TOTP code (each time step only once) or an unused recovery code.
+    async fn verify_second_factor(state: &AppState, user: &User, code: &str) -> AppResult<()> {
+        let secret = user
+            .totp_secret
+            .as_deref()
+            .ok_or_else(|| AppError::validation("Two-factor authentication is not enabled"))?;
+
+        let code = code.trim();
+        if code.len() == 6 && code.chars().all(|c| c.is_ascii_digit()) {
+            let step = totp::verify(secret, code)?.ok_or_else(|| AppError::unauthorized("Invalid code"))?;
+
+            let mut redis_conn = state.redis.get_conn().await?;
+            if !AuthRepository::mark_totp_step_used(&mut redis_conn, user.id, step, TOTP_REPLAY_TTL_SECS).await? {
+                return Err(AppError::unauthorized("Code was already used, wait for the next one"));
+            }
+            return Ok(());
+        }
+
+        let normalized = Self::normalize_recovery_code(code);
+        if AuthRepository::consume_recovery_code(&state.db, user.id, &security::hash_token(&normalized)).await? {
+            tracing::info!("Recovery code used by user {}", user.id);
+            return Ok(());
+        }
+
+        Err(AppError::unauthorized("Invalid code"))
+    }
+
+    async fn generate_recovery_codes(state: &AppState, user_id: Uuid) -> AppResult<RecoveryCodesResponse> {
+        let mut recovery_codes = Vec::with_capacity(RECOVERY_CODE_COUNT);
+        let mut hashes = Vec::with_capacity(RECOVERY_CODE_COUNT);
+
+        for _ in 0..RECOVERY_CODE_COUNT {
+            // 10 hex characters shown as "xxxxx-xxxxx"
+            let raw = &security::generate_token()[..10];
+            recovery_codes.push(format!("{}-{}", &raw[..5], &raw[5..]));
+            hashes.push(security::hash_token(raw));
+        }
+
+        AuthRepository::replace_recovery_codes(&state.db, user_id, &hashes).await?;
+        Ok(RecoveryCodesResponse { recovery_codes })
+    }
+
+    fn normalize_recovery_code(code: &str) -> String {
+        code.chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_lowercase())
+            .collect()
+    }
+
+    // --- DEVICE AUTHORIZATION ---
+
+    /// Starts a device authorization grant for a TV or console. The device shows the
+    /// user code and polls `device_token` while the user approves it on another device.
+    pub async fn start_device_authorization(
+        state: AppState,
+        req: DeviceCodeRequest,
+        client: ClientContext,
+    ) -> AppResult<DeviceCodeResponse> {
+        let device_code = security::generate_token();
+        let device_code_hash = security::hash_token(&device_code);
+        let mut redis_conn = state.redis.get_conn().await?;
+
+        // 20^8 codes; a collision with a live code only costs another draw
+        let mut authorization = None;
+        for _ in 0..5 {
+            let candidate = DeviceAuthorization {
+                user_code: Self::generate_user_code(),
+                client_name: req.client_name.clone().filter(|n| !n.trim().is_empty()),
+                ip_address: client.ip_address.clone(),
+                user_agent: client.user_agent.clone(),
+                created_at: OffsetDateTime::now_utc(),
+                status: DeviceAuthorizationStatus::Pending,
+            };
+            if AuthRepository::create_device_authorization(&mut redis_conn, &device_code_hash, &candidate, DEVICE_CODE_TTL_SECS).await? {
+                authorization = Some(candidate);
+                break;
+            }
+        }
+        let authorization = authorization.ok_or_else(|| AppError::internal("Failed to allocate a user code"))?;
+
+        let verification_uri = format!("{}/device", state.config.app_url.trim_end_matches('/'));
+        Ok(DeviceCodeResponse {
+            device_code,
+            verification_uri_complete: format!("{}?user_code={}", verification_uri, authorization.user_code),
+            user_code: authorization.user_code,
+            verification_uri,
+            expires_in: DEVICE_CODE_TTL_SECS,
+            interval: DEVICE_POLL_INTERVAL_SECS,
+        })
+    }
+
+    /// Polled by the device. Once the user approved, opens a session for the device
+    /// (with its own IP and user agent) and returns the tokens, exactly once.
+    pub async fn device_token(state: AppState, device_code: &str) -> Result<(AuthResponse, String), DeviceGrantError> {
+        let device_code_hash = security::hash_token(device_code);
+        let mut redis_conn = state.redis.get_conn().await?;
+
+        let authorization = AuthRepository::get_device_authorization(&mut redis_conn, &device_code_hash)
+            .await?
+            .ok_or(DeviceGrantError::ExpiredToken)?;
+
+        if !AuthRepository::record_device_poll(&mut redis_conn, &device_code_hash, DEVICE_POLL_INTERVAL_SECS).await? {
+            return Err(DeviceGrantError::SlowDown { retry_after: DEVICE_POLL_INTERVAL_SECS });
+        }
+
+        if authorization.status == DeviceAuthorizationStatus::Pending {
+            return Err(DeviceGrantError::AuthorizationPending);
+        }
+
+        // Whoever takes the record first gets the tokens
+        let authorization = AuthRepository::take_device_authorization(&mut redis_conn, &device_code_hash)
+            .await?
+            .ok_or(DeviceGrantError::ExpiredToken)?;
+
+        let DeviceAuthorizationStatus::Approved { user_id, mfa_verified } = authorization.status else {
+            return Err(DeviceGrantError::AccessDenied);
+        };
+
+        let user = AuthRepository::find_user_by_id(&state.db, user_id)
+            .await?
+            .ok_or(DeviceGrantError::AccessDenied)?;
+        if user.is_suspended() {
+            return Err(Self::suspended().into());
+        }
+
+        let client = ClientContext {
+            ip_address: authorization.ip_address,
+            user_agent: authorization.user_agent,
+        };
+        tracing::info!("Device authorization approved by user {} completed", user.id);
+        Ok(Self::issue_session(&state, user, client, mfa_verified).await?)
+    }
+
+    /// Details of a pending request, so the user can check it is their device before approving.
+    pub async fn get_device_authorization(
+        state: AppState,
+        user_id: Uuid,
+        user_code: &str,
+    ) -> AppResult<DeviceAuthorizationResponse> {
+        let (_, authorization) = Self::find_pending_device(&state, user_id, user_code).await?;
+
+        Ok(DeviceAuthorizationResponse {
+            user_code: authorization.user_code,
+            client_name: authorization.client_name,
+            ip_address: authorization.ip_address,
+            user_agent: authorization.user_agent,
+            created_at: authorization.created_at,
+        })
+    }
+
+    /// Approves or denies a pending request with the caller's account. The device
+    /// inherits whether the approving session passed a second factor.
+    pub async fn decide_device_authorization(
+        state: AppState,
+        claims: &TokenClaims,
+        req: &DeviceApprovalRequest,
+    ) -> AppResult<()> {
+        let (device_code_hash, mut authorization) = Self::find_pending_device(&state, claims.sub, &req.user_code).await?;
+
+        authorization.status = if req.approve {
+            DeviceAuthorizationStatus::Approved { user_id: claims.sub, mfa_verified: claims.mfa }
+        } else {
+            DeviceAuthorizationStatus::Denied
+        };
+
+        let mut redis_conn = state.redis.get_conn().await?;
+        AuthRepository::update_device_authorization(&mut redis_conn, &device_code_hash, &authorization).await?;
+        Ok(())
+    }
+
+    /// Looks up a pending request by user code. Unknown codes count against the
+    /// caller, so the short codes cannot be guessed from a logged-in account.
+    async fn find_pending_device(
+        state: &AppState,
+        user_id: Uuid,
+        user_code: &str,
+    ) -> AppResult<(String, DeviceAuthorization)> {
+        let mut redis_conn = state.redis.get_conn().await?;
+        if AuthRepository::device_code_failures(&mut redis_conn, user_id).await? >= DEVICE_MAX_CODE_FAILURES {
+            return Err(AppError::rate_limited("Too many invalid codes, try again later", None));
+        }
+
+        let user_code = Self::normalize_user_code(user_code);
+        let found = match AuthRepository::find_device_code_hash(&mut redis_conn, &user_code).await? {
+            Some(hash) => AuthRepository::get_device_authorization(&mut redis_conn, &hash)
+                .await?
+                .filter(|a| a.status == DeviceAuthorizationStatus::Pending)
+                .map(|a| (hash, a)),
+            None => None,
+        };
+
+        match found {
+            Some(found) => Ok(found),
+            None => {
+                AuthRepository::record_device_code_failure(&mut redis_conn, user_id, DEVICE_CODE_TTL_SECS).await?;
+                Err(AppError::not_found("Code is invalid or expired"))
+            }
+        }
+    }
+
+    fn generate_user_code() -> String {
+        let mut rng = rand::rng();
+        let code: String = (0..8)
+            .map(|_| USER_CODE_ALPHABET[rng.random_range(0..USER_CODE_ALPHABET.len())] as char)
+            .collect();
+        format!("{}-{}", &code[..4], &code[4..])
+    }
+
+    /// Accepts codes typed in lower case, with spaces or without the dash.
+    fn normalize_user_code(code: &str) -> String {
+        let code: String = code
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        if code.len() == 8 {
+            format!("{}-{}", &code[..4], &code[4..])
+        } else {
+            code
+        }
+    }
+
+    // --- SESSIONS ---
+
+    pub async fn list_sessions(state: AppState, user_id: Uuid, current_session_id: Uuid) -> AppResult<Vec<SessionResponse>> {
+        let mut redis_conn = state.redis.get_conn().await?;
+        let sessions = AuthRepository::list_sessions(&mut redis_conn, user_id).await?;
+
+        Ok(sessions
+            .into_iter()
+            .map(|s| SessionResponse {
+                current: s.id == current_session_id,
+                impersonation: s.impersonator.is_some(),
+                id: s.id,
+                ip_address: s.ip_address,
+                user_agent: s.user_agent,
+                created_at: s.created_at,
+                last_used_at: s.last_used_at,
+            })
+            .collect())
+    }
+
+    pub async fn revoke_session(state: AppState, user_id: Uuid, session_id: Uuid) -> AppResult<()> {
+        let mut redis_conn = state.redis.get_conn().await?;
+        let session = AuthRepository::get_session(&mut redis_conn, session_id)
+            .await?
+            .filter(|s| s.user_id == user_id)
+            .ok_or_else(|| AppError::not_found("Session not found"))?;
+
+        AuthRepository::delete_session(&mut redis_conn, &session).await?;
+        tracing::info!("Revoked session {} of user {}", session_id, user_id);
+        Ok(())
+    }
+
+    /// Revokes every session of the user, logging them out on all devices. Returns how many were revoked.
+    pub async fn revoke_all_sessions(state: AppState, user_id: Uuid) -> AppResult<usize> {
+        let mut redis_conn = state.redis.get_conn().await?;
+        let sessions = AuthRepository::list_sessions(&mut redis_conn, user_id).await?;
+
+        for session in &sessions {
+            AuthRepository::delete_session(&mut redis_conn, session).await?;
+        }
+
+        Ok(sessions.len())
+    }
+
+    /// Revokes every session of the user except `keep_session_id`. Returns how many were revoked.
+    pub async fn revoke_other_sessions(state: AppState, user_id: Uuid, keep_session_id: Uuid) -> AppResult<usize> {
+        let mut redis_conn = state.redis.get_conn().await?;
+        let sessions = AuthRepository::list_sessions(&mut redis_conn, user_id).await?;
+
+        let mut revoked = 0;
+        for session in sessions.iter().filter(|s| s.id != keep_session_id) {
+            AuthRepository::delete_session(&mut redis_conn, session).await?;
+            revoked += 1;
+        }
+
+        tracing::info!("Revoked {} other sessions of user {}", revoked, user_id);
+        Ok(revoked)
+    }
+
+    /// Signs an access token for a session, carrying the permissions of the user's role at this moment.
+    pub async fn create_access_token(state: &AppState, user: &User, session: &Session) -> AppResult<String> {
+        let expiration = get_current_timestamp() as usize + ACCESS_TOKEN_TTL_SECS as usize; // 15 minutes
+        let (permissions, mfa_required) =
+            RoleService::resolve_permissions(state, &user.role, session.mfa_verified).await?;
         
         let claims = TokenClaims {
-            sub: user_id,
-            role: role.to_string(),
+            sub: user.id,
+            role: user.role.clone(),
+            sid: session.id,
+            mfa: session.mfa_verified,
+            permissions,
+            mfa_required,
+            profile_id: session.profile_id,
+            act: session.impersonator.clone(),
             exp: expiration,
             iat: get_current_timestamp() as usize,
+            api_key: None,
         };
         
-        encode(
-            &Header::default(),
-            &claims,
-            &EncodingKey::from_secret(secret.as_bytes()),
-        )
-        .map_err(|e| anyhow!(e.to_string()))
+        Ok(state.jwt.sign(&claims)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn login_delay_grows_after_the_first_failures_and_is_capped() {
+        let delays: Vec<Option<u64>> = (0..=6).map(AuthService::login_delay_secs).collect();
+        assert_eq!(delays, vec![None, None, None, Some(1), Some(2), Some(4), Some(8)]);
+        assert_eq!(AuthService::login_delay_secs(20), Some(LOGIN_MAX_DELAY_SECS));
+        assert_eq!(AuthService::login_delay_secs(u64::MAX), Some(LOGIN_MAX_DELAY_SECS));
+    }
+
+    #[test]
+    fn user_codes_are_two_groups_from_the_alphabet() {
+        for _ in 0..100 {
+            let code = AuthService::generate_user_code();
+            let (first, second) = code.split_once('-').expect("dash");
+            assert_eq!((first.len(), second.len()), (4, 4));
+            assert!(first.bytes().chain(second.bytes()).all(|b| USER_CODE_ALPHABET.contains(&b)));
+            assert_eq!(AuthService::normalize_user_code(&code), code);
+        }
+    }
+
+    #[test]
+    fn user_codes_are_accepted_as_typed() {
+        assert_eq!(AuthService::normalize_user_code("bcdf-ghjk"), "BCDF-GHJK");
+        assert_eq!(AuthService::normalize_user_code(" bcdf ghjk "), "BCDF-GHJK");
+        assert_eq!(AuthService::normalize_user_code("BCDFGHJK"), "BCDF-GHJK");
+        // Wrong lengths are left alone and simply not found
+        assert_eq!(AuthService::normalize_user_code("bcd"), "BCD");
+    }
+
+    #[test]
+    fn recovery_codes_match_regardless_of_formatting() {
+        assert_eq!(AuthService::normalize_recovery_code(" AB12-cd34 "), "ab12cd34");
+        assert_eq!(AuthService::normalize_recovery_code("ab12 cd34"), "ab12cd34");
     }
 }
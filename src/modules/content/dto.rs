@@ -1,8 +1,29 @@
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use time::OffsetDateTime;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 use super::model::{Movie, Series, Season, Episode};
 use crate::modules::genre::dto::GenreResponse;
+use crate::modules::rating::model::MaturityRating;
+
+// --- LISTING ---
+
+/// Filters and sort of the movie and series lists.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ContentListQuery {
+    /// Genre slug
+    pub genre: Option<String>,
+    pub year_from: Option<i32>,
+    pub year_to: Option<i32>,
+    /// `DRAFT`, `PROCESSING`, `READY` or `FAILED`; a series matches when one of its episodes has it
+    pub status: Option<String>,
+    /// Minimum average rating
+    pub min_rating: Option<f64>,
+    /// `title`, `year`, `views`, `rating` or `recent` (default)
+    pub sort: Option<String>,
+    /// `asc` or `desc`; defaults to `asc` for title and `desc` otherwise
+    pub order: Option<String>,
+}
 
 // --- MOVIE DTOs ---
 
@@ -13,6 +34,10 @@ pub struct CreateMovieRequest {
     pub release_year: Option<i32>,
     pub duration_seconds: Option<i32>,
     pub genre_ids: Vec<Uuid>,
+    /// Rating code in `MATURITY_RATING_SYSTEM`, e.g. `PG-13`
+    pub maturity_rating: Option<String>,
+    #[serde(default)]
+    pub content_advisories: Vec<String>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -21,12 +46,32 @@ pub struct UpdateMovieRequest {
     pub description: Option<String>,
     pub release_year: Option<i32>,
     pub genre_ids: Option<Vec<Uuid>>,
+    /// Rating code in `MATURITY_RATING_SYSTEM`; an empty string removes the rating
+    pub maturity_rating: Option<String>,
+    pub content_advisories: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct MovieResponse {
     pub movie: Movie,
     pub genres: Vec<GenreResponse>,
+    pub maturity_rating: Option<MaturityRating>,
+}
+
+/// Visibility of a movie or series and its schedule. The schedule is replaced:
+/// a timestamp left out clears the pending change.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateVisibilityRequest {
+    /// `published` or `unpublished`, applied right away
+    pub visibility: String,
+    /// Publish at this time, e.g. the release date of an unpublished title
+    #[serde(default, with = "time::serde::iso8601::option")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub publish_at: Option<OffsetDateTime>,
+    /// Unpublish at this time, e.g. when the license ends
+    #[serde(default, with = "time::serde::iso8601::option")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub unpublish_at: Option<OffsetDateTime>,
 }
 
 // --- SERIES DTOs ---
@@ -37,6 +82,10 @@ pub struct CreateSeriesRequest {
     pub description: Option<String>,
     pub release_year: Option<i32>,
     pub genre_ids: Vec<Uuid>,
+    /// Rating code in `MATURITY_RATING_SYSTEM`, e.g. `PG-13`
+    pub maturity_rating: Option<String>,
+    #[serde(default)]
+    pub content_advisories: Vec<String>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -45,12 +94,16 @@ pub struct UpdateSeriesRequest {
     pub description: Option<String>,
     pub release_year: Option<i32>,
     pub genre_ids: Option<Vec<Uuid>>,
+    /// Rating code in `MATURITY_RATING_SYSTEM`; an empty string removes the rating
+    pub maturity_rating: Option<String>,
+    pub content_advisories: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct SeriesResponse {
     pub series: Series,
     pub genres: Vec<GenreResponse>,
+    pub maturity_rating: Option<MaturityRating>,
     pub seasons: Vec<SeasonResponse>, // Nested full structure
 }
 
@@ -58,6 +111,7 @@ pub struct SeriesResponse {
 pub struct SeriesListResponse {
     pub series: Series,
     pub genres: Vec<GenreResponse>,
+    pub maturity_rating: Option<MaturityRating>,
 }
 
 // --- SEASON DTOs ---
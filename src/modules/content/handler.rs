@@ -1,10 +1,18 @@
-use crate::common::response::{ApiError, ApiResponse, ApiSuccess};
+use crate::common::error::AppError;
+use crate::common::pagination::{Page, PageParams, Pagination};
+use crate::common::response::{ApiResponse, ApiSuccess};
 use crate::common::upload::stream_to_s3;
 use crate::state::AppState;
 use crate::modules::content::dto::*;
+use crate::modules::content::model::{Movie, Series, VISIBILITY_PUBLISHED};
 use crate::modules::content::service::ContentService;
+use crate::modules::profile::model::ViewerContext;
+use crate::modules::content::repository::ContentRepository;
+use crate::modules::rating::service::RatingService;
+use crate::modules::audit::model::{AuditContext, AuditEntry};
+use crate::modules::audit::service::AuditService;
 use axum::{
-    extract::{Path, State, Multipart},
+    extract::{Path, Query, State, Multipart},
     http::header,
     http::StatusCode,
     response::IntoResponse,
@@ -47,27 +55,44 @@ fn sanitize_filename(name: &str) -> String {
 )]
 pub async fn create_movie(
     State(state): State<AppState>,
+    audit: AuditContext,
     Json(req): Json<CreateMovieRequest>,
 ) -> impl IntoResponse {
-    match ContentService::create_movie(state, req).await {
-        Ok(res) => ApiSuccess(ApiResponse::success(res, "Movie created successfully").into(), StatusCode::CREATED).into_response(),
-        Err(e) => ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response(),
+    match ContentService::create_movie(state.clone(), req).await {
+        Ok(res) => {
+            let entry = AuditEntry::new("movie.create", "movie", Some(res.movie.id)).after(&res.movie);
+            AuditService::record(&state, &audit, entry).await;
+            ApiSuccess(ApiResponse::success(res, "Movie created successfully").into(), StatusCode::CREATED).into_response()
+        }
+        Err(e) => e.into_response(),
     }
 }
 
 #[utoipa::path(
     get,
     path = "/api/v1/movies",
+    params(
+        ContentListQuery,
+        PageParams,
+        ("x-parental-pin" = Option<String>, Header, description = "Parental PIN lifting the profile's maturity limit")
+    ),
     responses(
-        (status = 200, description = "List Movies", body = ApiResponse<Vec<MovieResponse>>),
+        (status = 200, description = "Page of Movies allowed by the viewer's maturity limit", body = ApiResponse<Page<MovieResponse>>),
+        (status = 400, description = "Invalid filter, sort or cursor"),
+        (status = 403, description = "Invalid parental PIN"),
         (status = 500, description = "Internal Server Error")
     ),
     tag = "Content"
 )]
-pub async fn list_movies(State(state): State<AppState>) -> impl IntoResponse {
-    match ContentService::list_movies(state).await {
+pub async fn list_movies(
+    State(state): State<AppState>,
+    viewer: ViewerContext,
+    pagination: Pagination,
+    Query(query): Query<ContentListQuery>,
+) -> impl IntoResponse {
+    match ContentService::list_movies(state, viewer, query, pagination).await {
         Ok(res) => ApiSuccess(ApiResponse::success(res, "Movies retrieved successfully").into(), StatusCode::OK).into_response(),
-        Err(e) => ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -75,10 +100,12 @@ pub async fn list_movies(State(state): State<AppState>) -> impl IntoResponse {
     get,
     path = "/api/v1/movies/{id}",
     params(
-        ("id" = Uuid, Path, description = "Movie ID")
+        ("id" = Uuid, Path, description = "Movie ID"),
+        ("x-parental-pin" = Option<String>, Header, description = "Parental PIN lifting the profile's maturity limit")
     ),
     responses(
         (status = 200, description = "Get Movie", body = ApiResponse<MovieResponse>),
+        (status = 403, description = "Restricted by parental controls or invalid parental PIN"),
         (status = 404, description = "Movie Not Found"),
         (status = 500, description = "Internal Server Error")
     ),
@@ -86,11 +113,12 @@ pub async fn list_movies(State(state): State<AppState>) -> impl IntoResponse {
 )]
 pub async fn get_movie(
     State(state): State<AppState>,
+    viewer: ViewerContext,
     Path(id): Path<Uuid>,
 ) -> impl IntoResponse {
-    match ContentService::get_movie(state, id).await {
+    match ContentService::get_movie(state, id, viewer).await {
         Ok(res) => ApiSuccess(ApiResponse::success(res, "Movie retrieved successfully").into(), StatusCode::OK).into_response(),
-        Err(e) => ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -101,14 +129,22 @@ pub async fn get_movie(
         ("id" = Uuid, Path, description = "Movie ID")
     ),
     responses(
-        (status = 200, description = "Transcode progress", body = ApiResponse<u8>)
+        (status = 200, description = "Transcode progress", body = ApiResponse<u8>),
+        (status = 404, description = "Not Found")
     ),
     tag = "Content"
 )]
 pub async fn get_movie_transcode_progress(
     State(state): State<AppState>,
+    viewer: ViewerContext,
     Path(id): Path<Uuid>,
 ) -> impl IntoResponse {
+    match ContentRepository::get_movie_by_id(&state.db, id).await {
+        Ok(Some(m)) if m.is_published() || viewer.sees_unpublished => {}
+        Ok(_) => return AppError::not_found("Movie not found").into_response(),
+        Err(e) => return AppError::from(e).into_response(),
+    }
+
     let key = format!("transcode_progress:movie:{}", id);
     let progress = match state.redis.get_conn().await {
         Ok(mut conn) => conn.get::<_, Option<u8>>(key).await.unwrap_or(Some(0)),
@@ -133,14 +169,22 @@ pub async fn get_movie_transcode_progress(
         ("id" = Uuid, Path, description = "Episode ID")
     ),
     responses(
-        (status = 200, description = "Transcode progress", body = ApiResponse<u8>)
+        (status = 200, description = "Transcode progress", body = ApiResponse<u8>),
+        (status = 404, description = "Not Found")
     ),
     tag = "Content"
 )]
 pub async fn get_episode_transcode_progress(
     State(state): State<AppState>,
+    viewer: ViewerContext,
     Path(id): Path<Uuid>,
 ) -> impl IntoResponse {
+    match ContentRepository::get_episode_visibility(&state.db, id).await {
+        Ok(Some(v)) if v == VISIBILITY_PUBLISHED || viewer.sees_unpublished => {}
+        Ok(_) => return AppError::not_found("Episode not found").into_response(),
+        Err(e) => return AppError::from(e).into_response(),
+    }
+
     let key = format!("transcode_progress:episode:{}", id);
     let progress = match state.redis.get_conn().await {
         Ok(mut conn) => conn.get::<_, Option<u8>>(key).await.unwrap_or(Some(0)),
@@ -171,19 +215,32 @@ pub async fn get_episode_transcode_progress(
 )]
 pub async fn get_episode_subtitle(
     State(state): State<AppState>,
+    viewer: ViewerContext,
     Path(id): Path<Uuid>,
 ) -> impl IntoResponse {
     use crate::modules::content::repository::ContentRepository;
 
-    let episode_opt = ContentRepository::get_episode_by_id(&state.db, id).await.unwrap_or(None);
-    let episode = match episode_opt {
-        Some(e) => e,
-        None => return ApiError("Episode not found".to_string(), StatusCode::NOT_FOUND).into_response(),
+    match ContentRepository::get_episode_visibility(&state.db, id).await {
+        Ok(Some(v)) if v == VISIBILITY_PUBLISHED || viewer.sees_unpublished => {}
+        Ok(_) => return AppError::not_found("Episode not found").into_response(),
+        Err(e) => return AppError::from(e).into_response(),
+    }
+
+    match ContentRepository::get_episode_maturity_level(&state.db, id).await {
+        Ok(level) if viewer.allows(level) => {}
+        Ok(_) => return AppError::not_found("Episode not found").into_response(),
+        Err(e) => return AppError::from(e).into_response(),
+    }
+
+    let episode = match ContentRepository::get_episode_by_id(&state.db, id).await {
+        Ok(Some(e)) => e,
+        Ok(None) => return AppError::not_found("Episode not found").into_response(),
+        Err(e) => return AppError::from(e).into_response(),
     };
 
     let key = match episode.subtitle_url {
         Some(k) => k,
-        None => return ApiError("Episode has no subtitle".to_string(), StatusCode::NOT_FOUND).into_response(),
+        None => return AppError::not_found("Episode has no subtitle").into_response(),
     };
 
     match state.storage.get_object(&key).await {
@@ -196,7 +253,7 @@ pub async fn get_episode_subtitle(
         }
         Err(e) => {
             tracing::error!("Failed to fetch subtitle {}: {}", key, e);
-            ApiError("Subtitle not found in storage".to_string(), StatusCode::NOT_FOUND).into_response()
+            AppError::not_found("Subtitle not found in storage").into_response()
         }
     }
 }
@@ -217,27 +274,44 @@ pub async fn get_episode_subtitle(
 )]
 pub async fn create_series(
     State(state): State<AppState>,
+    audit: AuditContext,
     Json(req): Json<CreateSeriesRequest>,
 ) -> impl IntoResponse {
-    match ContentService::create_series(state, req).await {
-        Ok(res) => ApiSuccess(ApiResponse::success(res, "Series created successfully").into(), StatusCode::CREATED).into_response(),
-        Err(e) => ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response(),
+    match ContentService::create_series(state.clone(), req).await {
+        Ok(res) => {
+            let entry = AuditEntry::new("series.create", "series", Some(res.series.id)).after(&res.series);
+            AuditService::record(&state, &audit, entry).await;
+            ApiSuccess(ApiResponse::success(res, "Series created successfully").into(), StatusCode::CREATED).into_response()
+        }
+        Err(e) => e.into_response(),
     }
 }
 
 #[utoipa::path(
     get,
     path = "/api/v1/series",
+    params(
+        ContentListQuery,
+        PageParams,
+        ("x-parental-pin" = Option<String>, Header, description = "Parental PIN lifting the profile's maturity limit")
+    ),
     responses(
-        (status = 200, description = "List Series", body = ApiResponse<Vec<SeriesListResponse>>),
+        (status = 200, description = "Page of Series allowed by the viewer's maturity limit", body = ApiResponse<Page<SeriesListResponse>>),
+        (status = 400, description = "Invalid filter, sort or cursor"),
+        (status = 403, description = "Invalid parental PIN"),
         (status = 500, description = "Internal Server Error")
     ),
     tag = "Content"
 )]
-pub async fn list_series(State(state): State<AppState>) -> impl IntoResponse {
-    match ContentService::list_series(state).await {
+pub async fn list_series(
+    State(state): State<AppState>,
+    viewer: ViewerContext,
+    pagination: Pagination,
+    Query(query): Query<ContentListQuery>,
+) -> impl IntoResponse {
+    match ContentService::list_series(state, viewer, query, pagination).await {
         Ok(res) => ApiSuccess(ApiResponse::success(res, "Series retrieved successfully").into(), StatusCode::OK).into_response(),
-        Err(e) => ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -245,10 +319,12 @@ pub async fn list_series(State(state): State<AppState>) -> impl IntoResponse {
     get,
     path = "/api/v1/series/{id}",
     params(
-        ("id" = Uuid, Path, description = "Series ID")
+        ("id" = Uuid, Path, description = "Series ID"),
+        ("x-parental-pin" = Option<String>, Header, description = "Parental PIN lifting the profile's maturity limit")
     ),
     responses(
         (status = 200, description = "Get Series", body = ApiResponse<SeriesResponse>),
+        (status = 403, description = "Restricted by parental controls or invalid parental PIN"),
         (status = 404, description = "Series Not Found"),
         (status = 500, description = "Internal Server Error")
     ),
@@ -256,11 +332,12 @@ pub async fn list_series(State(state): State<AppState>) -> impl IntoResponse {
 )]
 pub async fn get_series(
     State(state): State<AppState>,
+    viewer: ViewerContext,
     Path(id): Path<Uuid>,
 ) -> impl IntoResponse {
-    match ContentService::get_series(state, id).await {
+    match ContentService::get_series(state, id, viewer).await {
         Ok(res) => ApiSuccess(ApiResponse::success(res, "Series retrieved successfully").into(), StatusCode::OK).into_response(),
-        Err(e) => ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -280,11 +357,16 @@ pub async fn get_series(
 )]
 pub async fn create_season(
     State(state): State<AppState>,
+    audit: AuditContext,
     Json(req): Json<CreateSeasonRequest>,
 ) -> impl IntoResponse {
-    match ContentService::create_season(state, req).await {
-        Ok(res) => ApiSuccess(ApiResponse::success(res, "Season created successfully").into(), StatusCode::CREATED).into_response(),
-        Err(e) => ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response(),
+    match ContentService::create_season(state.clone(), req).await {
+        Ok(res) => {
+            let entry = AuditEntry::new("season.create", "season", Some(res.season.id)).after(&res.season);
+            AuditService::record(&state, &audit, entry).await;
+            ApiSuccess(ApiResponse::success(res, "Season created successfully").into(), StatusCode::CREATED).into_response()
+        }
+        Err(e) => e.into_response(),
     }
 }
 
@@ -302,11 +384,16 @@ pub async fn create_season(
 )]
 pub async fn create_episode(
     State(state): State<AppState>,
+    audit: AuditContext,
     Json(req): Json<CreateEpisodeRequest>,
 ) -> impl IntoResponse {
-    match ContentService::create_episode(state, req).await {
-        Ok(res) => ApiSuccess(ApiResponse::success(res, "Episode created successfully").into(), StatusCode::CREATED).into_response(),
-        Err(e) => ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response(),
+    match ContentService::create_episode(state.clone(), req).await {
+        Ok(res) => {
+            let entry = AuditEntry::new("episode.create", "episode", Some(res.id)).after(&res);
+            AuditService::record(&state, &audit, entry).await;
+            ApiSuccess(ApiResponse::success(res, "Episode created successfully").into(), StatusCode::CREATED).into_response()
+        }
+        Err(e) => e.into_response(),
     }
 }
 
@@ -331,19 +418,16 @@ pub async fn create_episode(
 )]
 pub async fn upload_movie_video(
     State(state): State<AppState>,
+    audit: AuditContext,
     Path(id): Path<Uuid>,
     mut multipart: Multipart,
 ) -> impl IntoResponse {
     // 1. Check if movie exists (Using Repository)
-    use crate::modules::content::repository::ContentRepository;
-    
-    let exists = ContentRepository::get_movie_by_id(&state.db, id).await;
-
-    match exists {
-        Ok(Some(_)) => {},
-        Ok(None) => return ApiError("Movie not found".to_string(), StatusCode::NOT_FOUND).into_response(),
-        Err(e) => return ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response(),
-    }
+    let before = match ContentRepository::get_movie_by_id(&state.db, id).await {
+        Ok(Some(movie)) => movie,
+        Ok(None) => return AppError::not_found("Movie not found").into_response(),
+        Err(e) => return AppError::from(e).into_response(),
+    };
 
     // 2. Process Multipart Stream
     while let Some(field) = multipart.next_field().await.unwrap_or(None) {
@@ -362,22 +446,26 @@ pub async fn upload_movie_video(
                     // 3. Update DB (Using Service)
                     // We store the RELATIVE KEY in the DB for portability
                     if let Err(e) = ContentService::initiate_movie_processing(state.clone(), id, key).await {
-                         return ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response();
+                         return e.into_response();
                     }
 
+                    let after = ContentRepository::get_movie_by_id(&state.db, id).await.ok().flatten();
+                    let entry = AuditEntry::new("movie.upload_video", "movie", Some(id)).before(&before).after(&after);
+                    AuditService::record(&state, &audit, entry).await;
+
                     return ApiSuccess(
                         ApiResponse::success(_url, "Video uploaded successfully"),
                         StatusCode::OK
                     ).into_response();
                 },
                 Err(e) => {
-                    return ApiError(format!("Upload failed: {}", e), StatusCode::INTERNAL_SERVER_ERROR).into_response();
+                    return AppError::internal(format!("Upload failed: {}", e)).into_response();
                 }
             }
         }
     }
 
-    ApiError("No video field found in multipart request".to_string(), StatusCode::BAD_REQUEST).into_response()
+    AppError::validation("No video field found in multipart request").into_response()
 }
 
 /// Upload Movie Thumbnail
@@ -400,18 +488,16 @@ pub async fn upload_movie_video(
 )]
 pub async fn upload_movie_thumbnail(
     State(state): State<AppState>,
+    audit: AuditContext,
     Path(id): Path<Uuid>,
     mut multipart: Multipart,
 ) -> impl IntoResponse {
     // 1. Check if movie exists
-    use crate::modules::content::repository::ContentRepository;
-    
-    let exists = ContentRepository::get_movie_by_id(&state.db, id).await;
-    match exists {
-        Ok(Some(_)) => {},
-        Ok(None) => return ApiError("Movie not found".to_string(), StatusCode::NOT_FOUND).into_response(),
-        Err(e) => return ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response(),
-    }
+    let before = match ContentRepository::get_movie_by_id(&state.db, id).await {
+        Ok(Some(movie)) => movie,
+        Ok(None) => return AppError::not_found("Movie not found").into_response(),
+        Err(e) => return AppError::from(e).into_response(),
+    };
 
     // 2. Process Multipart
     while let Some(field) = multipart.next_field().await.unwrap_or(None) {
@@ -448,22 +534,26 @@ pub async fn upload_movie_thumbnail(
                     // Or usually we allow frontend to guess or backend to serve it via proxy.
                     // For now, save relative key.
                     if let Err(e) = ContentService::complete_movie_thumbnail_upload(state.clone(), id, key).await {
-                         return ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response();
+                         return e.into_response();
                     }
 
+                    let after = ContentRepository::get_movie_by_id(&state.db, id).await.ok().flatten();
+                    let entry = AuditEntry::new("movie.upload_thumbnail", "movie", Some(id)).before(&before).after(&after);
+                    AuditService::record(&state, &audit, entry).await;
+
                     return ApiSuccess(
                         ApiResponse::success(_url, "Thumbnail uploaded successfully"),
                         StatusCode::OK
                     ).into_response();
                 },
                 Err(e) => {
-                    return ApiError(format!("Upload failed: {}", e), StatusCode::INTERNAL_SERVER_ERROR).into_response();
+                    return AppError::internal(format!("Upload failed: {}", e)).into_response();
                 }
             }
         }
     }
 
-    ApiError("No thumbnail field found in multipart request".to_string(), StatusCode::BAD_REQUEST).into_response()
+    AppError::validation("No thumbnail field found in multipart request").into_response()
 }
 
 /// Get Movie Thumbnail
@@ -480,20 +570,27 @@ pub async fn upload_movie_thumbnail(
 )]
 pub async fn get_movie_thumbnail(
     State(state): State<AppState>,
+    viewer: ViewerContext,
     Path(id): Path<Uuid>,
 ) -> impl IntoResponse {
     // 1. Get Movie and Thumbnail Key
     use crate::modules::content::repository::ContentRepository;
     
-    let movie_opt = ContentRepository::get_movie_by_id(&state.db, id).await.unwrap_or(None);
-    let movie = match movie_opt {
-        Some(m) => m,
-        None => return ApiError("Movie not found".to_string(), StatusCode::NOT_FOUND).into_response(),
+    let movie = match ContentRepository::get_movie_by_id(&state.db, id).await {
+        Ok(Some(m)) if m.is_published() || viewer.sees_unpublished => m,
+        Ok(_) => return AppError::not_found("Movie not found").into_response(),
+        Err(e) => return AppError::from(e).into_response(),
     };
 
+    match ContentRepository::get_movie_maturity_level(&state.db, id).await {
+        Ok(level) if viewer.allows(level) => {}
+        Ok(_) => return AppError::not_found("Movie not found").into_response(),
+        Err(e) => return AppError::from(e).into_response(),
+    }
+
     let key = match movie.thumbnail_url {
         Some(k) => k,
-        None => return ApiError("Movie has no thumbnail".to_string(), StatusCode::NOT_FOUND).into_response(),
+        None => return AppError::not_found("Movie has no thumbnail").into_response(),
     };
 
     // 2. Fetch from MinIO (Thumbs bucket)
@@ -515,7 +612,7 @@ pub async fn get_movie_thumbnail(
         },
         Err(e) => {
             tracing::error!("Failed to fetch thumbnail {}: {}", key, e);
-            ApiError("Thumbnail not found in storage".to_string(), StatusCode::NOT_FOUND).into_response()
+            AppError::not_found("Thumbnail not found in storage").into_response()
         }
     }
 }
@@ -540,17 +637,17 @@ pub async fn get_movie_thumbnail(
 )]
 pub async fn upload_series_thumbnail(
     State(state): State<AppState>,
+    audit: AuditContext,
     Path(id): Path<Uuid>,
     mut multipart: Multipart,
 ) -> impl IntoResponse {
     use crate::modules::content::repository::ContentRepository;
 
-    let exists = ContentRepository::get_series_by_id(&state.db, id).await;
-    match exists {
-        Ok(Some(_)) => {}
-        Ok(None) => return ApiError("Series not found".to_string(), StatusCode::NOT_FOUND).into_response(),
-        Err(e) => return ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response(),
-    }
+    let before = match ContentRepository::get_series_by_id(&state.db, id).await {
+        Ok(Some(series)) => series,
+        Ok(None) => return AppError::not_found("Series not found").into_response(),
+        Err(e) => return AppError::from(e).into_response(),
+    };
 
     while let Some(field) = multipart.next_field().await.unwrap_or(None) {
         let name = field.name().unwrap_or("").to_string();
@@ -571,9 +668,13 @@ pub async fn upload_series_thumbnail(
             match stream_to_s3(&storage_for_thumb, field, key.clone()).await {
                 Ok(_url) => {
                     if let Err(e) = ContentService::complete_series_thumbnail_upload(state.clone(), id, key).await {
-                        return ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response();
+                        return e.into_response();
                     }
 
+                    let after = ContentRepository::get_series_by_id(&state.db, id).await.ok().flatten();
+                    let entry = AuditEntry::new("series.upload_thumbnail", "series", Some(id)).before(&before).after(&after);
+                    AuditService::record(&state, &audit, entry).await;
+
                     return ApiSuccess(
                         ApiResponse::success(_url, "Thumbnail uploaded successfully"),
                         StatusCode::OK,
@@ -581,13 +682,13 @@ pub async fn upload_series_thumbnail(
                     .into_response();
                 }
                 Err(e) => {
-                    return ApiError(format!("Upload failed: {}", e), StatusCode::INTERNAL_SERVER_ERROR).into_response();
+                    return AppError::internal(format!("Upload failed: {}", e)).into_response();
                 }
             }
         }
     }
 
-    ApiError("No thumbnail field found in multipart request".to_string(), StatusCode::BAD_REQUEST).into_response()
+    AppError::validation("No thumbnail field found in multipart request").into_response()
 }
 
 /// Get Series Thumbnail
@@ -604,19 +705,26 @@ pub async fn upload_series_thumbnail(
 )]
 pub async fn get_series_thumbnail(
     State(state): State<AppState>,
+    viewer: ViewerContext,
     Path(id): Path<Uuid>,
 ) -> impl IntoResponse {
     use crate::modules::content::repository::ContentRepository;
 
-    let series_opt = ContentRepository::get_series_by_id(&state.db, id).await.unwrap_or(None);
-    let series = match series_opt {
-        Some(s) => s,
-        None => return ApiError("Series not found".to_string(), StatusCode::NOT_FOUND).into_response(),
+    let series = match ContentRepository::get_series_by_id(&state.db, id).await {
+        Ok(Some(s)) if s.is_published() || viewer.sees_unpublished => s,
+        Ok(_) => return AppError::not_found("Series not found").into_response(),
+        Err(e) => return AppError::from(e).into_response(),
     };
 
+    match RatingService::get(&state, series.maturity_rating_id).await {
+        Ok(rating) if viewer.allows(rating.as_ref().map(|r| r.level)) => {}
+        Ok(_) => return AppError::not_found("Series not found").into_response(),
+        Err(e) => return e.into_response(),
+    }
+
     let key = match series.thumbnail_url {
         Some(k) => k,
-        None => return ApiError("Series has no thumbnail".to_string(), StatusCode::NOT_FOUND).into_response(),
+        None => return AppError::not_found("Series has no thumbnail").into_response(),
     };
 
     let mut storage_for_thumb = state.storage.clone();
@@ -629,7 +737,7 @@ pub async fn get_series_thumbnail(
         }
         Err(e) => {
             tracing::error!("Failed to fetch thumbnail {}: {}", key, e);
-            ApiError("Thumbnail not found in storage".to_string(), StatusCode::NOT_FOUND).into_response()
+            AppError::not_found("Thumbnail not found in storage").into_response()
         }
     }
 }
@@ -647,19 +755,26 @@ pub async fn get_series_thumbnail(
 )]
 pub async fn get_movie_subtitle(
     State(state): State<AppState>,
+    viewer: ViewerContext,
     Path(id): Path<Uuid>,
 ) -> impl IntoResponse {
     use crate::modules::content::repository::ContentRepository;
 
-    let movie_opt = ContentRepository::get_movie_by_id(&state.db, id).await.unwrap_or(None);
-    let movie = match movie_opt {
-        Some(m) => m,
-        None => return ApiError("Movie not found".to_string(), StatusCode::NOT_FOUND).into_response(),
+    let movie = match ContentRepository::get_movie_by_id(&state.db, id).await {
+        Ok(Some(m)) if m.is_published() || viewer.sees_unpublished => m,
+        Ok(_) => return AppError::not_found("Movie not found").into_response(),
+        Err(e) => return AppError::from(e).into_response(),
     };
 
+    match ContentRepository::get_movie_maturity_level(&state.db, id).await {
+        Ok(level) if viewer.allows(level) => {}
+        Ok(_) => return AppError::not_found("Movie not found").into_response(),
+        Err(e) => return AppError::from(e).into_response(),
+    }
+
     let key = match movie.subtitle_url {
         Some(k) => k,
-        None => return ApiError("Movie has no subtitle".to_string(), StatusCode::NOT_FOUND).into_response(),
+        None => return AppError::not_found("Movie has no subtitle").into_response(),
     };
 
     match state.storage.get_object(&key).await {
@@ -672,7 +787,7 @@ pub async fn get_movie_subtitle(
         }
         Err(e) => {
             tracing::error!("Failed to fetch subtitle {}: {}", key, e);
-            ApiError("Subtitle not found in storage".to_string(), StatusCode::NOT_FOUND).into_response()
+            AppError::not_found("Subtitle not found in storage").into_response()
         }
     }
 }
@@ -693,12 +808,51 @@ pub async fn get_movie_subtitle(
 )]
 pub async fn update_movie(
     State(state): State<AppState>,
+    audit: AuditContext,
     Path(id): Path<Uuid>,
     Json(req): Json<UpdateMovieRequest>,
 ) -> impl IntoResponse {
-    match ContentService::update_movie(state, id, req).await {
-        Ok(res) => ApiSuccess(ApiResponse::success(res, "Movie updated").into(), StatusCode::OK).into_response(),
-        Err(e) => ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response(),
+    let before = ContentRepository::get_movie_by_id(&state.db, id).await.ok().flatten();
+
+    match ContentService::update_movie(state.clone(), id, req).await {
+        Ok(res) => {
+            let entry = AuditEntry::new("movie.update", "movie", Some(id)).before(&before).after(&res.movie);
+            AuditService::record(&state, &audit, entry).await;
+            ApiSuccess(ApiResponse::success(res, "Movie updated").into(), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Publish or unpublish a movie, now or on a schedule
+#[utoipa::path(
+    put,
+    path = "/api/v1/movies/{id}/visibility",
+    params(("id" = Uuid, Path, description = "Movie ID")),
+    request_body = UpdateVisibilityRequest,
+    responses(
+        (status = 200, description = "Visibility updated", body = ApiResponse<Movie>),
+        (status = 400, description = "Invalid visibility or schedule"),
+        (status = 404, description = "Not Found")
+    ),
+    tag = "Content",
+    security(("bearer_auth" = []))
+)]
+pub async fn update_movie_visibility(
+    State(state): State<AppState>,
+    audit: AuditContext,
+    Path(id): Path<Uuid>,
+    Json(req): Json<UpdateVisibilityRequest>,
+) -> impl IntoResponse {
+    let before = ContentRepository::get_movie_by_id(&state.db, id).await.ok().flatten();
+
+    match ContentService::update_movie_visibility(state.clone(), id, req).await {
+        Ok(movie) => {
+            let entry = AuditEntry::new("movie.visibility", "movie", Some(id)).before(&before).after(&movie);
+            AuditService::record(&state, &audit, entry).await;
+            ApiSuccess(ApiResponse::success(movie, "Movie visibility updated"), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
     }
 }
 
@@ -715,11 +869,17 @@ pub async fn update_movie(
 )]
 pub async fn delete_movie(
     State(state): State<AppState>,
+    audit: AuditContext,
     Path(id): Path<Uuid>,
 ) -> impl IntoResponse {
-    match ContentService::delete_movie(state, id).await {
-        Ok(_) => ApiSuccess(ApiResponse::success((), "Movie deleted").into(), StatusCode::OK).into_response(),
-        Err(e) => ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response(),
+    let before = ContentRepository::get_movie_by_id(&state.db, id).await.ok().flatten();
+
+    match ContentService::delete_movie(state.clone(), id).await {
+        Ok(_) => {
+            AuditService::record(&state, &audit, AuditEntry::new("movie.delete", "movie", Some(id)).before(&before)).await;
+            ApiSuccess(ApiResponse::success((), "Movie deleted").into(), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
     }
 }
 
@@ -736,12 +896,51 @@ pub async fn delete_movie(
 )]
 pub async fn update_series(
     State(state): State<AppState>,
+    audit: AuditContext,
     Path(id): Path<Uuid>,
     Json(req): Json<UpdateSeriesRequest>,
 ) -> impl IntoResponse {
-    match ContentService::update_series(state, id, req).await {
-        Ok(res) => ApiSuccess(ApiResponse::success(res, "Series updated").into(), StatusCode::OK).into_response(),
-        Err(e) => ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response(),
+    let before = ContentRepository::get_series_by_id(&state.db, id).await.ok().flatten();
+
+    match ContentService::update_series(state.clone(), id, req).await {
+        Ok(res) => {
+            let entry = AuditEntry::new("series.update", "series", Some(id)).before(&before).after(&res.series);
+            AuditService::record(&state, &audit, entry).await;
+            ApiSuccess(ApiResponse::success(res, "Series updated").into(), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Publish or unpublish a series with all its episodes, now or on a schedule
+#[utoipa::path(
+    put,
+    path = "/api/v1/series/{id}/visibility",
+    params(("id" = Uuid, Path, description = "Series ID")),
+    request_body = UpdateVisibilityRequest,
+    responses(
+        (status = 200, description = "Visibility updated", body = ApiResponse<Series>),
+        (status = 400, description = "Invalid visibility or schedule"),
+        (status = 404, description = "Not Found")
+    ),
+    tag = "Content",
+    security(("bearer_auth" = []))
+)]
+pub async fn update_series_visibility(
+    State(state): State<AppState>,
+    audit: AuditContext,
+    Path(id): Path<Uuid>,
+    Json(req): Json<UpdateVisibilityRequest>,
+) -> impl IntoResponse {
+    let before = ContentRepository::get_series_by_id(&state.db, id).await.ok().flatten();
+
+    match ContentService::update_series_visibility(state.clone(), id, req).await {
+        Ok(series) => {
+            let entry = AuditEntry::new("series.visibility", "series", Some(id)).before(&before).after(&series);
+            AuditService::record(&state, &audit, entry).await;
+            ApiSuccess(ApiResponse::success(series, "Series visibility updated"), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
     }
 }
 
@@ -757,11 +956,17 @@ pub async fn update_series(
 )]
 pub async fn delete_series(
     State(state): State<AppState>,
+    audit: AuditContext,
     Path(id): Path<Uuid>,
 ) -> impl IntoResponse {
-    match ContentService::delete_series(state, id).await {
-        Ok(_) => ApiSuccess(ApiResponse::success((), "Series deleted").into(), StatusCode::OK).into_response(),
-        Err(e) => ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response(),
+    let before = ContentRepository::get_series_by_id(&state.db, id).await.ok().flatten();
+
+    match ContentService::delete_series(state.clone(), id).await {
+        Ok(_) => {
+            AuditService::record(&state, &audit, AuditEntry::new("series.delete", "series", Some(id)).before(&before)).await;
+            ApiSuccess(ApiResponse::success((), "Series deleted").into(), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
     }
 }
 
@@ -777,12 +982,19 @@ pub async fn delete_series(
 )]
 pub async fn update_season(
     State(state): State<AppState>,
+    audit: AuditContext,
     Path(id): Path<Uuid>,
     Json(req): Json<UpdateSeasonRequest>,
 ) -> impl IntoResponse {
-    match ContentService::update_season(state, id, req).await {
-        Ok(res) => ApiSuccess(ApiResponse::success(res, "Season updated").into(), StatusCode::OK).into_response(),
-        Err(e) => ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response(),
+    let before = ContentRepository::get_season_by_id(&state.db, id).await.ok().flatten();
+
+    match ContentService::update_season(state.clone(), id, req).await {
+        Ok(res) => {
+            let entry = AuditEntry::new("season.update", "season", Some(id)).before(&before).after(&res.season);
+            AuditService::record(&state, &audit, entry).await;
+            ApiSuccess(ApiResponse::success(res, "Season updated").into(), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
     }
 }
 
@@ -796,11 +1008,17 @@ pub async fn update_season(
 )]
 pub async fn delete_season(
     State(state): State<AppState>,
+    audit: AuditContext,
     Path(id): Path<Uuid>,
 ) -> impl IntoResponse {
-    match ContentService::delete_season(state, id).await {
-        Ok(_) => ApiSuccess(ApiResponse::success((), "Season deleted").into(), StatusCode::OK).into_response(),
-        Err(e) => ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response(),
+    let before = ContentRepository::get_season_by_id(&state.db, id).await.ok().flatten();
+
+    match ContentService::delete_season(state.clone(), id).await {
+        Ok(_) => {
+            AuditService::record(&state, &audit, AuditEntry::new("season.delete", "season", Some(id)).before(&before)).await;
+            ApiSuccess(ApiResponse::success((), "Season deleted").into(), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
     }
 }
 
@@ -815,12 +1033,19 @@ pub async fn delete_season(
 )]
 pub async fn update_episode(
     State(state): State<AppState>,
+    audit: AuditContext,
     Path(id): Path<Uuid>,
     Json(req): Json<UpdateEpisodeRequest>,
 ) -> impl IntoResponse {
-    match ContentService::update_episode(state, id, req).await {
-        Ok(res) => ApiSuccess(ApiResponse::success(res, "Episode updated").into(), StatusCode::OK).into_response(),
-        Err(e) => ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response(),
+    let before = ContentRepository::get_episode_by_id(&state.db, id).await.ok().flatten();
+
+    match ContentService::update_episode(state.clone(), id, req).await {
+        Ok(res) => {
+            let entry = AuditEntry::new("episode.update", "episode", Some(id)).before(&before).after(&res);
+            AuditService::record(&state, &audit, entry).await;
+            ApiSuccess(ApiResponse::success(res, "Episode updated").into(), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
     }
 }
 
@@ -834,11 +1059,17 @@ pub async fn update_episode(
 )]
 pub async fn delete_episode(
     State(state): State<AppState>,
+    audit: AuditContext,
     Path(id): Path<Uuid>,
 ) -> impl IntoResponse {
-    match ContentService::delete_episode(state, id).await {
-        Ok(_) => ApiSuccess(ApiResponse::success((), "Episode deleted").into(), StatusCode::OK).into_response(),
-        Err(e) => ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response(),
+    let before = ContentRepository::get_episode_by_id(&state.db, id).await.ok().flatten();
+
+    match ContentService::delete_episode(state.clone(), id).await {
+        Ok(_) => {
+            AuditService::record(&state, &audit, AuditEntry::new("episode.delete", "episode", Some(id)).before(&before)).await;
+            ApiSuccess(ApiResponse::success((), "Episode deleted").into(), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
     }
 }
 
@@ -861,17 +1092,17 @@ pub async fn delete_episode(
 )]
 pub async fn upload_episode_video(
     State(state): State<AppState>,
+    audit: AuditContext,
     Path(id): Path<Uuid>,
     mut multipart: Multipart,
 ) -> impl IntoResponse {
     use crate::modules::content::repository::ContentRepository;
     
-    let exists = ContentRepository::get_episode_by_id(&state.db, id).await;
-    match exists {
-        Ok(Some(_)) => {},
-        Ok(None) => return ApiError("Episode not found".to_string(), StatusCode::NOT_FOUND).into_response(),
-        Err(e) => return ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response(),
-    }
+    let before = match ContentRepository::get_episode_by_id(&state.db, id).await {
+        Ok(Some(episode)) => episode,
+        Ok(None) => return AppError::not_found("Episode not found").into_response(),
+        Err(e) => return AppError::from(e).into_response(),
+    };
 
     while let Some(field) = multipart.next_field().await.unwrap_or(None) {
         let name = field.name().unwrap_or("").to_string();
@@ -886,22 +1117,26 @@ pub async fn upload_episode_video(
             match stream_to_s3(&state.storage, field, key.clone()).await {
                 Ok(_url) => {
                     if let Err(e) = ContentService::initiate_episode_processing(state.clone(), id, key).await {
-                         return ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response();
+                         return e.into_response();
                     }
 
+                    let after = ContentRepository::get_episode_by_id(&state.db, id).await.ok().flatten();
+                    let entry = AuditEntry::new("episode.upload_video", "episode", Some(id)).before(&before).after(&after);
+                    AuditService::record(&state, &audit, entry).await;
+
                     return ApiSuccess(
                         ApiResponse::success(_url, "Episode video uploaded successfully"),
                         StatusCode::OK
                     ).into_response();
                 },
                 Err(e) => {
-                    return ApiError(format!("Upload failed: {}", e), StatusCode::INTERNAL_SERVER_ERROR).into_response();
+                    return AppError::internal(format!("Upload failed: {}", e)).into_response();
                 }
             }
         }
     }
 
-    ApiError("No video field found in multipart request".to_string(), StatusCode::BAD_REQUEST).into_response()
+    AppError::validation("No video field found in multipart request").into_response()
 }
 
 /// Upload Episode Thumbnail
@@ -923,17 +1158,17 @@ pub async fn upload_episode_video(
 )]
 pub async fn upload_episode_thumbnail(
     State(state): State<AppState>,
+    audit: AuditContext,
     Path(id): Path<Uuid>,
     mut multipart: Multipart,
 ) -> impl IntoResponse {
     use crate::modules::content::repository::ContentRepository;
     
-    let exists = ContentRepository::get_episode_by_id(&state.db, id).await;
-    match exists {
-        Ok(Some(_)) => {},
-        Ok(None) => return ApiError("Episode not found".to_string(), StatusCode::NOT_FOUND).into_response(),
-        Err(e) => return ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response(),
-    }
+    let before = match ContentRepository::get_episode_by_id(&state.db, id).await {
+        Ok(Some(episode)) => episode,
+        Ok(None) => return AppError::not_found("Episode not found").into_response(),
+        Err(e) => return AppError::from(e).into_response(),
+    };
 
     while let Some(field) = multipart.next_field().await.unwrap_or(None) {
         let name = field.name().unwrap_or("").to_string();
@@ -951,20 +1186,24 @@ pub async fn upload_episode_thumbnail(
             match stream_to_s3(&storage_for_thumb, field, key.clone()).await {
                 Ok(_url) => {
                     if let Err(e) = ContentService::complete_episode_thumbnail_upload(state.clone(), id, key).await {
-                         return ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response();
+                         return e.into_response();
                     }
 
+                    let after = ContentRepository::get_episode_by_id(&state.db, id).await.ok().flatten();
+                    let entry = AuditEntry::new("episode.upload_thumbnail", "episode", Some(id)).before(&before).after(&after);
+                    AuditService::record(&state, &audit, entry).await;
+
                     return ApiSuccess(
                         ApiResponse::success(_url, "Episode thumbnail uploaded successfully"),
                         StatusCode::OK
                     ).into_response();
                 },
                 Err(e) => {
-                    return ApiError(format!("Upload failed: {}", e), StatusCode::INTERNAL_SERVER_ERROR).into_response();
+                    return AppError::internal(format!("Upload failed: {}", e)).into_response();
                 }
             }
         }
     }
 
-    ApiError("No thumbnail field found in multipart request".to_string(), StatusCode::BAD_REQUEST).into_response()
+    AppError::validation("No thumbnail field found in multipart request").into_response()
 }
@@ -1,6 +1,8 @@
 use axum::Router;
 use axum::routing::post;
 use crate::state::AppState;
+use crate::modules::api_key::model::SCOPE_CONTENT_WRITE;
+use crate::modules::role::model::{CONTENT_DELETE, CONTENT_WRITE};
 use axum::middleware;
 
 pub mod handler;
@@ -17,38 +19,63 @@ pub fn router(state: AppState) -> axum::Router<AppState> {
         .route("/movies", axum::routing::get(handler::list_movies))
         .route("/movies/{id}", axum::routing::get(handler::get_movie))
         .route("/movies/{id}/progress", axum::routing::get(handler::get_movie_transcode_progress))
-        .route("/movies/{id}/stream", axum::routing::get(stream_handler::stream_movie))
         .route("/movies/{id}/thumbnail", axum::routing::get(handler::get_movie_thumbnail))
         .route("/movies/{id}/subtitle", axum::routing::get(handler::get_movie_subtitle))
-        .route("/episodes/{id}/stream", axum::routing::get(stream_handler::stream_episode))
         .route("/episodes/{id}/progress", axum::routing::get(handler::get_episode_transcode_progress))
         .route("/episodes/{id}/subtitle", axum::routing::get(handler::get_episode_subtitle))
         .route("/series", axum::routing::get(handler::list_series))
         .route("/series/{id}", axum::routing::get(handler::get_series))
         .route("/series/{id}/thumbnail", axum::routing::get(handler::get_series_thumbnail));
 
-    let protected_routes = Router::new()
+    let stream_routes = Router::new()
+        .route("/movies/{id}/stream", axum::routing::get(stream_handler::stream_movie))
+        .route("/episodes/{id}/stream", axum::routing::get(stream_handler::stream_episode))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            crate::middleware::auth::verified_email_stream_guard
+        ));
+
+    let write_routes = Router::new()
         .route("/movies", post(handler::create_movie))
         .route("/movies/{id}/upload", post(handler::upload_movie_video))
         .route("/movies/{id}/upload-thumbnail", post(handler::upload_movie_thumbnail))
-        .route("/movies/{id}", axum::routing::put(handler::update_movie).delete(handler::delete_movie))
+        .route("/movies/{id}", axum::routing::put(handler::update_movie))
+        .route("/movies/{id}/visibility", axum::routing::put(handler::update_movie_visibility))
         
         .route("/series", post(handler::create_series))
         .route("/series/{id}/upload-thumbnail", post(handler::upload_series_thumbnail))
-        .route("/series/{id}", axum::routing::put(handler::update_series).delete(handler::delete_series))
+        .route("/series/{id}", axum::routing::put(handler::update_series))
+        .route("/series/{id}/visibility", axum::routing::put(handler::update_series_visibility))
         
         .route("/seasons", post(handler::create_season))
-        .route("/seasons/{id}", axum::routing::put(handler::update_season).delete(handler::delete_season))
+        .route("/seasons/{id}", axum::routing::put(handler::update_season))
         
         .route("/episodes", post(handler::create_episode))
-        .route("/episodes/{id}", axum::routing::put(handler::update_episode).delete(handler::delete_episode))
+        .route("/episodes/{id}", axum::routing::put(handler::update_episode))
         .route("/episodes/{id}/upload", post(handler::upload_episode_video))
         .route("/episodes/{id}/upload-thumbnail", post(handler::upload_episode_thumbnail))
-        .route_layer(middleware::from_fn(crate::middleware::role::admin_guard))
+        .route_layer(middleware::from_fn(|req, next| {
+            crate::middleware::role::require_permission(CONTENT_WRITE, req, next)
+        }));
+
+    let delete_routes = Router::new()
+        .route("/movies/{id}", axum::routing::delete(handler::delete_movie))
+        .route("/series/{id}", axum::routing::delete(handler::delete_series))
+        .route("/seasons/{id}", axum::routing::delete(handler::delete_season))
+        .route("/episodes/{id}", axum::routing::delete(handler::delete_episode))
+        .route_layer(middleware::from_fn(|req, next| {
+            crate::middleware::role::require_permission(CONTENT_DELETE, req, next)
+        }));
+
+    let protected_routes = write_routes
+        .merge(delete_routes)
+        .route_layer(middleware::from_fn(|req, next| {
+            crate::middleware::auth::require_scope(SCOPE_CONTENT_WRITE, req, next)
+        }))
         .route_layer(middleware::from_fn_with_state(
             state,
             crate::middleware::auth::auth_middleware
         ));
 
-    public_routes.merge(protected_routes)
+    public_routes.merge(stream_routes).merge(protected_routes)
 }
@@ -34,6 +34,10 @@ impl From<String> for ContentStatus {
     }
 }
 
+// Visibility of a movie or series; episodes follow their series
+pub const VISIBILITY_PUBLISHED: &str = "published";
+pub const VISIBILITY_UNPUBLISHED: &str = "unpublished";
+
 #[derive(Debug, Serialize, Deserialize, FromRow, Clone, ToSchema)]
 pub struct Movie {
     pub id: Uuid,
@@ -52,6 +56,25 @@ pub struct Movie {
     pub created_at: OffsetDateTime,
     #[schema(value_type = String, format = Date)]
     pub updated_at: OffsetDateTime,
+    pub maturity_rating_id: Option<Uuid>,
+    /// e.g. violence, language
+    pub content_advisories: Vec<String>,
+    /// `published` or `unpublished`; only published titles are shown to viewers
+    pub visibility: String,
+    /// Scheduled switch to `published`
+    #[serde(with = "time::serde::iso8601::option")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub publish_at: Option<OffsetDateTime>,
+    /// Scheduled switch to `unpublished`
+    #[serde(with = "time::serde::iso8601::option")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub unpublish_at: Option<OffsetDateTime>,
+}
+
+impl Movie {
+    pub fn is_published(&self) -> bool {
+        self.visibility == VISIBILITY_PUBLISHED
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow, Clone, ToSchema)]
@@ -67,6 +90,22 @@ pub struct Series {
     pub created_at: OffsetDateTime,
     #[schema(value_type = String, format = Date)]
     pub updated_at: OffsetDateTime,
+    pub maturity_rating_id: Option<Uuid>,
+    pub content_advisories: Vec<String>,
+    /// `published` or `unpublished`; the episodes are shown with the series
+    pub visibility: String,
+    #[serde(with = "time::serde::iso8601::option")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub publish_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::iso8601::option")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub unpublish_at: Option<OffsetDateTime>,
+}
+
+impl Series {
+    pub fn is_published(&self) -> bool {
+        self.visibility == VISIBILITY_PUBLISHED
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow, Clone, ToSchema)]
@@ -1,14 +1,75 @@
+use std::collections::HashMap;
 use sqlx::PgPool;
+use time::OffsetDateTime;
 use uuid::Uuid;
 use super::model::{Movie, Series, Season, Episode};
+use crate::common::pagination::SortKey;
 use crate::modules::genre::model::Genre;
 use anyhow::{Result, anyhow};
 
 pub struct ContentRepository;
 
+/// Filters of the movie and series lists, all optional.
+pub struct ContentFilter<'a> {
+    pub max_maturity_level: Option<i32>,
+    /// Genre slug
+    pub genre: Option<&'a str>,
+    pub year_from: Option<i32>,
+    pub year_to: Option<i32>,
+    pub status: Option<&'a str>,
+    pub min_rating: Option<f64>,
+    /// `published` for viewers; staff see every visibility
+    pub visibility: Option<&'a str>,
+}
+
+/// Order of a list page. `sort` is `title`, `year`, `views`, `rating` or `recent`;
+/// ties are broken by id. With `after`, only items past that id and sort value are
+/// returned; the value must be of the kind the sort compares (`Text` for `title`,
+/// `Int` for `year` and `views`, `Float` for `rating`, `Time` for `recent`).
+pub struct ContentOrder<'a> {
+    pub sort: &'a str,
+    pub descending: bool,
+    pub after: Option<(Uuid, SortKey)>,
+}
+
+impl ContentOrder<'_> {
+    fn after_id(&self) -> Option<Uuid> {
+        self.after.as_ref().map(|(id, _)| *id)
+    }
+
+    fn after_text(&self) -> Option<&str> {
+        match &self.after {
+            Some((_, SortKey::Text(value))) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn after_int(&self) -> Option<i64> {
+        match &self.after {
+            Some((_, SortKey::Int(value))) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn after_float(&self) -> Option<f64> {
+        match &self.after {
+            Some((_, SortKey::Float(value))) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn after_time(&self) -> Option<OffsetDateTime> {
+        match &self.after {
+            Some((_, SortKey::Time(value))) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
 impl ContentRepository {
     // --- MOVIE ---
     
+    #[allow(clippy::too_many_arguments)]
     pub async fn create_movie(
         pool: &PgPool,
         title: &str,
@@ -16,19 +77,23 @@ impl ContentRepository {
         description: Option<String>,
         release_year: Option<i32>,
         duration_seconds: Option<i32>,
+        maturity_rating_id: Option<Uuid>,
+        content_advisories: &[String],
     ) -> Result<Movie> {
         let movie = sqlx::query_as!(
             Movie,
             r#"
-            INSERT INTO movies (title, slug, description, release_year, duration_seconds)
-            VALUES ($1, $2, $3, $4, $5)
+            INSERT INTO movies (title, slug, description, release_year, duration_seconds, maturity_rating_id, content_advisories)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
             RETURNING *
             "#,
             title,
             slug,
             description,
             release_year,
-            duration_seconds
+            duration_seconds,
+            maturity_rating_id,
+            content_advisories
         )
         .fetch_one(pool)
         .await?;
@@ -80,6 +145,35 @@ impl ContentRepository {
         Ok(genres)
     }
 
+    /// Genres of several movies in one query, keyed by movie id. Movies without
+    /// genres are missing from the map.
+    pub async fn get_genres_for_movies(pool: &PgPool, movie_ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<Genre>>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT cg.movie_id as "owner_id!", g.id, g.name, g.slug, g.created_at, g.updated_at
+            FROM genres g
+            JOIN content_genres cg ON g.id = cg.genre_id
+            WHERE cg.movie_id = ANY($1)
+            ORDER BY g.name ASC
+            "#,
+            movie_ids
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut genres: HashMap<Uuid, Vec<Genre>> = HashMap::new();
+        for row in rows {
+            genres.entry(row.owner_id).or_default().push(Genre {
+                id: row.id,
+                name: row.name,
+                slug: row.slug,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            });
+        }
+        Ok(genres)
+    }
+
     pub async fn link_movie_genres(pool: &PgPool, movie_id: Uuid, genre_ids: &[Uuid]) -> Result<()> {
         // Start transaction manually if needed, or query one by one. 
         // For simple inserts, UNNEST is efficient.
@@ -97,15 +191,120 @@ impl ContentRepository {
         Ok(())
     }
 
-    pub async fn list_movies(pool: &PgPool) -> Result<Vec<Movie>> {
+    /// One page of movies. A missing value sorts as 0.
+    pub async fn list_movies(
+        pool: &PgPool,
+        filter: &ContentFilter<'_>,
+        order: &ContentOrder<'_>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Movie>> {
         let movies = sqlx::query_as!(
             Movie,
-            "SELECT * FROM movies ORDER BY created_at DESC"
+            r#"
+            SELECT m.* FROM movies m
+            WHERE ($1::INT IS NULL
+                   OR m.maturity_rating_id IN (SELECT id FROM maturity_ratings WHERE level <= $1))
+              AND ($2::VARCHAR IS NULL OR EXISTS (
+                   SELECT 1 FROM content_genres cg JOIN genres g ON g.id = cg.genre_id
+                   WHERE cg.movie_id = m.id AND g.slug = $2))
+              AND ($3::INT IS NULL OR m.release_year >= $3)
+              AND ($4::INT IS NULL OR m.release_year <= $4)
+              AND ($5::VARCHAR IS NULL OR m.status = $5)
+              AND ($6::FLOAT8 IS NULL OR m.rating >= $6)
+              AND ($12::VARCHAR IS NULL OR m.visibility = $12)
+              AND ($8::UUID IS NULL OR CASE
+                   WHEN $7::TEXT = 'title' AND NOT $9::BOOLEAN THEN (LOWER(m.title), m.id) > (LOWER($13::TEXT), $8)
+                   WHEN $7 = 'title' THEN (LOWER(m.title), m.id) < (LOWER($13), $8)
+                   WHEN $7 = 'year' AND NOT $9 THEN (COALESCE(m.release_year, 0)::BIGINT, m.id) > ($14::BIGINT, $8)
+                   WHEN $7 = 'year' THEN (COALESCE(m.release_year, 0)::BIGINT, m.id) < ($14, $8)
+                   WHEN $7 = 'views' AND NOT $9 THEN (COALESCE(m.views, 0)::BIGINT, m.id) > ($14, $8)
+                   WHEN $7 = 'views' THEN (COALESCE(m.views, 0)::BIGINT, m.id) < ($14, $8)
+                   WHEN $7 = 'rating' AND NOT $9 THEN (COALESCE(m.rating, 0), m.id) > ($15::FLOAT8, $8)
+                   WHEN $7 = 'rating' THEN (COALESCE(m.rating, 0), m.id) < ($15, $8)
+                   WHEN NOT $9 THEN (m.created_at, m.id) > ($16::TIMESTAMPTZ, $8)
+                   ELSE (m.created_at, m.id) < ($16, $8)
+                  END)
+            ORDER BY
+                CASE WHEN $7 = 'title' AND NOT $9 THEN LOWER(m.title) END ASC,
+                CASE WHEN $7 = 'title' AND $9 THEN LOWER(m.title) END DESC,
+                CASE WHEN $7 = 'year' AND NOT $9 THEN COALESCE(m.release_year, 0) END ASC,
+                CASE WHEN $7 = 'year' AND $9 THEN COALESCE(m.release_year, 0) END DESC,
+                CASE WHEN $7 = 'views' AND NOT $9 THEN COALESCE(m.views, 0) END ASC,
+                CASE WHEN $7 = 'views' AND $9 THEN COALESCE(m.views, 0) END DESC,
+                CASE WHEN $7 = 'rating' AND NOT $9 THEN COALESCE(m.rating, 0) END ASC,
+                CASE WHEN $7 = 'rating' AND $9 THEN COALESCE(m.rating, 0) END DESC,
+                CASE WHEN $7 = 'recent' AND NOT $9 THEN m.created_at END ASC,
+                CASE WHEN $7 = 'recent' AND $9 THEN m.created_at END DESC,
+                CASE WHEN NOT $9 THEN m.id END ASC,
+                CASE WHEN $9 THEN m.id END DESC
+            LIMIT $10 OFFSET $11
+            "#,
+            filter.max_maturity_level,
+            filter.genre,
+            filter.year_from,
+            filter.year_to,
+            filter.status,
+            filter.min_rating,
+            order.sort,
+            order.after_id(),
+            order.descending,
+            limit,
+            offset,
+            filter.visibility,
+            order.after_text(),
+            order.after_int(),
+            order.after_float(),
+            order.after_time()
         )
         .fetch_all(pool)
         .await?;
         Ok(movies)
     }
+
+    pub async fn count_movies(pool: &PgPool, filter: &ContentFilter<'_>) -> Result<i64> {
+        let row = sqlx::query!(
+            r#"
+            SELECT COUNT(*) as "count!"
+            FROM movies m
+            WHERE ($1::INT IS NULL
+                   OR m.maturity_rating_id IN (SELECT id FROM maturity_ratings WHERE level <= $1))
+              AND ($2::VARCHAR IS NULL OR EXISTS (
+                   SELECT 1 FROM content_genres cg JOIN genres g ON g.id = cg.genre_id
+                   WHERE cg.movie_id = m.id AND g.slug = $2))
+              AND ($3::INT IS NULL OR m.release_year >= $3)
+              AND ($4::INT IS NULL OR m.release_year <= $4)
+              AND ($5::VARCHAR IS NULL OR m.status = $5)
+              AND ($6::FLOAT8 IS NULL OR m.rating >= $6)
+              AND ($7::VARCHAR IS NULL OR m.visibility = $7)
+            "#,
+            filter.max_maturity_level,
+            filter.genre,
+            filter.year_from,
+            filter.year_to,
+            filter.status,
+            filter.min_rating,
+            filter.visibility
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(row.count)
+    }
+
+    pub async fn get_movie_maturity_level(pool: &PgPool, id: Uuid) -> Result<Option<i32>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT r.level as "level?"
+            FROM movies m
+            LEFT JOIN maturity_ratings r ON r.id = m.maturity_rating_id
+            WHERE m.id = $1
+            "#,
+            id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.and_then(|r| r.level))
+    }
     
     // --- SERIES ---
 
@@ -115,18 +314,22 @@ impl ContentRepository {
         slug: &str,
         description: Option<String>,
         release_year: Option<i32>,
+        maturity_rating_id: Option<Uuid>,
+        content_advisories: &[String],
     ) -> Result<Series> {
         let series = sqlx::query_as!(
             Series,
             r#"
-            INSERT INTO series (title, slug, description, release_year)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO series (title, slug, description, release_year, maturity_rating_id, content_advisories)
+            VALUES ($1, $2, $3, $4, $5, $6)
             RETURNING *
             "#,
             title,
             slug,
             description,
-            release_year
+            release_year,
+            maturity_rating_id,
+            content_advisories
         )
         .fetch_one(pool)
         .await?;
@@ -175,16 +378,189 @@ impl ContentRepository {
         Ok(genres)
     }
 
-    pub async fn list_series(pool: &PgPool) -> Result<Vec<Series>> {
+    /// Genres of several series in one query, like `get_genres_for_movies`.
+    pub async fn get_genres_for_series(pool: &PgPool, series_ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<Genre>>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT cg.series_id as "owner_id!", g.id, g.name, g.slug, g.created_at, g.updated_at
+            FROM genres g
+            JOIN content_genres cg ON g.id = cg.genre_id
+            WHERE cg.series_id = ANY($1)
+            ORDER BY g.name ASC
+            "#,
+            series_ids
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut genres: HashMap<Uuid, Vec<Genre>> = HashMap::new();
+        for row in rows {
+            genres.entry(row.owner_id).or_default().push(Genre {
+                id: row.id,
+                name: row.name,
+                slug: row.slug,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            });
+        }
+        Ok(genres)
+    }
+
+    /// Views of several series in one query, keyed by series id.
+    pub async fn get_views_for_series(pool: &PgPool, series_ids: &[Uuid]) -> Result<HashMap<Uuid, i64>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, series_views(id) as "views!"
+            FROM series
+            WHERE id = ANY($1)
+            "#,
+            series_ids
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(rows.into_iter().map(|r| (r.id, r.views)).collect())
+    }
+
+    /// One page of series, like `list_movies`. Views are the sum of the episodes'
+    /// views; `status` matches series with an episode in that status.
+    pub async fn list_series(
+        pool: &PgPool,
+        filter: &ContentFilter<'_>,
+        order: &ContentOrder<'_>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Series>> {
         let series = sqlx::query_as!(
             Series,
-            "SELECT * FROM series ORDER BY created_at DESC"
+            r#"
+            SELECT s.* FROM series s
+            WHERE ($1::INT IS NULL
+                   OR s.maturity_rating_id IN (SELECT id FROM maturity_ratings WHERE level <= $1))
+              AND ($2::VARCHAR IS NULL OR EXISTS (
+                   SELECT 1 FROM content_genres cg JOIN genres g ON g.id = cg.genre_id
+                   WHERE cg.series_id = s.id AND g.slug = $2))
+              AND ($3::INT IS NULL OR s.release_year >= $3)
+              AND ($4::INT IS NULL OR s.release_year <= $4)
+              AND ($5::VARCHAR IS NULL OR EXISTS (
+                   SELECT 1 FROM seasons sn JOIN episodes e ON e.season_id = sn.id
+                   WHERE sn.series_id = s.id AND e.status = $5))
+              AND ($6::FLOAT8 IS NULL OR s.rating >= $6)
+              AND ($12::VARCHAR IS NULL OR s.visibility = $12)
+              AND ($8::UUID IS NULL OR CASE
+                   WHEN $7::TEXT = 'title' AND NOT $9::BOOLEAN THEN (LOWER(s.title), s.id) > (LOWER($13::TEXT), $8)
+                   WHEN $7 = 'title' THEN (LOWER(s.title), s.id) < (LOWER($13), $8)
+                   WHEN $7 = 'year' AND NOT $9 THEN (COALESCE(s.release_year, 0)::BIGINT, s.id) > ($14::BIGINT, $8)
+                   WHEN $7 = 'year' THEN (COALESCE(s.release_year, 0)::BIGINT, s.id) < ($14, $8)
+                   WHEN $7 = 'views' AND NOT $9 THEN (series_views(s.id)::BIGINT, s.id) > ($14, $8)
+                   WHEN $7 = 'views' THEN (series_views(s.id)::BIGINT, s.id) < ($14, $8)
+                   WHEN $7 = 'rating' AND NOT $9 THEN (COALESCE(s.rating, 0), s.id) > ($15::FLOAT8, $8)
+                   WHEN $7 = 'rating' THEN (COALESCE(s.rating, 0), s.id) < ($15, $8)
+                   WHEN NOT $9 THEN (s.created_at, s.id) > ($16::TIMESTAMPTZ, $8)
+                   ELSE (s.created_at, s.id) < ($16, $8)
+                  END)
+            ORDER BY
+                CASE WHEN $7 = 'title' AND NOT $9 THEN LOWER(s.title) END ASC,
+                CASE WHEN $7 = 'title' AND $9 THEN LOWER(s.title) END DESC,
+                CASE WHEN $7 = 'year' AND NOT $9 THEN COALESCE(s.release_year, 0) END ASC,
+                CASE WHEN $7 = 'year' AND $9 THEN COALESCE(s.release_year, 0) END DESC,
+                CASE WHEN $7 = 'views' AND NOT $9 THEN series_views(s.id) END ASC,
+                CASE WHEN $7 = 'views' AND $9 THEN series_views(s.id) END DESC,
+                CASE WHEN $7 = 'rating' AND NOT $9 THEN COALESCE(s.rating, 0) END ASC,
+                CASE WHEN $7 = 'rating' AND $9 THEN COALESCE(s.rating, 0) END DESC,
+                CASE WHEN $7 = 'recent' AND NOT $9 THEN s.created_at END ASC,
+                CASE WHEN $7 = 'recent' AND $9 THEN s.created_at END DESC,
+                CASE WHEN NOT $9 THEN s.id END ASC,
+                CASE WHEN $9 THEN s.id END DESC
+            LIMIT $10 OFFSET $11
+            "#,
+            filter.max_maturity_level,
+            filter.genre,
+            filter.year_from,
+            filter.year_to,
+            filter.status,
+            filter.min_rating,
+            order.sort,
+            order.after_id(),
+            order.descending,
+            limit,
+            offset,
+            filter.visibility,
+            order.after_text(),
+            order.after_int(),
+            order.after_float(),
+            order.after_time()
         )
         .fetch_all(pool)
         .await?;
         Ok(series)
     }
 
+    pub async fn count_series(pool: &PgPool, filter: &ContentFilter<'_>) -> Result<i64> {
+        let row = sqlx::query!(
+            r#"
+            SELECT COUNT(*) as "count!"
+            FROM series s
+            WHERE ($1::INT IS NULL
+                   OR s.maturity_rating_id IN (SELECT id FROM maturity_ratings WHERE level <= $1))
+              AND ($2::VARCHAR IS NULL OR EXISTS (
+                   SELECT 1 FROM content_genres cg JOIN genres g ON g.id = cg.genre_id
+                   WHERE cg.series_id = s.id AND g.slug = $2))
+              AND ($3::INT IS NULL OR s.release_year >= $3)
+              AND ($4::INT IS NULL OR s.release_year <= $4)
+              AND ($5::VARCHAR IS NULL OR EXISTS (
+                   SELECT 1 FROM seasons sn JOIN episodes e ON e.season_id = sn.id
+                   WHERE sn.series_id = s.id AND e.status = $5))
+              AND ($6::FLOAT8 IS NULL OR s.rating >= $6)
+              AND ($7::VARCHAR IS NULL OR s.visibility = $7)
+            "#,
+            filter.max_maturity_level,
+            filter.genre,
+            filter.year_from,
+            filter.year_to,
+            filter.status,
+            filter.min_rating,
+            filter.visibility
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(row.count)
+    }
+
+    /// Maturity level of the series an episode belongs to.
+    pub async fn get_episode_maturity_level(pool: &PgPool, episode_id: Uuid) -> Result<Option<i32>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT r.level as "level?"
+            FROM episodes e
+            JOIN seasons s ON s.id = e.season_id
+            JOIN series sr ON sr.id = s.series_id
+            LEFT JOIN maturity_ratings r ON r.id = sr.maturity_rating_id
+            WHERE e.id = $1
+            "#,
+            episode_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.and_then(|r| r.level))
+    }
+
+    /// Visibility of the series an episode belongs to.
+    pub async fn get_episode_visibility(pool: &PgPool, episode_id: Uuid) -> Result<Option<String>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT sr.visibility
+            FROM episodes e
+            JOIN seasons s ON s.id = e.season_id
+            JOIN series sr ON sr.id = s.series_id
+            WHERE e.id = $1
+            "#,
+            episode_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| r.visibility))
+    }
+
     // --- SEASONS ---
 
     pub async fn create_season(
@@ -280,6 +656,24 @@ impl ContentRepository {
         .await?;
         Ok(episodes)
     }
+
+    /// Episodes of several seasons in one query, keyed by season id and ordered
+    /// by episode number.
+    pub async fn get_episodes_for_seasons(pool: &PgPool, season_ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<Episode>>> {
+        let rows = sqlx::query_as!(
+            Episode,
+            "SELECT * FROM episodes WHERE season_id = ANY($1) ORDER BY episode_number ASC",
+            season_ids
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut episodes: HashMap<Uuid, Vec<Episode>> = HashMap::new();
+        for episode in rows {
+            episodes.entry(episode.season_id).or_default().push(episode);
+        }
+        Ok(episodes)
+    }
     // --- MOVIE UPDATES ---
 
     pub async fn update_movie(
@@ -288,6 +682,8 @@ impl ContentRepository {
         title: Option<String>,
         description: Option<String>,
         release_year: Option<i32>,
+        maturity_rating_id: Option<Option<Uuid>>,
+        content_advisories: Option<Vec<String>>,
     ) -> Result<Movie> {
         let movie = sqlx::query_as!(
             Movie,
@@ -297,13 +693,44 @@ impl ContentRepository {
                 title = COALESCE($1, title),
                 description = COALESCE($2, description),
                 release_year = COALESCE($3, release_year),
+                maturity_rating_id = CASE WHEN $4 THEN $5 ELSE maturity_rating_id END,
+                content_advisories = COALESCE($6, content_advisories),
                 updated_at = NOW()
-            WHERE id = $4
+            WHERE id = $7
             RETURNING *
             "#,
             title,
             description,
             release_year,
+            maturity_rating_id.is_some(),
+            maturity_rating_id.flatten(),
+            content_advisories.as_deref(),
+            id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(movie)
+    }
+
+    /// Sets the visibility and replaces the publishing schedule.
+    pub async fn update_movie_visibility(
+        pool: &PgPool,
+        id: Uuid,
+        visibility: &str,
+        publish_at: Option<OffsetDateTime>,
+        unpublish_at: Option<OffsetDateTime>,
+    ) -> Result<Movie> {
+        let movie = sqlx::query_as!(
+            Movie,
+            r#"
+            UPDATE movies
+            SET visibility = $1, publish_at = $2, unpublish_at = $3, updated_at = NOW()
+            WHERE id = $4
+            RETURNING *
+            "#,
+            visibility,
+            publish_at,
+            unpublish_at,
             id
         )
         .fetch_one(pool)
@@ -311,6 +738,29 @@ impl ContentRepository {
         Ok(movie)
     }
 
+    /// Applies the schedules that are due and clears them. When both changes are
+    /// due, e.g. after downtime, the later one wins. Returns the ids of the movies.
+    pub async fn apply_movie_schedules(pool: &PgPool) -> Result<Vec<Uuid>> {
+        let rows = sqlx::query!(
+            r#"
+            UPDATE movies
+            SET visibility = CASE
+                    WHEN unpublish_at <= NOW() AND (publish_at IS NULL OR publish_at > NOW() OR publish_at < unpublish_at)
+                    THEN 'unpublished'
+                    ELSE 'published'
+                END,
+                publish_at = CASE WHEN publish_at <= NOW() THEN NULL ELSE publish_at END,
+                unpublish_at = CASE WHEN unpublish_at <= NOW() THEN NULL ELSE unpublish_at END,
+                updated_at = NOW()
+            WHERE publish_at <= NOW() OR unpublish_at <= NOW()
+            RETURNING id
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(rows.into_iter().map(|r| r.id).collect())
+    }
+
     pub async fn delete_movie(pool: &PgPool, id: Uuid) -> Result<()> {
         sqlx::query!("DELETE FROM movies WHERE id = $1", id)
             .execute(pool)
@@ -326,6 +776,8 @@ impl ContentRepository {
         title: Option<String>,
         description: Option<String>,
         release_year: Option<i32>,
+        maturity_rating_id: Option<Option<Uuid>>,
+        content_advisories: Option<Vec<String>>,
     ) -> Result<Series> {
         let series = sqlx::query_as!(
             Series,
@@ -335,13 +787,18 @@ impl ContentRepository {
                 title = COALESCE($1, title),
                 description = COALESCE($2, description),
                 release_year = COALESCE($3, release_year),
+                maturity_rating_id = CASE WHEN $4 THEN $5 ELSE maturity_rating_id END,
+                content_advisories = COALESCE($6, content_advisories),
                 updated_at = NOW()
-            WHERE id = $4
+            WHERE id = $7
             RETURNING *
             "#,
             title,
             description,
             release_year,
+            maturity_rating_id.is_some(),
+            maturity_rating_id.flatten(),
+            content_advisories.as_deref(),
             id
         )
         .fetch_one(pool)
@@ -364,6 +821,54 @@ impl ContentRepository {
         Ok(())
     }
 
+    /// Sets the visibility and replaces the publishing schedule.
+    pub async fn update_series_visibility(
+        pool: &PgPool,
+        id: Uuid,
+        visibility: &str,
+        publish_at: Option<OffsetDateTime>,
+        unpublish_at: Option<OffsetDateTime>,
+    ) -> Result<Series> {
+        let series = sqlx::query_as!(
+            Series,
+            r#"
+            UPDATE series
+            SET visibility = $1, publish_at = $2, unpublish_at = $3, updated_at = NOW()
+            WHERE id = $4
+            RETURNING *
+            "#,
+            visibility,
+            publish_at,
+            unpublish_at,
+            id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(series)
+    }
+
+    /// Like `apply_movie_schedules`, for series.
+    pub async fn apply_series_schedules(pool: &PgPool) -> Result<Vec<Uuid>> {
+        let rows = sqlx::query!(
+            r#"
+            UPDATE series
+            SET visibility = CASE
+                    WHEN unpublish_at <= NOW() AND (publish_at IS NULL OR publish_at > NOW() OR publish_at < unpublish_at)
+                    THEN 'unpublished'
+                    ELSE 'published'
+                END,
+                publish_at = CASE WHEN publish_at <= NOW() THEN NULL ELSE publish_at END,
+                unpublish_at = CASE WHEN unpublish_at <= NOW() THEN NULL ELSE unpublish_at END,
+                updated_at = NOW()
+            WHERE publish_at <= NOW() OR unpublish_at <= NOW()
+            RETURNING id
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(rows.into_iter().map(|r| r.id).collect())
+    }
+
     pub async fn delete_series(pool: &PgPool, id: Uuid) -> Result<()> {
         sqlx::query!("DELETE FROM series WHERE id = $1", id)
             .execute(pool)
@@ -1,18 +1,31 @@
 use super::dto::{
     CreateMovieRequest, CreateSeriesRequest, CreateSeasonRequest, CreateEpisodeRequest,
     UpdateMovieRequest, UpdateSeriesRequest, UpdateSeasonRequest, UpdateEpisodeRequest,
-    MovieResponse, SeriesResponse, SeriesListResponse, SeasonResponse
+    MovieResponse, SeriesResponse, SeriesListResponse, SeasonResponse, ContentListQuery, UpdateVisibilityRequest
 };
-use super::repository::ContentRepository;
+use super::model::{Movie, Season, Series, VISIBILITY_PUBLISHED, VISIBILITY_UNPUBLISHED};
+use super::repository::{ContentFilter, ContentOrder, ContentRepository};
+use crate::common::pagination::{Cursor, Page, Pagination, SortKey};
 use crate::modules::genre::dto::GenreResponse;
+use crate::modules::profile::model::ViewerContext;
+use crate::modules::rating::model::MaturityRating;
+use crate::modules::rating::service::RatingService;
+use crate::modules::search::model::{CONTENT_MOVIE, CONTENT_SERIES};
+use crate::modules::search::service::SearchService;
 use crate::state::AppState;
 use crate::modules::content::events::TranscodeJob;
-use anyhow::{Result, anyhow};
+use crate::common::error::{AppError, AppResult};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use time::OffsetDateTime;
 use uuid::Uuid;
 // use slug::slugify; // Removed unused import
 
 pub struct ContentService;
 
+const LIST_SORTS: &[&str] = &["title", "year", "views", "rating", "recent"];
+const LIST_STATUSES: &[&str] = &["DRAFT", "PROCESSING", "READY", "FAILED"];
+
 impl ContentService {
     fn generate_slug(title: &str) -> String {
         // For now let's just use a simple replace. Ideally use `slug` crate.
@@ -23,10 +36,144 @@ impl ContentService {
             .collect()
     }
 
+    /// Trims, lowercases and de-duplicates content advisories.
+    fn normalize_advisories(advisories: Vec<String>) -> Vec<String> {
+        let mut advisories: Vec<String> = advisories
+            .into_iter()
+            .map(|a| a.trim().to_lowercase())
+            .filter(|a| !a.is_empty())
+            .collect();
+        advisories.sort();
+        advisories.dedup();
+        advisories
+    }
+
+    /// The title is rated above the maturity limit of the viewer's profile.
+    fn restricted() -> AppError {
+        AppError::forbidden("This title is restricted by parental controls")
+    }
+
+    /// Validates the sort of a list request. Returns the sort field, whether it is
+    /// descending, and the cursor scope of that combination.
+    fn list_sort(query: &ContentListQuery) -> AppResult<(&str, bool, String)> {
+        let sort = query.sort.as_deref().unwrap_or("recent");
+        if !LIST_SORTS.contains(&sort) {
+            return Err(AppError::validation(format!(
+                "Invalid sort '{}', expected one of {}", sort, LIST_SORTS.join(", ")
+            )));
+        }
+        let descending = match query.order.as_deref() {
+            None => sort != "title",
+            Some("asc") => false,
+            Some("desc") => true,
+            Some(other) => return Err(AppError::validation(format!("Invalid order '{}', expected 'asc' or 'desc'", other))),
+        };
+        let scope = format!("{}:{}", sort, if descending { "desc" } else { "asc" });
+        Ok((sort, descending, scope))
+    }
+
+    /// Order of a list page, continuing from the request's cursor. A cursor whose sort
+    /// value does not fit the sort was not made by `paginate` and is rejected.
+    fn list_order(sort: &str, descending: bool, cursor: Option<Cursor>) -> AppResult<ContentOrder<'_>> {
+        let backward = cursor.as_ref().is_some_and(|c| c.backward);
+        let after = match cursor {
+            None => None,
+            Some(cursor) => {
+                let fits = matches!(
+                    (sort, &cursor.key),
+                    ("title", SortKey::Text(_))
+                        | ("year" | "views", SortKey::Int(_))
+                        | ("rating", SortKey::Float(_))
+                        | ("recent", SortKey::Time(_))
+                );
+                if !fits {
+                    return Err(AppError::validation("Invalid cursor"));
+                }
+                Some((cursor.id, cursor.key))
+            }
+        };
+
+        Ok(ContentOrder {
+            sort,
+            // A backward cursor reads the list in reverse from the cursor
+            descending: descending != backward,
+            after,
+        })
+    }
+
+    /// Sort value of a list item, as the list query computes it (missing values are 0).
+    fn sort_key(
+        sort: &str,
+        title: &str,
+        release_year: Option<i32>,
+        views: i64,
+        rating: Option<f64>,
+        created_at: OffsetDateTime,
+    ) -> SortKey {
+        match sort {
+            "title" => SortKey::Text(title.to_string()),
+            "year" => SortKey::Int(release_year.unwrap_or(0).into()),
+            "views" => SortKey::Int(views),
+            "rating" => SortKey::Float(rating.unwrap_or(0.0)),
+            _ => SortKey::Time(created_at),
+        }
+    }
+
+    fn list_filter<'a>(query: &'a ContentListQuery, viewer: &ViewerContext, status: Option<&'a str>) -> AppResult<ContentFilter<'a>> {
+        if let (Some(from), Some(to)) = (query.year_from, query.year_to)
+            && from > to
+        {
+            return Err(AppError::validation("year_from must not be after year_to"));
+        }
+        if let Some(status) = status
+            && !LIST_STATUSES.contains(&status)
+        {
+            return Err(AppError::validation(format!(
+                "Invalid status '{}', expected one of {}", status, LIST_STATUSES.join(", ")
+            )));
+        }
+
+        Ok(ContentFilter {
+            max_maturity_level: viewer.max_maturity_level,
+            genre: query.genre.as_deref().map(str::trim).filter(|g| !g.is_empty()),
+            year_from: query.year_from,
+            year_to: query.year_to,
+            status,
+            min_rating: query.min_rating,
+            visibility: (!viewer.sees_unpublished).then_some(VISIBILITY_PUBLISHED),
+        })
+    }
+
+    /// Checks a visibility change. Scheduled times must lie ahead and differ, so
+    /// the order of the two changes is clear.
+    fn check_visibility(req: &UpdateVisibilityRequest) -> AppResult<()> {
+        if req.visibility != VISIBILITY_PUBLISHED && req.visibility != VISIBILITY_UNPUBLISHED {
+            return Err(AppError::validation(format!(
+                "Invalid visibility '{}', expected '{}' or '{}'", req.visibility, VISIBILITY_PUBLISHED, VISIBILITY_UNPUBLISHED
+            )));
+        }
+        let now = OffsetDateTime::now_utc();
+        if [req.publish_at, req.unpublish_at].into_iter().flatten().any(|at| at <= now) {
+            return Err(AppError::validation("publish_at and unpublish_at must be in the future"));
+        }
+        if req.publish_at.is_some() && req.publish_at == req.unpublish_at {
+            return Err(AppError::validation("publish_at and unpublish_at must differ"));
+        }
+        Ok(())
+    }
+
+    async fn resolve_rating(state: &AppState, code: Option<&str>) -> AppResult<Option<MaturityRating>> {
+        match code {
+            Some(code) => RatingService::resolve_code(state, code).await,
+            None => Ok(None),
+        }
+    }
+
     // --- MOVIE ---
 
-    pub async fn create_movie(state: AppState, req: CreateMovieRequest) -> Result<MovieResponse> {
+    pub async fn create_movie(state: AppState, req: CreateMovieRequest) -> AppResult<MovieResponse> {
         let slug = format!("{}-{}", Self::generate_slug(&req.title), Uuid::new_v4().as_simple().to_string()[..6].to_string());
+        let maturity_rating = Self::resolve_rating(&state, req.maturity_rating.as_deref()).await?;
         
         let movie = ContentRepository::create_movie(
             &state.db,
@@ -35,11 +182,14 @@ impl ContentService {
             req.description,
             req.release_year,
             req.duration_seconds,
+            maturity_rating.as_ref().map(|r| r.id),
+            &Self::normalize_advisories(req.content_advisories),
         ).await?;
 
         if !req.genre_ids.is_empty() {
             ContentRepository::link_movie_genres(&state.db, movie.id, &req.genre_ids).await?;
         }
+        SearchService::refresh_suggestion(&state, CONTENT_MOVIE, movie.id).await;
         
         // Fetch full data for response
         let genres = ContentRepository::get_movie_genres(&state.db, movie.id).await?;
@@ -48,25 +198,42 @@ impl ContentService {
         Ok(MovieResponse {
             movie,
             genres: genre_dtos,
+            maturity_rating,
         })
     }
     
-    pub async fn list_movies(state: AppState) -> Result<Vec<MovieResponse>> {
-        let movies = ContentRepository::list_movies(&state.db).await?;
-        
-        let mut responses = Vec::new();
-        for movie in movies {
-             let genres = ContentRepository::get_movie_genres(&state.db, movie.id).await?;
-             let genre_dtos = genres.into_iter().map(GenreResponse::from).collect();
-             responses.push(MovieResponse { movie, genres: genre_dtos });
-        }
-        
-        Ok(responses)
+    /// One page of the movies the viewer's maturity limit allows.
+    pub async fn list_movies(
+        state: AppState,
+        viewer: ViewerContext,
+        query: ContentListQuery,
+        pagination: Pagination,
+    ) -> AppResult<Page<MovieResponse>> {
+        let (sort, descending, scope) = Self::list_sort(&query)?;
+        let status = query.status.as_deref().map(|s| s.trim().to_uppercase());
+        let filter = Self::list_filter(&query, &viewer, status.as_deref())?;
+        let order = Self::list_order(sort, descending, pagination.cursor(&scope)?)?;
+
+        let movies = ContentRepository::list_movies(&state.db, &filter, &order, pagination.fetch_limit(), pagination.offset()).await?;
+        let total = ContentRepository::count_movies(&state.db, &filter).await?;
+        let page = pagination.paginate(movies, total, &scope, |m| {
+            let movie_views = m.views.unwrap_or(0).into();
+            (m.id, Self::sort_key(sort, &m.title, m.release_year, movie_views, m.rating, m.created_at))
+        });
+        let ratings = RatingService::all_by_id(&state).await?;
+        let responses = Self::movie_responses(&state.db, &page.items, &ratings).await?;
+        Ok(page.with_items(responses))
     }
 
-    pub async fn get_movie(state: AppState, id: Uuid) -> Result<MovieResponse> {
+    pub async fn get_movie(state: AppState, id: Uuid, viewer: ViewerContext) -> AppResult<MovieResponse> {
         let movie = ContentRepository::get_movie_by_id(&state.db, id).await?
-            .ok_or(anyhow!("Movie not found"))?;
+            .filter(|m| m.is_published() || viewer.sees_unpublished)
+            .ok_or_else(|| AppError::not_found("Movie not found"))?;
+
+        let maturity_rating = RatingService::get(&state, movie.maturity_rating_id).await?;
+        if !viewer.allows(maturity_rating.as_ref().map(|r| r.level)) {
+            return Err(Self::restricted());
+        }
             
         let genres = ContentRepository::get_movie_genres(&state.db, movie.id).await?;
         let genre_dtos = genres.into_iter().map(GenreResponse::from).collect();
@@ -74,13 +241,15 @@ impl ContentService {
         Ok(MovieResponse {
             movie,
             genres: genre_dtos,
+            maturity_rating,
         })
     }
 
     // --- SERIES ---
 
-    pub async fn create_series(state: AppState, req: CreateSeriesRequest) -> Result<SeriesResponse> {
+    pub async fn create_series(state: AppState, req: CreateSeriesRequest) -> AppResult<SeriesResponse> {
         let slug = format!("{}-{}", Self::generate_slug(&req.title), Uuid::new_v4().as_simple().to_string()[..6].to_string());
+        let maturity_rating = Self::resolve_rating(&state, req.maturity_rating.as_deref()).await?;
         
         let series = ContentRepository::create_series(
             &state.db,
@@ -88,11 +257,14 @@ impl ContentService {
             &slug,
             req.description,
             req.release_year,
+            maturity_rating.as_ref().map(|r| r.id),
+            &Self::normalize_advisories(req.content_advisories),
         ).await?;
 
         if !req.genre_ids.is_empty() {
             ContentRepository::link_series_genres(&state.db, series.id, &req.genre_ids).await?;
         }
+        SearchService::refresh_suggestion(&state, CONTENT_SERIES, series.id).await;
         
         let genres = ContentRepository::get_series_genres(&state.db, series.id).await?;
         let genre_dtos = genres.into_iter().map(GenreResponse::from).collect();
@@ -100,57 +272,127 @@ impl ContentService {
         Ok(SeriesResponse {
             series,
             genres: genre_dtos,
+            maturity_rating,
             seasons: vec![],
         })
     }
 
-    pub async fn list_series(state: AppState) -> Result<Vec<SeriesListResponse>> {
-        let series_list = ContentRepository::list_series(&state.db).await?;
-        
-        let mut responses = Vec::new();
-        for s in series_list {
-             let genres = ContentRepository::get_series_genres(&state.db, s.id).await?;
-             let genre_dtos = genres.into_iter().map(GenreResponse::from).collect();
-             responses.push(SeriesListResponse { series: s, genres: genre_dtos });
-        }
-        
-        Ok(responses)
+    /// One page of the series the viewer's maturity limit allows.
+    pub async fn list_series(
+        state: AppState,
+        viewer: ViewerContext,
+        query: ContentListQuery,
+        pagination: Pagination,
+    ) -> AppResult<Page<SeriesListResponse>> {
+        let (sort, descending, scope) = Self::list_sort(&query)?;
+        let status = query.status.as_deref().map(|s| s.trim().to_uppercase());
+        let filter = Self::list_filter(&query, &viewer, status.as_deref())?;
+        let order = Self::list_order(sort, descending, pagination.cursor(&scope)?)?;
+
+        let series_list = ContentRepository::list_series(&state.db, &filter, &order, pagination.fetch_limit(), pagination.offset()).await?;
+        let total = ContentRepository::count_series(&state.db, &filter).await?;
+        // Series views are computed from the episodes, only needed for cursors of that sort
+        let views = if sort == "views" {
+            let ids: Vec<Uuid> = series_list.iter().map(|s| s.id).collect();
+            ContentRepository::get_views_for_series(&state.db, &ids).await?
+        } else {
+            HashMap::new()
+        };
+        let page = pagination.paginate(series_list, total, &scope, |s| {
+            let series_views = views.get(&s.id).copied().unwrap_or(0);
+            (s.id, Self::sort_key(sort, &s.title, s.release_year, series_views, s.rating, s.created_at))
+        });
+        let ratings = RatingService::all_by_id(&state).await?;
+        let responses = Self::series_list_responses(&state.db, &page.items, &ratings).await?;
+        Ok(page.with_items(responses))
     }
     
-    pub async fn get_series(state: AppState, id: Uuid) -> Result<SeriesResponse> {
+    pub async fn get_series(state: AppState, id: Uuid, viewer: ViewerContext) -> AppResult<SeriesResponse> {
         let series = ContentRepository::get_series_by_id(&state.db, id).await?
-            .ok_or(anyhow!("Series not found"))?;
+            .filter(|s| s.is_published() || viewer.sees_unpublished)
+            .ok_or_else(|| AppError::not_found("Series not found"))?;
+
+        let maturity_rating = RatingService::get(&state, series.maturity_rating_id).await?;
+        if !viewer.allows(maturity_rating.as_ref().map(|r| r.level)) {
+            return Err(Self::restricted());
+        }
             
         let genres = ContentRepository::get_series_genres(&state.db, series.id).await?;
         let genre_dtos = genres.into_iter().map(GenreResponse::from).collect();
         
         // Get seasons and episodes
         let season_models = ContentRepository::get_series_seasons(&state.db, series.id).await?;
-        let mut season_responses = Vec::new();
-        
-        for season in season_models {
-            let episodes = ContentRepository::get_season_episodes(&state.db, season.id).await?;
-            season_responses.push(SeasonResponse {
-                season,
-                episodes
-            });
-        }
+        let season_responses = Self::season_responses(&state.db, season_models).await?;
 
         Ok(SeriesResponse {
             series,
             genres: genre_dtos,
+            maturity_rating,
             seasons: season_responses,
         })
     }
 
 
 
+    // --- RESPONSE ASSEMBLY ---
+    // Related rows are loaded for the whole set in one query each and matched up
+    // in memory, so the query count does not grow with the number of items.
+
+    async fn movie_responses(
+        db: &PgPool,
+        movies: &[Movie],
+        ratings: &HashMap<Uuid, MaturityRating>,
+    ) -> AppResult<Vec<MovieResponse>> {
+        let ids: Vec<Uuid> = movies.iter().map(|m| m.id).collect();
+        let mut genres = ContentRepository::get_genres_for_movies(db, &ids).await?;
+
+        Ok(movies
+            .iter()
+            .map(|movie| MovieResponse {
+                movie: movie.clone(),
+                genres: genres.remove(&movie.id).unwrap_or_default().into_iter().map(GenreResponse::from).collect(),
+                maturity_rating: movie.maturity_rating_id.and_then(|id| ratings.get(&id).cloned()),
+            })
+            .collect())
+    }
+
+    async fn series_list_responses(
+        db: &PgPool,
+        series: &[Series],
+        ratings: &HashMap<Uuid, MaturityRating>,
+    ) -> AppResult<Vec<SeriesListResponse>> {
+        let ids: Vec<Uuid> = series.iter().map(|s| s.id).collect();
+        let mut genres = ContentRepository::get_genres_for_series(db, &ids).await?;
+
+        Ok(series
+            .iter()
+            .map(|s| SeriesListResponse {
+                series: s.clone(),
+                genres: genres.remove(&s.id).unwrap_or_default().into_iter().map(GenreResponse::from).collect(),
+                maturity_rating: s.maturity_rating_id.and_then(|id| ratings.get(&id).cloned()),
+            })
+            .collect())
+    }
+
+    async fn season_responses(db: &PgPool, seasons: Vec<Season>) -> AppResult<Vec<SeasonResponse>> {
+        let ids: Vec<Uuid> = seasons.iter().map(|s| s.id).collect();
+        let mut episodes = ContentRepository::get_episodes_for_seasons(db, &ids).await?;
+
+        Ok(seasons
+            .into_iter()
+            .map(|season| SeasonResponse {
+                episodes: episodes.remove(&season.id).unwrap_or_default(),
+                season,
+            })
+            .collect())
+    }
+
     // --- SEASONS & EPISODES ---
 
-    pub async fn create_season(state: AppState, req: CreateSeasonRequest) -> Result<SeasonResponse> {
+    pub async fn create_season(state: AppState, req: CreateSeasonRequest) -> AppResult<SeasonResponse> {
         // Verify series exists
         if ContentRepository::get_series_by_id(&state.db, req.series_id).await?.is_none() {
-            return Err(anyhow!("Series not found"));
+            return Err(AppError::not_found("Series not found"));
         }
 
         let season = ContentRepository::create_season(
@@ -166,10 +408,10 @@ impl ContentService {
         })
     }
 
-    pub async fn create_episode(state: AppState, req: CreateEpisodeRequest) -> Result<super::model::Episode> {
+    pub async fn create_episode(state: AppState, req: CreateEpisodeRequest) -> AppResult<super::model::Episode> {
         // Verify season exists
         if ContentRepository::get_season_by_id(&state.db, req.season_id).await?.is_none() {
-            return Err(anyhow!("Season not found"));
+            return Err(AppError::not_found("Season not found"));
         }
 
         let episode = ContentRepository::create_episode(
@@ -184,7 +426,7 @@ impl ContentService {
         Ok(episode)
     }
 
-    pub async fn update_episode(state: AppState, id: Uuid, req: UpdateEpisodeRequest) -> Result<super::model::Episode> {
+    pub async fn update_episode(state: AppState, id: Uuid, req: UpdateEpisodeRequest) -> AppResult<super::model::Episode> {
         let episode = ContentRepository::update_episode(
             &state.db,
             id,
@@ -196,8 +438,22 @@ impl ContentService {
         Ok(episode)
     }
 
-    pub async fn delete_episode(state: AppState, id: Uuid) -> Result<()> {
-        ContentRepository::delete_episode(&state.db, id).await
+    pub async fn delete_episode(state: AppState, id: Uuid) -> AppResult<()> {
+        let series_id = Self::episode_series_id(&state, id).await?;
+        ContentRepository::delete_episode(&state.db, id).await?;
+        if let Some(series_id) = series_id {
+            SearchService::refresh_suggestion(&state, CONTENT_SERIES, series_id).await;
+        }
+        Ok(())
+    }
+
+    /// Series the episode belongs to.
+    pub async fn episode_series_id(state: &AppState, episode_id: Uuid) -> AppResult<Option<Uuid>> {
+        let Some(episode) = ContentRepository::get_episode_by_id(&state.db, episode_id).await? else {
+            return Ok(None);
+        };
+        let season = ContentRepository::get_season_by_id(&state.db, episode.season_id).await?;
+        Ok(season.map(|s| s.series_id))
     }
 }
 
@@ -205,7 +461,7 @@ impl ContentService {
    // ... previous methods ...
 
     // --- MOVIE UPDATES ---
-    pub async fn initiate_movie_processing(state: AppState, id: Uuid, video_key: String) -> Result<()> {
+    pub async fn initiate_movie_processing(state: AppState, id: Uuid, video_key: String) -> AppResult<()> {
         let video_url = video_key.clone();
         
         // 1. Update DB to PROCESSING
@@ -224,7 +480,7 @@ impl ContentService {
         Ok(())
     }
 
-    pub async fn complete_movie_thumbnail_upload(state: AppState, id: Uuid, thumbnail_key: String) -> Result<()> {
+    pub async fn complete_movie_thumbnail_upload(state: AppState, id: Uuid, thumbnail_key: String) -> AppResult<()> {
         // Thumbnail URL handling
         let thumbnail_url = thumbnail_key;
         
@@ -239,17 +495,24 @@ impl ContentService {
         Ok(())
     }
 
-    pub async fn complete_series_thumbnail_upload(state: AppState, id: Uuid, thumbnail_key: String) -> Result<()> {
+    pub async fn complete_series_thumbnail_upload(state: AppState, id: Uuid, thumbnail_key: String) -> AppResult<()> {
         let thumbnail_url = thumbnail_key;
-        ContentRepository::update_series_thumbnail_url(&state.db, id, &thumbnail_url).await
+        Ok(ContentRepository::update_series_thumbnail_url(&state.db, id, &thumbnail_url).await?)
     }
-    pub async fn update_movie(state: AppState, id: Uuid, req: UpdateMovieRequest) -> Result<MovieResponse> {
+    pub async fn update_movie(state: AppState, id: Uuid, req: UpdateMovieRequest) -> AppResult<MovieResponse> {
+        let maturity_rating_id = match req.maturity_rating.as_deref() {
+            Some(code) => Some(RatingService::resolve_code(&state, code).await?.map(|r| r.id)),
+            None => None,
+        };
+
         let movie = ContentRepository::update_movie(
             &state.db,
             id,
             req.title,
             req.description,
             req.release_year,
+            maturity_rating_id,
+            req.content_advisories.map(Self::normalize_advisories),
         ).await?;
 
         if let Some(gids) = req.genre_ids {
@@ -258,29 +521,53 @@ impl ContentService {
                 ContentRepository::link_movie_genres(&state.db, id, &gids).await?;
             }
         }
+        SearchService::refresh_suggestion(&state, CONTENT_MOVIE, movie.id).await;
 
         let genres = ContentRepository::get_movie_genres(&state.db, movie.id).await?;
         let genre_dtos = genres.into_iter().map(GenreResponse::from).collect();
+        let maturity_rating = RatingService::get(&state, movie.maturity_rating_id).await?;
 
         Ok(MovieResponse {
             movie,
             genres: genre_dtos,
+            maturity_rating,
         })
     }
 
-    pub async fn delete_movie(state: AppState, id: Uuid) -> Result<()> {
-        ContentRepository::delete_movie(&state.db, id).await
+    pub async fn delete_movie(state: AppState, id: Uuid) -> AppResult<()> {
+        ContentRepository::delete_movie(&state.db, id).await?;
+        SearchService::refresh_suggestion(&state, CONTENT_MOVIE, id).await;
+        Ok(())
+    }
+
+    pub async fn update_movie_visibility(state: AppState, id: Uuid, req: UpdateVisibilityRequest) -> AppResult<Movie> {
+        Self::check_visibility(&req)?;
+        let movie = ContentRepository::update_movie_visibility(&state.db, id, &req.visibility, req.publish_at, req.unpublish_at)
+            .await
+            .map_err(|e| match AppError::from(e) {
+                AppError::NotFound(_) => AppError::not_found("Movie not found"),
+                other => other,
+            })?;
+        SearchService::refresh_suggestion(&state, CONTENT_MOVIE, movie.id).await;
+        Ok(movie)
     }
 
     // --- SERIES UPDATES ---
 
-    pub async fn update_series(state: AppState, id: Uuid, req: UpdateSeriesRequest) -> Result<SeriesResponse> {
+    pub async fn update_series(state: AppState, id: Uuid, req: UpdateSeriesRequest) -> AppResult<SeriesResponse> {
+        let maturity_rating_id = match req.maturity_rating.as_deref() {
+            Some(code) => Some(RatingService::resolve_code(&state, code).await?.map(|r| r.id)),
+            None => None,
+        };
+
         let series = ContentRepository::update_series(
             &state.db,
             id,
             req.title,
             req.description,
             req.release_year,
+            maturity_rating_id,
+            req.content_advisories.map(Self::normalize_advisories),
         ).await?;
 
         if let Some(gids) = req.genre_ids {
@@ -289,24 +576,55 @@ impl ContentService {
                 ContentRepository::link_series_genres(&state.db, id, &gids).await?;
             }
         }
+        SearchService::refresh_suggestion(&state, CONTENT_SERIES, series.id).await;
 
         let genres = ContentRepository::get_series_genres(&state.db, series.id).await?;
         let genre_dtos = genres.into_iter().map(GenreResponse::from).collect();
+        let maturity_rating = RatingService::get(&state, series.maturity_rating_id).await?;
 
         Ok(SeriesResponse {
             series,
             genres: genre_dtos,
+            maturity_rating,
             seasons: vec![], // TODO: fetch seasons if needed, or keeping lightweight for update
         })
     }
 
-    pub async fn delete_series(state: AppState, id: Uuid) -> Result<()> {
-        ContentRepository::delete_series(&state.db, id).await
+    pub async fn delete_series(state: AppState, id: Uuid) -> AppResult<()> {
+        ContentRepository::delete_series(&state.db, id).await?;
+        SearchService::refresh_suggestion(&state, CONTENT_SERIES, id).await;
+        Ok(())
+    }
+
+    pub async fn update_series_visibility(state: AppState, id: Uuid, req: UpdateVisibilityRequest) -> AppResult<Series> {
+        Self::check_visibility(&req)?;
+        let series = ContentRepository::update_series_visibility(&state.db, id, &req.visibility, req.publish_at, req.unpublish_at)
+            .await
+            .map_err(|e| match AppError::from(e) {
+                AppError::NotFound(_) => AppError::not_found("Series not found"),
+                other => other,
+            })?;
+        SearchService::refresh_suggestion(&state, CONTENT_SERIES, series.id).await;
+        Ok(series)
+    }
+
+    /// Applies the publishing schedules that are due. Returns how many titles changed.
+    pub async fn apply_visibility_schedules(state: &AppState) -> AppResult<usize> {
+        let movie_ids = ContentRepository::apply_movie_schedules(&state.db).await?;
+        let series_ids = ContentRepository::apply_series_schedules(&state.db).await?;
+
+        for id in &movie_ids {
+            SearchService::refresh_suggestion(state, CONTENT_MOVIE, *id).await;
+        }
+        for id in &series_ids {
+            SearchService::refresh_suggestion(state, CONTENT_SERIES, *id).await;
+        }
+        Ok(movie_ids.len() + series_ids.len())
     }
 
     // --- SEASON UPDATES ---
 
-    pub async fn update_season(state: AppState, id: Uuid, req: UpdateSeasonRequest) -> Result<SeasonResponse> {
+    pub async fn update_season(state: AppState, id: Uuid, req: UpdateSeasonRequest) -> AppResult<SeasonResponse> {
         let season = ContentRepository::update_season(
             &state.db,
             id,
@@ -323,13 +641,19 @@ impl ContentService {
         })
     }
 
-    pub async fn delete_season(state: AppState, id: Uuid) -> Result<()> {
-        ContentRepository::delete_season(&state.db, id).await
+    pub async fn delete_season(state: AppState, id: Uuid) -> AppResult<()> {
+        let season = ContentRepository::get_season_by_id(&state.db, id).await?;
+        ContentRepository::delete_season(&state.db, id).await?;
+        // The series may have lost its last playable episode
+        if let Some(season) = season {
+            SearchService::refresh_suggestion(&state, CONTENT_SERIES, season.series_id).await;
+        }
+        Ok(())
     }
     
     // --- EPISODE UPLOADS ---
     
-    pub async fn initiate_episode_processing(state: AppState, id: Uuid, video_key: String) -> Result<()> {
+    pub async fn initiate_episode_processing(state: AppState, id: Uuid, video_key: String) -> AppResult<()> {
         let video_url = video_key.clone();
         // 1. Update DB to PROCESSING
         ContentRepository::update_episode_video_url(&state.db, id, &video_url, "PROCESSING").await?;
@@ -347,8 +671,163 @@ impl ContentService {
         Ok(())
     }
     
-    pub async fn complete_episode_thumbnail_upload(state: AppState, id: Uuid, thumbnail_key: String) -> Result<()> {
+    pub async fn complete_episode_thumbnail_upload(state: AppState, id: Uuid, thumbnail_key: String) -> AppResult<()> {
         let thumbnail_url = thumbnail_key;
-        ContentRepository::update_episode_thumbnail_url(&state.db, id, &thumbnail_url).await
+        Ok(ContentRepository::update_episode_thumbnail_url(&state.db, id, &thumbnail_url).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::genre::repository::GenreRepository;
+    use std::future::Future;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+    use tracing_subscriber::Layer;
+
+    /// Counts the statements sqlx executes; it logs one `sqlx::query` event per statement.
+    #[derive(Clone, Default)]
+    struct QueryCounter(Arc<AtomicUsize>);
+
+    impl<S: tracing::Subscriber> Layer<S> for QueryCounter {
+        fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+            if event.metadata().target() == "sqlx::query" {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+    }
+
+    /// Awaits `f` and returns its output with the number of queries it ran.
+    /// `#[sqlx::test]` runs on a current-thread runtime, so the thread-local
+    /// subscriber sees every query of the future.
+    async fn count_queries<F: Future>(f: F) -> (F::Output, usize) {
+        let counter = QueryCounter::default();
+        let _guard = tracing::subscriber::set_default(tracing_subscriber::registry().with(counter.clone()));
+        let output = f.await;
+        (output, counter.0.load(Ordering::SeqCst))
+    }
+
+    async fn seed_genres(pool: &PgPool) -> Vec<Uuid> {
+        let mut ids = Vec::new();
+        for name in ["Query Count A", "Query Count B"] {
+            let genre = GenreRepository::create(pool, name, &name.to_lowercase().replace(' ', "-")).await.unwrap();
+            ids.push(genre.id);
+        }
+        ids
+    }
+
+    async fn seed_movies(pool: &PgPool, count: usize, genre_ids: &[Uuid]) -> Vec<Movie> {
+        let mut movies = Vec::new();
+        for i in 0..count {
+            let movie = ContentRepository::create_movie(pool, &format!("Movie {}", i), &format!("movie-{}", Uuid::new_v4()), None, None, None, None, &[])
+                .await
+                .unwrap();
+            ContentRepository::link_movie_genres(pool, movie.id, genre_ids).await.unwrap();
+            movies.push(movie);
+        }
+        movies
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn movie_responses_load_genres_in_one_query(pool: PgPool) {
+        let genre_ids = seed_genres(&pool).await;
+        let ratings = HashMap::new();
+
+        let movies = seed_movies(&pool, 1, &genre_ids).await;
+        let (_, single) = count_queries(ContentService::movie_responses(&pool, &movies, &ratings)).await;
+
+        let mut movies = seed_movies(&pool, 9, &genre_ids).await;
+        movies.extend(seed_movies(&pool, 1, &[]).await);
+        let (responses, many) = count_queries(ContentService::movie_responses(&pool, &movies, &ratings)).await;
+        let responses = responses.unwrap();
+
+        assert_eq!(single, 1);
+        assert_eq!(many, 1);
+        assert_eq!(responses.len(), 10);
+        for (response, movie) in responses.iter().zip(&movies) {
+            assert_eq!(response.movie.id, movie.id);
+        }
+        assert!(responses[..9].iter().all(|r| r.genres.len() == 2));
+        assert!(responses[9].genres.is_empty());
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn series_list_responses_load_genres_in_one_query(pool: PgPool) {
+        let genre_ids = seed_genres(&pool).await;
+        let mut series = Vec::new();
+        for i in 0..8 {
+            let s = ContentRepository::create_series(&pool, &format!("Series {}", i), &format!("series-{}", Uuid::new_v4()), None, None, None, &[])
+                .await
+                .unwrap();
+            ContentRepository::link_series_genres(&pool, s.id, &genre_ids[..1 + i % 2]).await.unwrap();
+            series.push(s);
+        }
+
+        let (responses, queries) = count_queries(ContentService::series_list_responses(&pool, &series, &HashMap::new())).await;
+        let responses = responses.unwrap();
+
+        assert_eq!(queries, 1);
+        for (i, response) in responses.iter().enumerate() {
+            assert_eq!(response.series.id, series[i].id);
+            assert_eq!(response.genres.len(), 1 + i % 2);
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn season_responses_load_episodes_in_one_query(pool: PgPool) {
+        let series = ContentRepository::create_series(&pool, "Series", "series", None, None, None, &[]).await.unwrap();
+        for season_number in 1..=4 {
+            let season = ContentRepository::create_season(&pool, series.id, season_number, None).await.unwrap();
+            // Created out of order to check the episodes come back sorted
+            for episode_number in [3, 1, 2] {
+                ContentRepository::create_episode(&pool, season.id, episode_number, None, None, None).await.unwrap();
+            }
+        }
+        let seasons = ContentRepository::get_series_seasons(&pool, series.id).await.unwrap();
+
+        let (responses, queries) = count_queries(ContentService::season_responses(&pool, seasons)).await;
+        let responses = responses.unwrap();
+
+        assert_eq!(queries, 1);
+        assert_eq!(responses.len(), 4);
+        for response in &responses {
+            let numbers: Vec<i32> = response.episodes.iter().map(|e| e.episode_number).collect();
+            assert_eq!(numbers, vec![1, 2, 3]);
+            assert!(response.episodes.iter().all(|e| e.season_id == response.season.id));
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn movie_cursor_survives_changes_to_its_row(pool: PgPool) {
+        let movies = seed_movies(&pool, 4, &[]).await;
+        for (movie, views) in movies.iter().zip([40, 30, 20, 10]) {
+            sqlx::query!("UPDATE movies SET views = $2 WHERE id = $1", movie.id, views).execute(&pool).await.unwrap();
+        }
+        let filter = ContentFilter {
+            max_maturity_level: None,
+            genre: None,
+            year_from: None,
+            year_to: None,
+            status: None,
+            min_rating: None,
+            visibility: None,
+        };
+        let ids = |page: Vec<Movie>| page.into_iter().map(|m| m.id).collect::<Vec<_>>();
+        let order = |after| ContentOrder { sort: "views", descending: true, after };
+
+        let first = ContentRepository::list_movies(&pool, &filter, &order(None), 2, 0).await.unwrap();
+        assert_eq!(ids(first), vec![movies[0].id, movies[1].id]);
+        let cursor = Some((movies[1].id, SortKey::Int(30)));
+
+        // The cursor's movie gets popular before the next page is read
+        sqlx::query!("UPDATE movies SET views = 1000 WHERE id = $1", movies[1].id).execute(&pool).await.unwrap();
+        let second = ContentRepository::list_movies(&pool, &filter, &order(cursor.clone()), 2, 0).await.unwrap();
+        assert_eq!(ids(second), vec![movies[2].id, movies[3].id]);
+
+        ContentRepository::delete_movie(&pool, movies[1].id).await.unwrap();
+        let second = ContentRepository::list_movies(&pool, &filter, &order(cursor), 2, 0).await.unwrap();
+        assert_eq!(ids(second), vec![movies[2].id, movies[3].id]);
     }
 }
@@ -4,6 +4,10 @@ use axum::{
     http::{header, HeaderMap, StatusCode},
     response::IntoResponse,
 };
+use crate::common::error::AppError;
+use crate::modules::content::model::VISIBILITY_PUBLISHED;
+use crate::modules::content::repository::ContentRepository;
+use crate::modules::profile::model::ViewerContext;
 use crate::state::AppState;
 use uuid::Uuid;
 use futures_util::TryStreamExt;
@@ -15,11 +19,13 @@ use std::io;
     get,
     path = "/api/v1/movies/{id}/stream",
     params(
-        ("id" = Uuid, Path, description = "Movie ID")
+        ("id" = Uuid, Path, description = "Movie ID"),
+        ("x-parental-pin" = Option<String>, Header, description = "Parental PIN lifting the profile's maturity limit")
     ),
     responses(
         (status = 200, description = "Stream Content"),
         (status = 206, description = "Partial Content"),
+        (status = 403, description = "Restricted by parental controls"),
         (status = 404, description = "Not Found"),
         (status = 500, description = "Internal Server Error")
     ),
@@ -27,22 +33,28 @@ use std::io;
 )]
 pub async fn stream_movie(
     State(state): State<AppState>,
+    viewer: ViewerContext,
     Path(id): Path<Uuid>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    // 1. Get movie to find video_url
-    let movie = match crate::modules::content::repository::ContentRepository::get_movie_by_id(&state.db, id).await {
-        Ok(Some(m)) => m,
-        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
-        Err(e) => {
-            tracing::error!("Database Error: {}", e);
-            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-        }
+    // Visibility first: a restricted but unpublished title must look like a missing one
+    let movie = match ContentRepository::get_movie_by_id(&state.db, id).await {
+        Ok(Some(m)) if m.is_published() || viewer.sees_unpublished => m,
+        Ok(_) => return AppError::not_found("Movie not found").into_response(),
+        Err(e) => return AppError::from(e).into_response(),
     };
-    
+
+    match ContentRepository::get_movie_maturity_level(&state.db, id).await {
+        Ok(level) if !viewer.allows(level) => {
+            return AppError::forbidden("This title is restricted by parental controls").into_response();
+        }
+        Ok(_) => {}
+        Err(e) => return AppError::from(e).into_response(),
+    }
+
     let video_key = match movie.video_url {
         Some(k) => k,
-        None => return StatusCode::NOT_FOUND.into_response(),
+        None => return AppError::not_found("Movie has no video").into_response(),
     };
 
     // 2. Parse Range header
@@ -66,7 +78,7 @@ pub async fn stream_movie(
         Err(e) => {
             tracing::error!("S3 Error: {}", e);
             // Handle specific S3 errors like 404
-             return StatusCode::NOT_FOUND.into_response(); 
+            return AppError::not_found("Video not found in storage").into_response();
         }
     };
     
@@ -111,11 +123,13 @@ pub async fn stream_movie(
     get,
     path = "/api/v1/episodes/{id}/stream",
     params(
-        ("id" = Uuid, Path, description = "Episode ID")
+        ("id" = Uuid, Path, description = "Episode ID"),
+        ("x-parental-pin" = Option<String>, Header, description = "Parental PIN lifting the profile's maturity limit")
     ),
     responses(
         (status = 200, description = "Stream Content"),
         (status = 206, description = "Partial Content"),
+        (status = 403, description = "Restricted by parental controls"),
         (status = 404, description = "Not Found"),
         (status = 500, description = "Internal Server Error")
     ),
@@ -123,21 +137,34 @@ pub async fn stream_movie(
 )]
 pub async fn stream_episode(
     State(state): State<AppState>,
+    viewer: ViewerContext,
     Path(id): Path<Uuid>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
+    // Episodes are published and rated through their series
+    match ContentRepository::get_episode_visibility(&state.db, id).await {
+        Ok(visibility) if viewer.sees_unpublished || visibility.as_deref() == Some(VISIBILITY_PUBLISHED) => {}
+        Ok(_) => return AppError::not_found("Episode not found").into_response(),
+        Err(e) => return AppError::from(e).into_response(),
+    }
+
+    match ContentRepository::get_episode_maturity_level(&state.db, id).await {
+        Ok(level) if !viewer.allows(level) => {
+            return AppError::forbidden("This title is restricted by parental controls").into_response();
+        }
+        Ok(_) => {}
+        Err(e) => return AppError::from(e).into_response(),
+    }
+
     let episode = match crate::modules::content::repository::ContentRepository::get_episode_by_id(&state.db, id).await {
         Ok(Some(e)) => e,
-        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
-        Err(e) => {
-            tracing::error!("Database Error: {}", e);
-            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-        }
+        Ok(None) => return AppError::not_found("Episode not found").into_response(),
+        Err(e) => return AppError::from(e).into_response(),
     };
 
     let video_key = match episode.video_url {
         Some(k) => k,
-        None => return StatusCode::NOT_FOUND.into_response(),
+        None => return AppError::not_found("Episode has no video").into_response(),
     };
 
     let range_header = headers.get(header::RANGE)
@@ -157,7 +184,7 @@ pub async fn stream_episode(
         Ok(r) => r,
         Err(e) => {
             tracing::error!("S3 Error: {}", e);
-            return StatusCode::NOT_FOUND.into_response();
+            return AppError::not_found("Video not found in storage").into_response();
         }
     };
 
@@ -1,6 +1,9 @@
 use super::dto::{CreateGenreRequest, GenreResponse, UpdateGenreRequest};
+use super::repository::GenreRepository;
 use super::service::GenreService;
-use crate::common::response::{ApiError, ApiResponse, ApiSuccess};
+use crate::modules::audit::model::{AuditContext, AuditEntry};
+use crate::modules::audit::service::AuditService;
+use crate::common::response::{ApiResponse, ApiSuccess};
 use crate::state::AppState;
 use axum::{
     extract::{Path, State},
@@ -26,7 +29,7 @@ pub async fn list_genres(State(state): State<AppState>) -> impl IntoResponse {
             StatusCode::OK,
         )
         .into_response(),
-        Err(e) => ApiError(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -46,16 +49,20 @@ pub async fn list_genres(State(state): State<AppState>) -> impl IntoResponse {
 )]
 pub async fn create_genre(
     State(state): State<AppState>,
+    audit: AuditContext,
     Json(payload): Json<CreateGenreRequest>,
 ) -> impl IntoResponse {
 
-    match GenreService::create(state, payload).await {
-        Ok(genre) => ApiSuccess(
-            ApiResponse::success(genre, "Genre created successfully"),
-            StatusCode::CREATED,
-        )
-        .into_response(),
-        Err(e) => ApiError(e.to_string(), StatusCode::BAD_REQUEST).into_response(),
+    match GenreService::create(state.clone(), payload).await {
+        Ok(genre) => {
+            AuditService::record(&state, &audit, AuditEntry::new("genre.create", "genre", Some(genre.id)).after(&genre)).await;
+            ApiSuccess(
+                ApiResponse::success(genre, "Genre created successfully"),
+                StatusCode::CREATED,
+            )
+            .into_response()
+        }
+        Err(e) => e.into_response(),
     }
 }
 
@@ -82,7 +89,7 @@ pub async fn get_genre(
             StatusCode::OK,
         )
         .into_response(),
-        Err(e) => ApiError(e.to_string(), StatusCode::NOT_FOUND).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -105,17 +112,23 @@ pub async fn get_genre(
 )]
 pub async fn update_genre(
     State(state): State<AppState>,
+    audit: AuditContext,
     Path(id): Path<Uuid>,
     Json(payload): Json<UpdateGenreRequest>,
 ) -> impl IntoResponse {
+    let before = GenreRepository::find_by_id(&state.db, id).await.ok().flatten();
 
-    match GenreService::update(state, id, payload).await {
-        Ok(genre) => ApiSuccess(
-            ApiResponse::success(genre, "Genre updated successfully"),
-            StatusCode::OK,
-        )
-        .into_response(),
-        Err(e) => ApiError(e.to_string(), StatusCode::BAD_REQUEST).into_response(),
+    match GenreService::update(state.clone(), id, payload).await {
+        Ok(genre) => {
+            let entry = AuditEntry::new("genre.update", "genre", Some(id)).before(&before).after(&genre);
+            AuditService::record(&state, &audit, entry).await;
+            ApiSuccess(
+                ApiResponse::success(genre, "Genre updated successfully"),
+                StatusCode::OK,
+            )
+            .into_response()
+        }
+        Err(e) => e.into_response(),
     }
 }
 
@@ -136,16 +149,21 @@ pub async fn update_genre(
 )]
 pub async fn delete_genre(
     State(state): State<AppState>,
+    audit: AuditContext,
     Path(id): Path<Uuid>,
 ) -> impl IntoResponse {
+    let before = GenreRepository::find_by_id(&state.db, id).await.ok().flatten();
 
-    match GenreService::delete(state, id).await {
-        Ok(_) => ApiSuccess(
-            ApiResponse::success((), "Genre deleted successfully"),
-            StatusCode::OK,
-        )
-        .into_response(),
-        Err(e) => ApiError(e.to_string(), StatusCode::NOT_FOUND).into_response(),
+    match GenreService::delete(state.clone(), id).await {
+        Ok(_) => {
+            AuditService::record(&state, &audit, AuditEntry::new("genre.delete", "genre", Some(id)).before(&before)).await;
+            ApiSuccess(
+                ApiResponse::success((), "Genre deleted successfully"),
+                StatusCode::OK,
+            )
+            .into_response()
+        }
+        Err(e) => e.into_response(),
     }
 }
 
@@ -1,6 +1,8 @@
 use axum::Router;
 use axum::routing::{get, post};
 use crate::state::AppState;
+use crate::modules::api_key::model::SCOPE_GENRES_WRITE;
+use crate::modules::role::model::GENRE_MANAGE;
 use axum::middleware;
 
 pub mod dto;
@@ -17,7 +19,12 @@ pub fn router(state: AppState) -> axum::Router<AppState> {
     let protected_routes = Router::new()
         .route("/", post(handler::create_genre))
         .route("/{id}",  axum::routing::put(handler::update_genre).delete(handler::delete_genre))
-        .route_layer(middleware::from_fn(crate::middleware::role::admin_guard))
+        .route_layer(middleware::from_fn(|req, next| {
+            crate::middleware::auth::require_scope(SCOPE_GENRES_WRITE, req, next)
+        }))
+        .route_layer(middleware::from_fn(|req, next| {
+            crate::middleware::role::require_permission(GENRE_MANAGE, req, next)
+        }))
         .route_layer(middleware::from_fn_with_state(
             state,
             crate::middleware::auth::auth_middleware
@@ -1,5 +1,6 @@
 use super::model::Genre;
-use anyhow::{anyhow, Result};
+use crate::common::error::AppError;
+use anyhow::{Context, Result};
 use sqlx::PgPool;
 use uuid::Uuid;
 
@@ -19,7 +20,7 @@ impl GenreRepository {
         )
         .fetch_one(pool)
         .await
-        .map_err(|e| anyhow!("Failed to create genre: {}", e))?;
+        .context("Failed to create genre")?;
 
         Ok(genre)
     }
@@ -35,7 +36,7 @@ impl GenreRepository {
         )
         .fetch_all(pool)
         .await
-        .map_err(|e| anyhow!("Failed to fetch genres: {}", e))?;
+        .context("Failed to fetch genres")?;
 
         Ok(genres)
     }
@@ -52,7 +53,7 @@ impl GenreRepository {
         )
         .fetch_optional(pool)
         .await
-        .map_err(|e| anyhow!("Failed to fetch genre: {}", e))?;
+        .context("Failed to fetch genre")?;
 
         Ok(genre)
     }
@@ -67,7 +68,7 @@ impl GenreRepository {
         let _current = sqlx::query!("SELECT id FROM genres WHERE id = $1", id)
             .fetch_optional(&mut *tx)
             .await?
-            .ok_or_else(|| anyhow!("Genre not found"))?;
+            .ok_or_else(|| AppError::not_found("Genre not found"))?;
 
         let genre = sqlx::query_as!(
             Genre,
@@ -86,7 +87,7 @@ impl GenreRepository {
         )
         .fetch_one(&mut *tx)
         .await
-        .map_err(|e| anyhow!("Failed to update genre: {}", e))?;
+        .context("Failed to update genre")?;
 
         tx.commit().await?;
         Ok(genre)
@@ -98,7 +99,7 @@ impl GenreRepository {
             .await?;
 
         if result.rows_affected() == 0 {
-            return Err(anyhow!("Genre not found"));
+            return Err(AppError::not_found("Genre not found").into());
         }
 
         Ok(())
@@ -1,14 +1,17 @@
 use super::dto::{CreateGenreRequest, GenreResponse, UpdateGenreRequest};
 use super::repository::GenreRepository;
 use crate::state::AppState;
-use anyhow::Result;
+use crate::common::error::{AppError, AppResult};
+use crate::modules::search::model::CONTENT_GENRE;
+use crate::modules::search::service::SearchService;
 use uuid::Uuid;
 
 pub struct GenreService;
 
 impl GenreService {
-    pub async fn create(state: AppState, req: CreateGenreRequest) -> Result<GenreResponse> {
+    pub async fn create(state: AppState, req: CreateGenreRequest) -> AppResult<GenreResponse> {
         let genre = GenreRepository::create(&state.db, &req.name, &req.slug).await?;
+        SearchService::refresh_suggestion(&state, CONTENT_GENRE, genre.id).await;
         
         Ok(GenreResponse {
             id: genre.id,
@@ -17,7 +20,7 @@ impl GenreService {
         })
     }
 
-    pub async fn find_all(state: AppState) -> Result<Vec<GenreResponse>> {
+    pub async fn find_all(state: AppState) -> AppResult<Vec<GenreResponse>> {
         let genres = GenreRepository::find_all(&state.db).await?;
         
         Ok(genres
@@ -30,10 +33,10 @@ impl GenreService {
             .collect())
     }
 
-    pub async fn find_by_id(state: AppState, id: Uuid) -> Result<GenreResponse> {
+    pub async fn find_by_id(state: AppState, id: Uuid) -> AppResult<GenreResponse> {
         let genre = GenreRepository::find_by_id(&state.db, id)
             .await?
-            .ok_or_else(|| anyhow::anyhow!("Genre not found"))?;
+            .ok_or_else(|| AppError::not_found("Genre not found"))?;
             
         Ok(GenreResponse {
             id: genre.id,
@@ -42,8 +45,9 @@ impl GenreService {
         })
     }
 
-    pub async fn update(state: AppState, id: Uuid, req: UpdateGenreRequest) -> Result<GenreResponse> {
+    pub async fn update(state: AppState, id: Uuid, req: UpdateGenreRequest) -> AppResult<GenreResponse> {
         let genre = GenreRepository::update(&state.db, id, req.name, req.slug).await?;
+        SearchService::refresh_suggestion(&state, CONTENT_GENRE, genre.id).await;
         
         Ok(GenreResponse {
             id: genre.id,
@@ -52,8 +56,9 @@ impl GenreService {
         })
     }
 
-    pub async fn delete(state: AppState, id: Uuid) -> Result<()> {
+    pub async fn delete(state: AppState, id: Uuid) -> AppResult<()> {
         GenreRepository::delete(&state.db, id).await?;
+        SearchService::refresh_suggestion(&state, CONTENT_GENRE, id).await;
         Ok(())
     }
 }
@@ -0,0 +1,51 @@
+use super::model::AccountJob;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct DeleteAccountRequest {
+    /// Current password, to confirm the deletion
+    #[validate(length(min = 1, message = "Password is required"))]
+    pub password: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountJobResponse {
+    pub id: Uuid,
+    /// EXPORT or DELETION
+    pub kind: String,
+    /// PENDING, RUNNING, COMPLETED or FAILED
+    pub status: String,
+    pub error: Option<String>,
+    /// Where to download the archive once the export is completed
+    pub download_url: Option<String>,
+    #[serde(with = "time::serde::iso8601")]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::iso8601::option")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub completed_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::iso8601::option")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub expires_at: Option<OffsetDateTime>,
+}
+
+impl From<AccountJob> for AccountJobResponse {
+    fn from(job: AccountJob) -> Self {
+        Self {
+            download_url: job
+                .is_downloadable()
+                .then(|| format!("/api/v1/users/me/export/{}/download", job.id)),
+            id: job.id,
+            kind: job.kind,
+            status: job.status,
+            error: job.error,
+            created_at: job.created_at,
+            completed_at: job.completed_at,
+            expires_at: job.expires_at,
+        }
+    }
+}
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Message on `ACCOUNT_TASKS_QUEUE`; the job row holds the details.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AccountTask {
+    pub job_id: Uuid,
+}
@@ -0,0 +1,143 @@
+use super::dto::{AccountJobResponse, DeleteAccountRequest};
+use super::service::JobService;
+use crate::common::error::AppError;
+use crate::common::response::{ApiResponse, ApiSuccess};
+use crate::modules::audit::model::{AuditContext, AuditEntry};
+use crate::modules::audit::service::AuditService;
+use crate::modules::auth::dto::TokenClaims;
+use crate::state::AppState;
+use axum::{
+    extract::{Extension, Path, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Request an export of all data stored about the current user
+#[utoipa::path(
+    post,
+    path = "/api/v1/users/me/export",
+    responses(
+        (status = 202, description = "Export queued; poll the job until it is completed", body = ApiResponse<AccountJobResponse>),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Account Data"
+)]
+pub async fn request_export(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    audit: AuditContext,
+) -> impl IntoResponse {
+    match JobService::request_export(state.clone(), claims.sub).await {
+        Ok(job) => {
+            AuditService::record(&state, &audit, AuditEntry::new("user.export_request", "user", Some(claims.sub))
+                .after(&serde_json::json!({ "job_id": job.id }))).await;
+            ApiSuccess(ApiResponse::success(AccountJobResponse::from(job), "Export queued"), StatusCode::ACCEPTED).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Get the status of a data export or account deletion job
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me/export/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Job ID")
+    ),
+    responses(
+        (status = 200, description = "Job status", body = ApiResponse<AccountJobResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Job not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Account Data"
+)]
+pub async fn get_export(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match JobService::get_job(state, claims.sub, id).await {
+        Ok(job) => ApiSuccess(ApiResponse::success(AccountJobResponse::from(job), "Job retrieved"), StatusCode::OK).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Download a completed data export (gzipped JSON)
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me/export/{id}/download",
+    params(
+        ("id" = Uuid, Path, description = "Job ID")
+    ),
+    responses(
+        (status = 200, description = "Export archive", body = Vec<u8>, content_type = "application/gzip"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Job not found"),
+        (status = 409, description = "Export not completed yet or expired")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Account Data"
+)]
+pub async fn download_export(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match JobService::download_export(state, claims.sub, id).await {
+        Ok((filename, bytes)) => (
+            [
+                (header::CONTENT_TYPE, "application/gzip".to_string()),
+                (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", filename)),
+            ],
+            bytes,
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Delete the current user's account; all sessions end immediately and the data is removed in the background
+#[utoipa::path(
+    delete,
+    path = "/api/v1/users/me",
+    request_body = DeleteAccountRequest,
+    responses(
+        (status = 202, description = "Deletion queued, all sessions revoked", body = ApiResponse<AccountJobResponse>),
+        (status = 400, description = "Password is incorrect"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Account Data"
+)]
+pub async fn delete_account(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    audit: AuditContext,
+    Json(payload): Json<DeleteAccountRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = payload.validate() {
+        return AppError::from(e).into_response();
+    }
+
+    match JobService::request_deletion(state.clone(), claims.sub, payload).await {
+        Ok(job) => {
+            AuditService::record(&state, &audit, AuditEntry::new("user.delete_request", "user", Some(claims.sub))
+                .after(&serde_json::json!({ "job_id": job.id }))).await;
+            ApiSuccess(ApiResponse::success(AccountJobResponse::from(job), "Account deletion queued"), StatusCode::ACCEPTED).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
@@ -1 +1,8 @@
-// jobs module
+// Background jobs for personal data: exports and account deletion.
+// Routes are mounted by the user module under /api/v1/users/me.
+pub mod dto;
+pub mod events;
+pub mod handler;
+pub mod model;
+pub mod repository;
+pub mod service;
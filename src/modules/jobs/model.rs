@@ -0,0 +1,75 @@
+use crate::modules::api_key::model::ApiKey;
+use crate::modules::auth::dto::{SessionResponse, UserResponse};
+use crate::modules::auth::model::{LoginLockoutEvent, UserIdentity};
+use crate::modules::profile::dto::ProfileResponse;
+use crate::modules::user::model::UserPreferences;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// RabbitMQ queue consumed by `workers::account`.
+pub const ACCOUNT_TASKS_QUEUE: &str = "account_tasks";
+
+pub const JOB_EXPORT: &str = "EXPORT";
+pub const JOB_DELETION: &str = "DELETION";
+
+pub const STATUS_PENDING: &str = "PENDING";
+pub const STATUS_RUNNING: &str = "RUNNING";
+pub const STATUS_COMPLETED: &str = "COMPLETED";
+pub const STATUS_FAILED: &str = "FAILED";
+
+/// A data export or account deletion requested by a user, run by the account worker.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct AccountJob {
+    pub id: Uuid,
+    /// Cleared once the account is deleted
+    pub user_id: Option<Uuid>,
+    /// EXPORT or DELETION
+    pub kind: String,
+    /// PENDING, RUNNING, COMPLETED or FAILED
+    pub status: String,
+    /// Object key of the export archive in the exports bucket
+    pub archive_key: Option<String>,
+    pub error: Option<String>,
+    #[serde(with = "time::serde::iso8601")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::iso8601::option")]
+    pub started_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::iso8601::option")]
+    pub completed_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::iso8601::option")]
+    pub expires_at: Option<OffsetDateTime>,
+}
+
+impl AccountJob {
+    /// A completed export whose archive has not expired yet.
+    pub fn is_downloadable(&self) -> bool {
+        self.kind == JOB_EXPORT
+            && self.status == STATUS_COMPLETED
+            && self.archive_key.is_some()
+            && self.expires_at.is_none_or(|at| at > OffsetDateTime::now_utc())
+    }
+}
+
+/// A viewer profile with its playback preferences, as written to the export.
+#[derive(Debug, Serialize)]
+pub struct ExportedProfile {
+    #[serde(flatten)]
+    pub profile: ProfileResponse,
+    pub preferences: UserPreferences,
+}
+
+/// Everything stored about an account, serialized as the export archive.
+/// Watch history, ratings and lists are added here as those modules start storing data.
+#[derive(Debug, Serialize)]
+pub struct AccountExport {
+    #[serde(with = "time::serde::iso8601")]
+    pub generated_at: OffsetDateTime,
+    pub account: UserResponse,
+    pub profiles: Vec<ExportedProfile>,
+    pub sessions: Vec<SessionResponse>,
+    pub api_keys: Vec<ApiKey>,
+    pub linked_identities: Vec<UserIdentity>,
+    pub login_lockouts: Vec<LoginLockoutEvent>,
+}
@@ -0,0 +1,138 @@
+use super::model::{AccountJob, STATUS_COMPLETED, STATUS_FAILED, STATUS_PENDING, STATUS_RUNNING};
+use anyhow::Result;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+pub struct JobRepository;
+
+impl JobRepository {
+    pub async fn create(pool: &PgPool, user_id: Uuid, kind: &str) -> Result<AccountJob> {
+        let job = sqlx::query_as!(
+            AccountJob,
+            r#"
+            INSERT INTO account_jobs (user_id, kind)
+            VALUES ($1, $2)
+            RETURNING id, user_id, kind, status, archive_key, error, created_at, started_at, completed_at, expires_at
+            "#,
+            user_id,
+            kind
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    pub async fn find(pool: &PgPool, id: Uuid) -> Result<Option<AccountJob>> {
+        let job = sqlx::query_as!(
+            AccountJob,
+            r#"
+            SELECT id, user_id, kind, status, archive_key, error, created_at, started_at, completed_at, expires_at
+            FROM account_jobs
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    pub async fn find_for_user(pool: &PgPool, user_id: Uuid, id: Uuid) -> Result<Option<AccountJob>> {
+        let job = sqlx::query_as!(
+            AccountJob,
+            r#"
+            SELECT id, user_id, kind, status, archive_key, error, created_at, started_at, completed_at, expires_at
+            FROM account_jobs
+            WHERE id = $1 AND user_id = $2
+            "#,
+            id,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    /// A job of this kind that is still waiting or running, if any.
+    pub async fn find_active(pool: &PgPool, user_id: Uuid, kind: &str) -> Result<Option<AccountJob>> {
+        let job = sqlx::query_as!(
+            AccountJob,
+            r#"
+            SELECT id, user_id, kind, status, archive_key, error, created_at, started_at, completed_at, expires_at
+            FROM account_jobs
+            WHERE user_id = $1 AND kind = $2 AND status IN ($3, $4)
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#,
+            user_id,
+            kind,
+            STATUS_PENDING,
+            STATUS_RUNNING
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    /// Object keys of every export archive of the user, removed with the account.
+    pub async fn list_archive_keys(pool: &PgPool, user_id: Uuid) -> Result<Vec<String>> {
+        let rows = sqlx::query!(
+            "SELECT archive_key FROM account_jobs WHERE user_id = $1 AND archive_key IS NOT NULL",
+            user_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows.into_iter().filter_map(|r| r.archive_key).collect())
+    }
+
+    pub async fn mark_running(pool: &PgPool, id: Uuid) -> Result<()> {
+        sqlx::query!(
+            "UPDATE account_jobs SET status = $2, started_at = NOW(), error = NULL WHERE id = $1",
+            id,
+            STATUS_RUNNING
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn mark_completed(
+        pool: &PgPool,
+        id: Uuid,
+        archive_key: Option<&str>,
+        expires_at: Option<OffsetDateTime>,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE account_jobs
+            SET status = $2, archive_key = $3, expires_at = $4, completed_at = NOW()
+            WHERE id = $1
+            "#,
+            id,
+            STATUS_COMPLETED,
+            archive_key,
+            expires_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn mark_failed(pool: &PgPool, id: Uuid, error: &str) -> Result<()> {
+        sqlx::query!(
+            "UPDATE account_jobs SET status = $2, error = $3, completed_at = NOW() WHERE id = $1",
+            id,
+            STATUS_FAILED,
+            error
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,250 @@
+use super::dto::DeleteAccountRequest;
+use super::events::AccountTask;
+use super::model::{
+    AccountExport, AccountJob, ExportedProfile, ACCOUNT_TASKS_QUEUE, JOB_DELETION, JOB_EXPORT, STATUS_COMPLETED,
+    STATUS_FAILED,
+};
+use super::repository::JobRepository;
+use crate::infrastructure::mail::sender::MailMessage;
+use crate::infrastructure::storage::s3::StorageService;
+use crate::modules::api_key::repository::ApiKeyRepository;
+use crate::modules::audit::repository::AuditRepository;
+use crate::modules::auth::dto::UserResponse;
+use crate::modules::auth::model::User;
+use crate::modules::auth::repository::AuthRepository;
+use crate::modules::auth::service::AuthService;
+use crate::modules::profile::dto::ProfileResponse;
+use crate::modules::profile::repository::ProfileRepository;
+use crate::modules::user::repository::UserRepository;
+use crate::state::AppState;
+use crate::common::error::{AppError, AppResult};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
+
+pub struct JobService;
+
+impl JobService {
+    /// Queues a personal data export. A request while one is still pending returns that job.
+    pub async fn request_export(state: AppState, user_id: Uuid) -> AppResult<AccountJob> {
+        if let Some(job) = JobRepository::find_active(&state.db, user_id, JOB_EXPORT).await? {
+            return Ok(job);
+        }
+
+        let job = JobRepository::create(&state.db, user_id, JOB_EXPORT).await?;
+        Self::enqueue(&state, &job).await?;
+        tracing::info!("User {} requested a data export ({})", user_id, job.id);
+        Ok(job)
+    }
+
+    /// Queues the deletion of the account after checking the password. Sessions are
+    /// revoked right away so the user is logged out before the worker picks the job up.
+    pub async fn request_deletion(state: AppState, user_id: Uuid, req: DeleteAccountRequest) -> AppResult<AccountJob> {
+        let user = AuthRepository::find_user_by_id(&state.db, user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("User not found"))?;
+
+        if !state.passwords.verify(&req.password, &user.password_hash)? {
+            return Err(AppError::validation("Password is incorrect"));
+        }
+
+        if let Some(job) = JobRepository::find_active(&state.db, user.id, JOB_DELETION).await? {
+            return Ok(job);
+        }
+
+        let job = JobRepository::create(&state.db, user.id, JOB_DELETION).await?;
+        Self::enqueue(&state, &job).await?;
+
+        AuthService::revoke_all_sessions(state, user.id).await?;
+        tracing::info!("User {} requested deletion of their account ({})", user.id, job.id);
+        Ok(job)
+    }
+
+    pub async fn get_job(state: AppState, user_id: Uuid, job_id: Uuid) -> AppResult<AccountJob> {
+        JobRepository::find_for_user(&state.db, user_id, job_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("Job not found"))
+    }
+
+    /// File name and gzipped JSON of a completed export.
+    pub async fn download_export(state: AppState, user_id: Uuid, job_id: Uuid) -> AppResult<(String, Vec<u8>)> {
+        let job = Self::get_job(state.clone(), user_id, job_id).await?;
+        if job.kind != JOB_EXPORT {
+            return Err(AppError::not_found("Job not found"));
+        }
+        if !job.is_downloadable() {
+            return Err(AppError::conflict("Export is not available for download"));
+        }
+
+        let key = job.archive_key.unwrap_or_default();
+        let bytes = Self::export_storage(&state)
+            .get_object(&key)
+            .await
+            .map_err(|e| AppError::internal(format!("Failed to fetch export: {}", e)))?;
+
+        Ok((format!("hiuramovie-export-{}.json.gz", job.id), bytes))
+    }
+
+    /// Runs a job taken from the queue. Finished jobs are skipped, so a redelivered
+    /// message does nothing; a job left RUNNING by a crashed worker runs again.
+    pub async fn process(state: &AppState, task: &AccountTask) -> AppResult<()> {
+        let job = JobRepository::find(&state.db, task.job_id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("Job {} not found", task.job_id)))?;
+
+        if job.status == STATUS_COMPLETED || job.status == STATUS_FAILED {
+            return Ok(());
+        }
+        let Some(user_id) = job.user_id else {
+            JobRepository::mark_failed(&state.db, job.id, "Account no longer exists").await?;
+            return Ok(());
+        };
+
+        JobRepository::mark_running(&state.db, job.id).await?;
+
+        let result = match job.kind.as_str() {
+            JOB_EXPORT => Self::run_export(state, &job, user_id).await,
+            JOB_DELETION => Self::run_deletion(state, &job, user_id).await,
+            other => Err(AppError::internal(format!("Unknown job kind '{}'", other))),
+        };
+
+        if let Err(e) = &result {
+            JobRepository::mark_failed(&state.db, job.id, &e.to_string()).await?;
+        }
+        result
+    }
+
+    async fn run_export(state: &AppState, job: &AccountJob, user_id: Uuid) -> AppResult<()> {
+        let user = AuthRepository::find_user_by_id(&state.db, user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("User not found"))?;
+
+        let archive = Self::build_archive(state, &user).await?;
+        let key = format!("exports/{}/{}.json.gz", user.id, job.id);
+
+        let storage = Self::export_storage(state);
+        storage
+            .client
+            .put_object()
+            .bucket(&storage.bucket)
+            .key(&key)
+            .body(aws_sdk_s3::primitives::ByteStream::from(archive))
+            .content_type("application/gzip")
+            .send()
+            .await
+            .map_err(|e| AppError::internal(format!("Failed to upload export: {}", e)))?;
+
+        let expires_at = OffsetDateTime::now_utc() + Duration::hours(state.config.data_export_ttl_hours);
+        JobRepository::mark_completed(&state.db, job.id, Some(&key), Some(expires_at)).await?;
+
+        let notice = MailMessage {
+            to: user.email.clone(),
+            subject: "Your HiuraMovie data export is ready".to_string(),
+            body: format!(
+                "Hi {},\n\nThe export of your account data is ready. Download it within {} hours:\n{}/api/v1/users/me/export/{}/download",
+                user.full_name, state.config.data_export_ttl_hours, state.config.app_url, job.id
+            ),
+        };
+        if let Err(e) = state.mailer.send(&notice).await {
+            tracing::error!("Failed to notify user {} about export {}: {}", user.id, job.id, e);
+        }
+
+        tracing::info!("Data export {} of user {} stored as {}", job.id, user.id, key);
+        Ok(())
+    }
+
+    async fn run_deletion(state: &AppState, job: &AccountJob, user_id: Uuid) -> AppResult<()> {
+        let user = AuthRepository::find_user_by_id(&state.db, user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("User not found"))?;
+
+        Self::erase_account(state, &user).await?;
+        JobRepository::mark_completed(&state.db, job.id, None, None).await?;
+
+        let notice = MailMessage {
+            to: user.email.clone(),
+            subject: "Your HiuraMovie account was deleted".to_string(),
+            body: format!(
+                "Hi {},\n\nYour account and the data stored with it were deleted as you requested.",
+                user.full_name
+            ),
+        };
+        if let Err(e) = state.mailer.send(&notice).await {
+            tracing::error!("Failed to confirm deletion to {}: {}", user.email, e);
+        }
+
+        Ok(())
+    }
+
+    /// Collects the account data and returns it as gzipped JSON.
+    async fn build_archive(state: &AppState, user: &User) -> AppResult<Vec<u8>> {
+        let mut profiles = Vec::new();
+        for profile in ProfileRepository::list(&state.db, user.id).await? {
+            let preferences = UserRepository::get_preferences(&state.db, user.id, profile.id).await?;
+            profiles.push(ExportedProfile {
+                profile: ProfileResponse::from(profile),
+                preferences,
+            });
+        }
+
+        let export = AccountExport {
+            generated_at: OffsetDateTime::now_utc(),
+            account: UserResponse::from(user.clone()),
+            profiles,
+            sessions: AuthService::list_sessions(state.clone(), user.id, Uuid::nil()).await?,
+            api_keys: ApiKeyRepository::list_by_user(&state.db, user.id).await?,
+            linked_identities: AuthRepository::list_identities(&state.db, user.id).await?,
+            login_lockouts: AuthRepository::list_lockout_events(&state.db, Some(&user.email), 1000, 0).await?,
+        };
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        serde_json::to_writer_pretty(&mut encoder, &export)?;
+        Ok(encoder.finish()?)
+    }
+
+    /// Removes an account: revokes its sessions, deletes stored files, anonymizes the
+    /// login history, redacts the user's audit log entries and deletes the user row, which
+    /// cascades to profiles, preferences, API keys, identities and tokens. The audit entries
+    /// themselves are kept, without the recorded rows and IP addresses.
+    pub async fn erase_account(state: &AppState, user: &User) -> AppResult<()> {
+        AuthService::revoke_all_sessions(state.clone(), user.id).await?;
+
+        let exports = Self::export_storage(state);
+        for key in JobRepository::list_archive_keys(&state.db, user.id).await? {
+            if let Err(e) = exports.client.delete_object().bucket(&exports.bucket).key(&key).send().await {
+                tracing::warn!("Failed to delete export '{}' of user {}: {}", key, user.id, e);
+            }
+        }
+
+        if let Some(avatar_key) = &user.avatar_url {
+            let bucket = &state.config.minio_bucket_thumbnails;
+            if let Err(e) = state.storage.client.delete_object().bucket(bucket).key(avatar_key).send().await {
+                tracing::warn!("Failed to delete avatar '{}' of user {}: {}", avatar_key, user.id, e);
+            }
+        }
+
+        AuthRepository::anonymize_lockout_events(&state.db, user.id, &user.email).await?;
+        AuditRepository::redact_user(&state.db, user.id).await?;
+        if !UserRepository::delete(&state.db, user.id).await? {
+            return Err(AppError::not_found("User not found"));
+        }
+
+        let mut redis_conn = state.redis.get_conn().await?;
+        UserRepository::clear_suspended(&mut redis_conn, user.id).await?;
+
+        tracing::info!("Erased account {}", user.id);
+        Ok(())
+    }
+
+    async fn enqueue(state: &AppState, job: &AccountJob) -> AppResult<()> {
+        let payload = serde_json::to_vec(&AccountTask { job_id: job.id })?;
+        Ok(state.queue.publish(ACCOUNT_TASKS_QUEUE, &payload).await?)
+    }
+
+    fn export_storage(state: &AppState) -> StorageService {
+        let mut storage = state.storage.clone();
+        storage.bucket = state.config.minio_bucket_exports.clone();
+        storage
+    }
+}
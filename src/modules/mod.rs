@@ -1,8 +1,14 @@
 pub mod auth;
 pub mod user;
+pub mod profile;
+pub mod api_key;
+pub mod role;
+pub mod audit;
 pub mod catalog;
 pub mod playback;
 pub mod progress;
 pub mod jobs;
 pub mod genre;
+pub mod rating;
 pub mod content;
+pub mod search;
@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use super::model::Profile;
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateViewerProfileRequest {
+    #[validate(length(min = 1, max = 50, message = "Name must be 1-50 characters"))]
+    pub name: String,
+    #[validate(length(max = 100, message = "Avatar must be at most 100 characters"))]
+    pub avatar: Option<String>,
+    #[serde(default)]
+    pub is_kids: bool,
+    /// 4-6 digits required to switch to this profile
+    pub pin: Option<String>,
+    /// Highest maturity level shown; kids profiles default to `KIDS_MAX_MATURITY_LEVEL`
+    #[validate(range(min = 0, max = 100, message = "Maturity level must be between 0 and 100"))]
+    pub max_maturity_level: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateViewerProfileRequest {
+    #[validate(length(min = 1, max = 50, message = "Name must be 1-50 characters"))]
+    pub name: Option<String>,
+    #[validate(length(max = 100, message = "Avatar must be at most 100 characters"))]
+    pub avatar: Option<String>,
+    pub is_kids: Option<bool>,
+    /// New 4-6 digit PIN
+    pub pin: Option<String>,
+    /// Removes the PIN; ignored when `pin` is set
+    #[serde(default)]
+    pub remove_pin: bool,
+    #[validate(range(min = 0, max = 100, message = "Maturity level must be between 0 and 100"))]
+    pub max_maturity_level: Option<i32>,
+    /// Lifts the maturity limit; ignored when `max_maturity_level` is set
+    #[serde(default)]
+    pub remove_max_maturity_level: bool,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SelectProfileRequest {
+    /// Required when the profile has a PIN
+    pub pin: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProfileResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub avatar: Option<String>,
+    pub is_kids: bool,
+    pub has_pin: bool,
+    pub max_maturity_level: Option<i32>,
+    pub is_default: bool,
+    #[serde(with = "time::serde::iso8601")]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: OffsetDateTime,
+}
+
+impl From<Profile> for ProfileResponse {
+    fn from(profile: Profile) -> Self {
+        Self {
+            id: profile.id,
+            name: profile.name,
+            avatar: profile.avatar,
+            is_kids: profile.is_kids,
+            has_pin: profile.pin_hash.is_some(),
+            max_maturity_level: profile.max_maturity_level,
+            is_default: profile.is_default,
+            created_at: profile.created_at,
+        }
+    }
+}
+
+/// Access token for the session with the newly selected profile.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SelectProfileResponse {
+    pub access_token: String,
+    pub access_token_expires_in: u64,
+    pub profile: ProfileResponse,
+}
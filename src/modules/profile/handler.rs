@@ -0,0 +1,216 @@
+use super::dto::{CreateViewerProfileRequest, ProfileResponse, SelectProfileRequest, SelectProfileResponse, UpdateViewerProfileRequest};
+use super::repository::ProfileRepository;
+use super::service::ProfileService;
+use crate::common::error::AppError;
+use crate::common::response::{ApiResponse, ApiSuccess};
+use crate::modules::audit::model::{AuditContext, AuditEntry};
+use crate::modules::audit::service::AuditService;
+use crate::modules::auth::dto::TokenClaims;
+use crate::state::AppState;
+use axum::{
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+/// List the viewer profiles of the account
+#[utoipa::path(
+    get,
+    path = "/api/v1/profiles",
+    responses(
+        (status = 200, description = "Profiles, default first", body = ApiResponse<Vec<ProfileResponse>>),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Profiles"
+)]
+pub async fn list_profiles(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+) -> impl IntoResponse {
+    match ProfileService::list(state, claims.sub).await {
+        Ok(profiles) => ApiSuccess(ApiResponse::success(profiles, "Profiles retrieved"), StatusCode::OK).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Get the profile active in this session
+#[utoipa::path(
+    get,
+    path = "/api/v1/profiles/current",
+    responses(
+        (status = 200, description = "Active profile", body = ApiResponse<ProfileResponse>),
+        (status = 400, description = "The selected profile was deleted"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Profiles"
+)]
+pub async fn get_active_profile(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+) -> impl IntoResponse {
+    match ProfileService::current(state, claims.sub, claims.profile_id).await {
+        Ok(profile) => ApiSuccess(ApiResponse::success(profile, "Active profile retrieved"), StatusCode::OK).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Add a viewer profile to the account
+#[utoipa::path(
+    post,
+    path = "/api/v1/profiles",
+    request_body = CreateViewerProfileRequest,
+    responses(
+        (status = 201, description = "Profile created", body = ApiResponse<ProfileResponse>),
+        (status = 400, description = "Invalid input or profile limit reached"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not allowed from a kids or restricted profile"),
+        (status = 409, description = "Name already used on this account")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Profiles"
+)]
+pub async fn create_profile(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    audit: AuditContext,
+    Json(payload): Json<CreateViewerProfileRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = payload.validate() {
+        return AppError::from(e).into_response();
+    }
+
+    match ProfileService::create(state.clone(), claims.sub, claims.profile_id, payload).await {
+        Ok(profile) => {
+            let entry = AuditEntry::new("profile.create", "profile", Some(profile.id)).after(&profile);
+            AuditService::record(&state, &audit, entry).await;
+            ApiSuccess(ApiResponse::success(profile, "Profile created"), StatusCode::CREATED).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Update name, avatar, kids flag, PIN or maturity limit of a profile
+#[utoipa::path(
+    patch,
+    path = "/api/v1/profiles/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Profile ID")
+    ),
+    request_body = UpdateViewerProfileRequest,
+    responses(
+        (status = 200, description = "Profile updated", body = ApiResponse<ProfileResponse>),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not allowed from a kids or restricted profile"),
+        (status = 404, description = "Profile not found"),
+        (status = 409, description = "Name already used on this account")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Profiles"
+)]
+pub async fn edit_profile(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    audit: AuditContext,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateViewerProfileRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = payload.validate() {
+        return AppError::from(e).into_response();
+    }
+
+    let before = ProfileRepository::find(&state.db, claims.sub, id)
+        .await
+        .ok()
+        .flatten()
+        .map(ProfileResponse::from);
+
+    match ProfileService::update(state.clone(), claims.sub, claims.profile_id, id, payload).await {
+        Ok(profile) => {
+            let entry = AuditEntry::new("profile.update", "profile", Some(id)).before(&before).after(&profile);
+            AuditService::record(&state, &audit, entry).await;
+            ApiSuccess(ApiResponse::success(profile, "Profile updated"), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Delete a profile and its viewing data
+#[utoipa::path(
+    delete,
+    path = "/api/v1/profiles/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Profile ID")
+    ),
+    responses(
+        (status = 200, description = "Profile deleted"),
+        (status = 400, description = "The default profile cannot be deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not allowed from a kids or restricted profile"),
+        (status = 404, description = "Profile not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Profiles"
+)]
+pub async fn delete_profile(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    audit: AuditContext,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match ProfileService::delete(state.clone(), claims.sub, claims.profile_id, id).await {
+        Ok(profile) => {
+            let entry = AuditEntry::new("profile.delete", "profile", Some(id)).before(&profile);
+            AuditService::record(&state, &audit, entry).await;
+            ApiSuccess(ApiResponse::success((), "Profile deleted"), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Switch this session to a profile; returns an access token scoped to it
+#[utoipa::path(
+    post,
+    path = "/api/v1/profiles/{id}/select",
+    params(
+        ("id" = Uuid, Path, description = "Profile ID")
+    ),
+    request_body = SelectProfileRequest,
+    responses(
+        (status = 200, description = "Profile selected", body = ApiResponse<SelectProfileResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing or invalid PIN"),
+        (status = 404, description = "Profile not found"),
+        (status = 429, description = "Too many wrong PINs")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Profiles"
+)]
+pub async fn select_profile(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<SelectProfileRequest>,
+) -> impl IntoResponse {
+    match ProfileService::select(state, claims.sub, claims.sid, id, payload).await {
+        Ok(response) => ApiSuccess(ApiResponse::success(response, "Profile selected"), StatusCode::OK).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
@@ -0,0 +1,24 @@
+use axum::Router;
+use axum::routing::{get, patch, post};
+use crate::state::AppState;
+use axum::middleware;
+
+pub mod dto;
+pub mod handler;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+pub fn router(state: AppState) -> axum::Router<AppState> {
+    // Profiles are chosen per device session, so API keys cannot use them
+    Router::new()
+        .route("/", get(handler::list_profiles).post(handler::create_profile))
+        .route("/current", get(handler::get_active_profile))
+        .route("/{id}", patch(handler::edit_profile).delete(handler::delete_profile))
+        .route("/{id}/select", post(handler::select_profile))
+        .route_layer(middleware::from_fn(crate::middleware::auth::session_only))
+        .route_layer(middleware::from_fn_with_state(
+            state,
+            crate::middleware::auth::auth_middleware
+        ))
+}
@@ -0,0 +1,135 @@
+use super::service::ProfileService;
+use crate::common::error::AppError;
+use crate::modules::api_key::model::SCOPE_CONTENT_WRITE;
+use crate::modules::audit::model::AuditContext;
+use crate::modules::auth::dto::TokenClaims;
+use crate::modules::rating::model::is_allowed;
+use crate::modules::role::model::CONTENT_WRITE;
+use crate::state::AppState;
+use axum::{
+    extract::{FromRequestParts, OriginalUri},
+    http::{header, request::Parts},
+};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Parental PIN that lifts the maturity limit of the active profile for one request.
+pub const PARENTAL_PIN_HEADER: &str = "x-parental-pin";
+
+/// A viewer under an account. Watch data and preferences are kept per profile,
+/// so members of a household sharing one login do not mix them up.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct Profile {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    /// Id of a built-in avatar picked by the client
+    pub avatar: Option<String>,
+    pub is_kids: bool,
+    #[serde(skip_serializing)]
+    pub pin_hash: Option<String>,
+    /// Highest maturity level (minimum viewer age) of titles shown; `None` is unrestricted
+    pub max_maturity_level: Option<i32>,
+    /// Created with the account and used when no profile is selected; cannot be deleted
+    pub is_default: bool,
+    #[serde(with = "time::serde::iso8601")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::iso8601")]
+    pub updated_at: OffsetDateTime,
+}
+
+/// Maturity limit of the viewer making a request. Anonymous requests get
+/// `ANONYMOUS_MAX_MATURITY_LEVEL`, so a restricted profile gains nothing by
+/// dropping its credentials; authenticated ones get the limit of the active profile.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ViewerContext {
+    pub max_maturity_level: Option<i32>,
+    /// Catalog staff (`content.write`) also see unpublished titles
+    pub sees_unpublished: bool,
+}
+
+impl ViewerContext {
+    /// Whether a title with this maturity level may be listed or streamed.
+    pub fn allows(&self, title_level: Option<i32>) -> bool {
+        is_allowed(title_level, self.max_maturity_level)
+    }
+}
+
+impl FromRequestParts<AppState> for ViewerContext {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let has_credentials =
+            parts.headers.contains_key(header::AUTHORIZATION) || parts.headers.contains_key("x-api-key");
+
+        // Routes behind an auth layer already carry claims; public routes authenticate optionally
+        let claims = match parts.extensions.get::<TokenClaims>() {
+            Some(claims) => claims.clone(),
+            None if has_credentials => {
+                let claims = crate::middleware::auth::authenticate(state, &parts.headers).await?;
+                // Public routes skip auth_middleware, so impersonated reads are checked and audited here
+                if claims.act.is_some() {
+                    if !crate::middleware::auth::is_read_only(&parts.method) {
+                        return Err(AppError::forbidden("Impersonation sessions are read-only"));
+                    }
+                    parts.extensions.insert(claims.clone());
+                    let Ok(audit) = AuditContext::from_request_parts(parts, state).await;
+                    let uri = parts.extensions.get::<OriginalUri>().map_or_else(|| parts.uri.clone(), |u| u.0.clone());
+                    crate::middleware::auth::record_impersonated_request(state, &audit, &claims, &parts.method, &uri, None)
+                        .await;
+                }
+                claims
+            }
+            None => {
+                return Ok(Self {
+                    max_maturity_level: state.config.anonymous_max_maturity_level,
+                    sees_unpublished: false,
+                })
+            }
+        };
+
+        let pin = parts.headers.get(PARENTAL_PIN_HEADER).and_then(|v| v.to_str().ok());
+        let max_maturity_level = ProfileService::maturity_limit(state, claims.sub, claims.profile_id, pin)
+            .await?;
+
+        // Same checks as the content write routes; an impersonating admin sees what the user sees
+        let sees_unpublished = claims.act.is_none()
+            && !claims.mfa_required
+            && claims.permissions.iter().any(|p| p == CONTENT_WRITE)
+            && claims.api_key.as_ref().is_none_or(|k| k.scopes.iter().any(|s| s == SCOPE_CONTENT_WRITE));
+
+        Ok(Self { max_maturity_level, sees_unpublished })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn viewer(max_maturity_level: Option<i32>) -> ViewerContext {
+        ViewerContext { max_maturity_level, sees_unpublished: false }
+    }
+
+    #[test]
+    fn unrestricted_viewer_sees_every_title() {
+        let viewer = viewer(None);
+        assert!(viewer.allows(None));
+        assert!(viewer.allows(Some(0)));
+        assert!(viewer.allows(Some(18)));
+    }
+
+    #[test]
+    fn limited_viewer_sees_titles_up_to_the_limit() {
+        let kids = viewer(Some(7));
+        assert!(kids.allows(Some(0)));
+        assert!(kids.allows(Some(7)));
+        assert!(!kids.allows(Some(13)));
+    }
+
+    #[test]
+    fn limited_viewer_does_not_see_unrated_titles() {
+        assert!(!viewer(Some(18)).allows(None));
+    }
+}
@@ -0,0 +1,197 @@
+use super::model::Profile;
+use anyhow::Result;
+use redis::aio::MultiplexedConnection;
+use redis::AsyncCommands;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub struct ProfileRepository;
+
+impl ProfileRepository {
+    pub async fn list(pool: &PgPool, user_id: Uuid) -> Result<Vec<Profile>> {
+        let profiles = sqlx::query_as!(
+            Profile,
+            r#"
+            SELECT id, user_id, name, avatar, is_kids, pin_hash, max_maturity_level, is_default, created_at, updated_at
+            FROM profiles
+            WHERE user_id = $1
+            ORDER BY is_default DESC, created_at ASC
+            "#,
+            user_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(profiles)
+    }
+
+    /// Looks up a profile of the given account; other accounts' profiles are not found.
+    pub async fn find(pool: &PgPool, user_id: Uuid, profile_id: Uuid) -> Result<Option<Profile>> {
+        let profile = sqlx::query_as!(
+            Profile,
+            r#"
+            SELECT id, user_id, name, avatar, is_kids, pin_hash, max_maturity_level, is_default, created_at, updated_at
+            FROM profiles
+            WHERE id = $1 AND user_id = $2
+            "#,
+            profile_id,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(profile)
+    }
+
+    pub async fn find_default(pool: &PgPool, user_id: Uuid) -> Result<Option<Profile>> {
+        let profile = sqlx::query_as!(
+            Profile,
+            r#"
+            SELECT id, user_id, name, avatar, is_kids, pin_hash, max_maturity_level, is_default, created_at, updated_at
+            FROM profiles
+            WHERE user_id = $1 AND is_default
+            "#,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(profile)
+    }
+
+    /// Creates the default profile unless the account already has one.
+    pub async fn ensure_default(pool: &PgPool, user_id: Uuid, name: &str) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO profiles (user_id, name, is_default)
+            VALUES ($1, $2, TRUE)
+            ON CONFLICT DO NOTHING
+            "#,
+            user_id,
+            name
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn count(pool: &PgPool, user_id: Uuid) -> Result<i64> {
+        let row = sqlx::query!(
+            r#"SELECT COUNT(*) as "count!" FROM profiles WHERE user_id = $1"#,
+            user_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(row.count)
+    }
+
+    pub async fn name_taken(pool: &PgPool, user_id: Uuid, name: &str, exclude_id: Option<Uuid>) -> Result<bool> {
+        let row = sqlx::query!(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM profiles
+                WHERE user_id = $1 AND LOWER(name) = LOWER($2) AND ($3::UUID IS NULL OR id <> $3)
+            ) as "exists!"
+            "#,
+            user_id,
+            name,
+            exclude_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(row.exists)
+    }
+
+    pub async fn create(
+        pool: &PgPool,
+        user_id: Uuid,
+        name: &str,
+        avatar: Option<&str>,
+        is_kids: bool,
+        pin_hash: Option<&str>,
+        max_maturity_level: Option<i32>,
+    ) -> Result<Profile> {
+        let profile = sqlx::query_as!(
+            Profile,
+            r#"
+            INSERT INTO profiles (user_id, name, avatar, is_kids, pin_hash, max_maturity_level)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id, user_id, name, avatar, is_kids, pin_hash, max_maturity_level, is_default, created_at, updated_at
+            "#,
+            user_id,
+            name,
+            avatar,
+            is_kids,
+            pin_hash,
+            max_maturity_level
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(profile)
+    }
+
+    pub async fn update(pool: &PgPool, profile: &Profile) -> Result<Profile> {
+        let profile = sqlx::query_as!(
+            Profile,
+            r#"
+            UPDATE profiles
+            SET name = $1, avatar = $2, is_kids = $3, pin_hash = $4, max_maturity_level = $5, updated_at = NOW()
+            WHERE id = $6 AND user_id = $7
+            RETURNING id, user_id, name, avatar, is_kids, pin_hash, max_maturity_level, is_default, created_at, updated_at
+            "#,
+            profile.name,
+            profile.avatar,
+            profile.is_kids,
+            profile.pin_hash,
+            profile.max_maturity_level,
+            profile.id,
+            profile.user_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(profile)
+    }
+
+    pub async fn delete(pool: &PgPool, user_id: Uuid, profile_id: Uuid) -> Result<bool> {
+        let result = sqlx::query!(
+            "DELETE FROM profiles WHERE id = $1 AND user_id = $2 AND NOT is_default",
+            profile_id,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // --- PIN THROTTLING ---
+
+    /// Counts a wrong PIN within the window and returns the failures so far.
+    pub async fn increment_pin_failures(
+        redis: &mut MultiplexedConnection,
+        profile_id: Uuid,
+        window_seconds: u64,
+    ) -> Result<u64> {
+        let key = format!("profile_pin_failures:{}", profile_id);
+        let failures: u64 = redis.incr(&key, 1).await?;
+        if failures == 1 {
+            let _: () = redis.expire(&key, window_seconds as i64).await?;
+        }
+        Ok(failures)
+    }
+
+    pub async fn pin_failures(redis: &mut MultiplexedConnection, profile_id: Uuid) -> Result<u64> {
+        let failures: Option<u64> = redis.get(format!("profile_pin_failures:{}", profile_id)).await?;
+        Ok(failures.unwrap_or(0))
+    }
+
+    pub async fn clear_pin_failures(redis: &mut MultiplexedConnection, profile_id: Uuid) -> Result<()> {
+        let _: () = redis.del(format!("profile_pin_failures:{}", profile_id)).await?;
+        Ok(())
+    }
+}
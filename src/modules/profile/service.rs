@@ -0,0 +1,268 @@
+use super::dto::{CreateViewerProfileRequest, ProfileResponse, SelectProfileRequest, SelectProfileResponse, UpdateViewerProfileRequest};
+use super::model::Profile;
+use super::repository::ProfileRepository;
+use crate::modules::auth::repository::AuthRepository;
+use crate::modules::auth::service::{AuthService, ACCESS_TOKEN_TTL_SECS};
+use crate::state::AppState;
+use crate::common::error::{AppError, AppResult};
+use redis::aio::MultiplexedConnection;
+use uuid::Uuid;
+
+/// Wrong PINs allowed per profile before switching to it is blocked for the window.
+const PIN_MAX_FAILURES: u64 = 5;
+const PIN_FAILURE_WINDOW_SECS: u64 = 15 * 60;
+
+pub struct ProfileService;
+
+impl ProfileService {
+    /// The profile per-viewer data is scoped to: the one selected for the
+    /// session, or the account's default profile when none was selected.
+    pub async fn resolve(state: &AppState, user_id: Uuid, profile_id: Option<Uuid>) -> AppResult<Profile> {
+        match profile_id {
+            Some(id) => ProfileRepository::find(&state.db, user_id, id)
+                .await?
+                .ok_or_else(|| AppError::not_found("Selected profile no longer exists, please select a profile")),
+            None => Self::default_profile(state, user_id).await,
+        }
+    }
+
+    /// Default profile of an account, created on first use for accounts that
+    /// were registered after the profiles migration.
+    async fn default_profile(state: &AppState, user_id: Uuid) -> AppResult<Profile> {
+        if let Some(profile) = ProfileRepository::find_default(&state.db, user_id).await? {
+            return Ok(profile);
+        }
+
+        let user = AuthRepository::find_user_by_id(&state.db, user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("User not found"))?;
+        let name: String = user.username.chars().take(50).collect();
+        ProfileRepository::ensure_default(&state.db, user_id, &name).await?;
+
+        ProfileRepository::find_default(&state.db, user_id)
+            .await?
+            .ok_or_else(|| AppError::internal("Failed to create default profile"))
+    }
+
+    pub async fn list(state: AppState, user_id: Uuid) -> AppResult<Vec<ProfileResponse>> {
+        Self::default_profile(&state, user_id).await?;
+        let profiles = ProfileRepository::list(&state.db, user_id).await?;
+        Ok(profiles.into_iter().map(ProfileResponse::from).collect())
+    }
+
+    pub async fn current(state: AppState, user_id: Uuid, profile_id: Option<Uuid>) -> AppResult<ProfileResponse> {
+        Ok(ProfileResponse::from(Self::resolve(&state, user_id, profile_id).await?))
+    }
+
+    pub async fn create(
+        state: AppState,
+        user_id: Uuid,
+        active_profile_id: Option<Uuid>,
+        req: CreateViewerProfileRequest,
+    ) -> AppResult<ProfileResponse> {
+        Self::ensure_can_manage(&state, user_id, active_profile_id).await?;
+
+        let count = ProfileRepository::count(&state.db, user_id).await?;
+        if count >= state.config.max_profiles_per_account {
+            return Err(AppError::conflict(format!(
+                "An account can have at most {} profiles",
+                state.config.max_profiles_per_account
+            )));
+        }
+
+        let name = req.name.trim();
+        if ProfileRepository::name_taken(&state.db, user_id, name, None).await? {
+            return Err(AppError::conflict(format!("A profile named '{}' already exists", name)));
+        }
+
+        let pin_hash = req.pin.as_deref().map(|pin| Self::hash_pin(&state, pin)).transpose()?;
+        let max_maturity_level = req
+            .max_maturity_level
+            .or(req.is_kids.then_some(state.config.kids_max_maturity_level));
+        let profile = ProfileRepository::create(
+            &state.db,
+            user_id,
+            name,
+            req.avatar.as_deref(),
+            req.is_kids,
+            pin_hash.as_deref(),
+            max_maturity_level,
+        )
+        .await?;
+
+        Ok(ProfileResponse::from(profile))
+    }
+
+    pub async fn update(
+        state: AppState,
+        user_id: Uuid,
+        active_profile_id: Option<Uuid>,
+        profile_id: Uuid,
+        req: UpdateViewerProfileRequest,
+    ) -> AppResult<ProfileResponse> {
+        Self::ensure_can_manage(&state, user_id, active_profile_id).await?;
+
+        let mut profile = ProfileRepository::find(&state.db, user_id, profile_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("Profile not found"))?;
+
+        if let Some(name) = req.name.as_deref().map(str::trim) {
+            if ProfileRepository::name_taken(&state.db, user_id, name, Some(profile.id)).await? {
+                return Err(AppError::conflict(format!("A profile named '{}' already exists", name)));
+            }
+            profile.name = name.to_string();
+        }
+        if let Some(avatar) = req.avatar {
+            profile.avatar = Some(avatar).filter(|a| !a.is_empty());
+        }
+        if let Some(is_kids) = req.is_kids {
+            // The default profile manages the others, so it cannot be restricted
+            if is_kids && profile.is_default {
+                return Err(AppError::validation("The default profile cannot be a kids profile"));
+            }
+            if is_kids && profile.max_maturity_level.is_none() {
+                profile.max_maturity_level = Some(state.config.kids_max_maturity_level);
+            }
+            profile.is_kids = is_kids;
+        }
+        if let Some(pin) = req.pin.as_deref() {
+            profile.pin_hash = Some(Self::hash_pin(&state, pin)?);
+        } else if req.remove_pin {
+            profile.pin_hash = None;
+        }
+        if let Some(level) = req.max_maturity_level {
+            if profile.is_default {
+                return Err(AppError::validation("The default profile cannot have a maturity limit"));
+            }
+            profile.max_maturity_level = Some(level);
+        } else if req.remove_max_maturity_level {
+            profile.max_maturity_level = None;
+        }
+
+        let profile = ProfileRepository::update(&state.db, &profile).await?;
+        Ok(ProfileResponse::from(profile))
+    }
+
+    /// Deletes a profile along with its per-viewer data. Returns the deleted profile.
+    pub async fn delete(
+        state: AppState,
+        user_id: Uuid,
+        active_profile_id: Option<Uuid>,
+        profile_id: Uuid,
+    ) -> AppResult<ProfileResponse> {
+        Self::ensure_can_manage(&state, user_id, active_profile_id).await?;
+
+        let profile = ProfileRepository::find(&state.db, user_id, profile_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("Profile not found"))?;
+        if profile.is_default {
+            return Err(AppError::forbidden("The default profile cannot be deleted"));
+        }
+
+        ProfileRepository::delete(&state.db, user_id, profile.id).await?;
+        Ok(ProfileResponse::from(profile))
+    }
+
+    /// Switches the session to a profile and issues an access token carrying it.
+    /// Refreshed tokens keep the profile until another one is selected.
+    pub async fn select(
+        state: AppState,
+        user_id: Uuid,
+        session_id: Uuid,
+        profile_id: Uuid,
+        req: SelectProfileRequest,
+    ) -> AppResult<SelectProfileResponse> {
+        let profile = ProfileRepository::find(&state.db, user_id, profile_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("Profile not found"))?;
+
+        let mut redis_conn = state.redis.get_conn().await?;
+
+        if profile.pin_hash.is_some() {
+            let pin = req.pin.as_deref().ok_or_else(|| AppError::forbidden("PIN required"))?;
+            Self::check_pin(&state, &mut redis_conn, &profile, pin).await?;
+        }
+
+        let mut session = AuthRepository::get_session(&mut redis_conn, session_id)
+            .await?
+            .filter(|s| s.user_id == user_id)
+            .ok_or_else(|| AppError::not_found("Session not found"))?;
+        session.profile_id = Some(profile.id);
+        AuthRepository::update_session(&mut redis_conn, &session).await?;
+
+        let user = AuthRepository::find_user_by_id(&state.db, user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("User not found"))?;
+        let access_token = AuthService::create_access_token(&state, &user, &session).await?;
+
+        Ok(SelectProfileResponse {
+            access_token,
+            access_token_expires_in: ACCESS_TOKEN_TTL_SECS,
+            profile: ProfileResponse::from(profile),
+        })
+    }
+
+    /// Maturity limit of the viewer. A correct parental PIN, the PIN of the
+    /// account's default profile, lifts the limit for the request.
+    pub async fn maturity_limit(
+        state: &AppState,
+        user_id: Uuid,
+        profile_id: Option<Uuid>,
+        parental_pin: Option<&str>,
+    ) -> AppResult<Option<i32>> {
+        let profile = Self::resolve(state, user_id, profile_id).await?;
+        let (Some(limit), Some(pin)) = (profile.max_maturity_level, parental_pin) else {
+            return Ok(profile.max_maturity_level);
+        };
+
+        let parent = Self::default_profile(state, user_id).await?;
+        if parent.pin_hash.is_none() {
+            return Err(AppError::forbidden("No parental PIN is set on this account"));
+        }
+
+        let mut redis_conn = state.redis.get_conn().await?;
+        Self::check_pin(state, &mut redis_conn, &parent, pin).await?;
+        tracing::info!("Parental PIN override of maturity limit {} on profile {}", limit, profile.id);
+        Ok(None)
+    }
+
+    /// Verifies a profile's PIN, blocking further attempts after too many wrong ones.
+    async fn check_pin(
+        state: &AppState,
+        redis: &mut MultiplexedConnection,
+        profile: &Profile,
+        pin: &str,
+    ) -> AppResult<()> {
+        let Some(pin_hash) = &profile.pin_hash else {
+            return Ok(());
+        };
+
+        if ProfileRepository::pin_failures(redis, profile.id).await? >= PIN_MAX_FAILURES {
+            return Err(AppError::rate_limited("Too many wrong PINs, try again later", None));
+        }
+
+        if !state.passwords.verify(pin, pin_hash)? {
+            ProfileRepository::increment_pin_failures(redis, profile.id, PIN_FAILURE_WINDOW_SECS).await?;
+            return Err(AppError::forbidden("Invalid PIN"));
+        }
+        ProfileRepository::clear_pin_failures(redis, profile.id).await?;
+        Ok(())
+    }
+
+    /// Kids profiles and profiles with a maturity limit may watch but not add,
+    /// change or remove profiles (which would let them lift their own limit).
+    async fn ensure_can_manage(state: &AppState, user_id: Uuid, active_profile_id: Option<Uuid>) -> AppResult<()> {
+        let profile = Self::resolve(state, user_id, active_profile_id).await?;
+        if profile.is_kids || profile.max_maturity_level.is_some() {
+            return Err(AppError::forbidden("Restricted profiles cannot manage profiles"));
+        }
+        Ok(())
+    }
+
+    fn hash_pin(state: &AppState, pin: &str) -> AppResult<String> {
+        if !(4..=6).contains(&pin.len()) || !pin.chars().all(|c| c.is_ascii_digit()) {
+            return Err(AppError::validation("PIN must be 4-6 digits"));
+        }
+        Ok(state.passwords.hash(pin)?)
+    }
+}
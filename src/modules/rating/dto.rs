@@ -0,0 +1,23 @@
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct MaturityRatingQuery {
+    /// Only ratings of this system, e.g. `MPAA`
+    pub system: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateMaturityRatingRequest {
+    /// Existing or new rating system
+    #[validate(length(min = 1, max = 20, message = "System must be 1-20 characters"))]
+    pub system: String,
+    #[validate(length(min = 1, max = 20, message = "Code must be 1-20 characters"))]
+    pub code: String,
+    /// Minimum viewer age
+    #[validate(range(min = 0, max = 100, message = "Level must be between 0 and 100"))]
+    pub level: i32,
+    #[validate(length(max = 255, message = "Description must be at most 255 characters"))]
+    pub description: Option<String>,
+}
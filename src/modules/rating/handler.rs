@@ -0,0 +1,71 @@
+use super::dto::{CreateMaturityRatingRequest, MaturityRatingQuery};
+use super::model::MaturityRating;
+use super::service::RatingService;
+use crate::common::error::AppError;
+use crate::common::response::{ApiResponse, ApiSuccess};
+use crate::modules::audit::model::{AuditContext, AuditEntry};
+use crate::modules::audit::service::AuditService;
+use crate::state::AppState;
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use validator::Validate;
+
+/// List maturity ratings, grouped by system and ordered by level
+#[utoipa::path(
+    get,
+    path = "/api/v1/maturity-ratings",
+    params(MaturityRatingQuery),
+    responses(
+        (status = 200, description = "Maturity ratings", body = ApiResponse<Vec<MaturityRating>>)
+    ),
+    tag = "Maturity Ratings"
+)]
+pub async fn list_maturity_ratings(
+    State(state): State<AppState>,
+    Query(query): Query<MaturityRatingQuery>,
+) -> impl IntoResponse {
+    match RatingService::list(state, query).await {
+        Ok(ratings) => ApiSuccess(ApiResponse::success(ratings, "Maturity ratings retrieved"), StatusCode::OK).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Add a rating to an existing or new rating system
+#[utoipa::path(
+    post,
+    path = "/api/v1/maturity-ratings",
+    request_body = CreateMaturityRatingRequest,
+    responses(
+        (status = 201, description = "Rating created", body = ApiResponse<MaturityRating>),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing permission content.write"),
+        (status = 409, description = "Rating already exists")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Maturity Ratings"
+)]
+pub async fn create_maturity_rating(
+    State(state): State<AppState>,
+    audit: AuditContext,
+    Json(payload): Json<CreateMaturityRatingRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = payload.validate() {
+        return AppError::from(e).into_response();
+    }
+
+    match RatingService::create(state.clone(), payload).await {
+        Ok(rating) => {
+            let entry = AuditEntry::new("maturity_rating.create", "maturity_rating", Some(rating.id)).after(&rating);
+            AuditService::record(&state, &audit, entry).await;
+            ApiSuccess(ApiResponse::success(rating, "Rating created"), StatusCode::CREATED).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
@@ -0,0 +1,32 @@
+use axum::Router;
+use axum::routing::{get, post};
+use crate::state::AppState;
+use crate::modules::api_key::model::SCOPE_CONTENT_WRITE;
+use crate::modules::role::model::CONTENT_WRITE;
+use axum::middleware;
+
+pub mod dto;
+pub mod handler;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+pub fn router(state: AppState) -> axum::Router<AppState> {
+    let public_routes = Router::new()
+        .route("/", get(handler::list_maturity_ratings));
+
+    let protected_routes = Router::new()
+        .route("/", post(handler::create_maturity_rating))
+        .route_layer(middleware::from_fn(|req, next| {
+            crate::middleware::role::require_permission(CONTENT_WRITE, req, next)
+        }))
+        .route_layer(middleware::from_fn(|req, next| {
+            crate::middleware::auth::require_scope(SCOPE_CONTENT_WRITE, req, next)
+        }))
+        .route_layer(middleware::from_fn_with_state(
+            state,
+            crate::middleware::auth::auth_middleware
+        ));
+
+    public_routes.merge(protected_routes)
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A certification within a rating system, e.g. MPAA `PG-13`.
+#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema, Clone)]
+pub struct MaturityRating {
+    pub id: Uuid,
+    pub system: String,
+    pub code: String,
+    /// Minimum viewer age; ratings of different systems compare on this scale
+    pub level: i32,
+    pub description: Option<String>,
+    #[serde(with = "time::serde::iso8601")]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: OffsetDateTime,
+}
+
+/// Whether a title rated `title_level` may be shown to a viewer limited to `max_level`.
+/// Unrated titles are only shown to unrestricted viewers.
+pub fn is_allowed(title_level: Option<i32>, max_level: Option<i32>) -> bool {
+    match max_level {
+        None => true,
+        Some(max) => title_level.is_some_and(|level| level <= max),
+    }
+}
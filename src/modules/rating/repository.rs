@@ -0,0 +1,79 @@
+use super::model::MaturityRating;
+use anyhow::Result;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub struct RatingRepository;
+
+impl RatingRepository {
+    pub async fn list(pool: &PgPool, system: Option<&str>) -> Result<Vec<MaturityRating>> {
+        let ratings = sqlx::query_as!(
+            MaturityRating,
+            r#"
+            SELECT id, system, code, level, description, created_at
+            FROM maturity_ratings
+            WHERE ($1::VARCHAR IS NULL OR system = $1)
+            ORDER BY system ASC, level ASC
+            "#,
+            system
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(ratings)
+    }
+
+    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<MaturityRating>> {
+        let rating = sqlx::query_as!(
+            MaturityRating,
+            "SELECT id, system, code, level, description, created_at FROM maturity_ratings WHERE id = $1",
+            id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(rating)
+    }
+
+    pub async fn find_by_code(pool: &PgPool, system: &str, code: &str) -> Result<Option<MaturityRating>> {
+        let rating = sqlx::query_as!(
+            MaturityRating,
+            r#"
+            SELECT id, system, code, level, description, created_at
+            FROM maturity_ratings
+            WHERE system = $1 AND UPPER(code) = UPPER($2)
+            "#,
+            system,
+            code
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(rating)
+    }
+
+    pub async fn create(
+        pool: &PgPool,
+        system: &str,
+        code: &str,
+        level: i32,
+        description: Option<&str>,
+    ) -> Result<MaturityRating> {
+        let rating = sqlx::query_as!(
+            MaturityRating,
+            r#"
+            INSERT INTO maturity_ratings (system, code, level, description)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, system, code, level, description, created_at
+            "#,
+            system,
+            code,
+            level,
+            description
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(rating)
+    }
+}
@@ -0,0 +1,55 @@
+use super::dto::{CreateMaturityRatingRequest, MaturityRatingQuery};
+use super::model::MaturityRating;
+use super::repository::RatingRepository;
+use crate::state::AppState;
+use crate::common::error::{AppError, AppResult};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub struct RatingService;
+
+impl RatingService {
+    pub async fn list(state: AppState, query: MaturityRatingQuery) -> AppResult<Vec<MaturityRating>> {
+        let system = query.system.map(|s| s.trim().to_uppercase());
+        Ok(RatingRepository::list(&state.db, system.as_deref()).await?)
+    }
+
+    pub async fn create(state: AppState, req: CreateMaturityRatingRequest) -> AppResult<MaturityRating> {
+        let system = req.system.trim().to_uppercase();
+        let code = req.code.trim();
+
+        if RatingRepository::find_by_code(&state.db, &system, code).await?.is_some() {
+            return Err(AppError::conflict(format!("Rating {} {} already exists", system, code)));
+        }
+
+        Ok(RatingRepository::create(&state.db, &system, code, req.level, req.description.as_deref()).await?)
+    }
+
+    /// Resolves a rating code in the configured rating system (`MATURITY_RATING_SYSTEM`).
+    /// An empty code clears the rating.
+    pub async fn resolve_code(state: &AppState, code: &str) -> AppResult<Option<MaturityRating>> {
+        let code = code.trim();
+        if code.is_empty() {
+            return Ok(None);
+        }
+
+        let system = &state.config.maturity_rating_system;
+        RatingRepository::find_by_code(&state.db, system, code)
+            .await?
+            .map(Some)
+            .ok_or_else(|| AppError::validation(format!("Unknown {} rating '{}'", system, code)))
+    }
+
+    pub async fn get(state: &AppState, id: Option<Uuid>) -> AppResult<Option<MaturityRating>> {
+        match id {
+            Some(id) => Ok(RatingRepository::find_by_id(&state.db, id).await?),
+            None => Ok(None),
+        }
+    }
+
+    /// All ratings by id, for attaching ratings to a list of titles.
+    pub async fn all_by_id(state: &AppState) -> AppResult<HashMap<Uuid, MaturityRating>> {
+        let ratings = RatingRepository::list(&state.db, None).await?;
+        Ok(ratings.into_iter().map(|r| (r.id, r)).collect())
+    }
+}
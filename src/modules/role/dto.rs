@@ -0,0 +1,27 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateRoleRequest {
+    /// Upper case letters and underscores, e.g. `CONTENT_EDITOR`
+    #[validate(length(min = 2, max = 50, message = "Name must be 2-50 characters"))]
+    pub name: String,
+    pub description: Option<String>,
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateRoleRequest {
+    pub description: Option<String>,
+    /// Replaces the permissions of the role when present
+    pub permissions: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct AssignRoleRequest {
+    pub role: String,
+    /// Why the role is changed, kept in the audit log
+    #[validate(length(min = 1, max = 500, message = "Reason must be 1-500 characters"))]
+    pub reason: String,
+}
@@ -0,0 +1,165 @@
+use super::dto::{CreateRoleRequest, UpdateRoleRequest};
+use super::model::{Permission, Role};
+use super::repository::RoleRepository;
+use super::service::RoleService;
+use crate::modules::audit::model::{AuditContext, AuditEntry};
+use crate::modules::audit::service::AuditService;
+use crate::common::error::AppError;
+use crate::common::response::{ApiResponse, ApiSuccess};
+use crate::state::AppState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use validator::Validate;
+
+/// List roles with their permissions
+#[utoipa::path(
+    get,
+    path = "/api/v1/roles",
+    responses(
+        (status = 200, description = "Roles retrieved", body = ApiResponse<Vec<Role>>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing permission users.manage")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Roles"
+)]
+pub async fn list_roles(State(state): State<AppState>) -> impl IntoResponse {
+    match RoleService::list_roles(state).await {
+        Ok(roles) => ApiSuccess(ApiResponse::success(roles, "Roles retrieved"), StatusCode::OK).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// List all permissions that can be granted to a role
+#[utoipa::path(
+    get,
+    path = "/api/v1/roles/permissions",
+    responses(
+        (status = 200, description = "Permissions retrieved", body = ApiResponse<Vec<Permission>>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing permission users.manage")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Roles"
+)]
+pub async fn list_permissions(State(state): State<AppState>) -> impl IntoResponse {
+    match RoleService::list_permissions(state).await {
+        Ok(permissions) => {
+            ApiSuccess(ApiResponse::success(permissions, "Permissions retrieved"), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Create a role
+#[utoipa::path(
+    post,
+    path = "/api/v1/roles",
+    request_body = CreateRoleRequest,
+    responses(
+        (status = 201, description = "Role created", body = ApiResponse<Role>),
+        (status = 400, description = "Invalid name or unknown permission"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing permission users.manage")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Roles"
+)]
+pub async fn create_role(
+    State(state): State<AppState>,
+    audit: AuditContext,
+    Json(payload): Json<CreateRoleRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = payload.validate() {
+        return AppError::from(e).into_response();
+    }
+
+    match RoleService::create_role(state.clone(), payload).await {
+        Ok(role) => {
+            AuditService::record(&state, &audit, AuditEntry::new("role.create", "role", Some(&role.name)).after(&role)).await;
+            ApiSuccess(ApiResponse::success(role, "Role created"), StatusCode::CREATED).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Update the description or permissions of a role
+#[utoipa::path(
+    put,
+    path = "/api/v1/roles/{name}",
+    params(
+        ("name" = String, Path, description = "Role name")
+    ),
+    request_body = UpdateRoleRequest,
+    responses(
+        (status = 200, description = "Role updated", body = ApiResponse<Role>),
+        (status = 400, description = "Unknown permission or locked role"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing permission users.manage")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Roles"
+)]
+pub async fn update_role(
+    State(state): State<AppState>,
+    audit: AuditContext,
+    Path(name): Path<String>,
+    Json(payload): Json<UpdateRoleRequest>,
+) -> impl IntoResponse {
+    let before = RoleRepository::find_role(&state.db, &name).await.ok().flatten();
+
+    match RoleService::update_role(state.clone(), &name, payload).await {
+        Ok(role) => {
+            let entry = AuditEntry::new("role.update", "role", Some(&name)).before(&before).after(&role);
+            AuditService::record(&state, &audit, entry).await;
+            ApiSuccess(ApiResponse::success(role, "Role updated"), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Delete a custom role
+#[utoipa::path(
+    delete,
+    path = "/api/v1/roles/{name}",
+    params(
+        ("name" = String, Path, description = "Role name")
+    ),
+    responses(
+        (status = 200, description = "Role deleted", body = ApiResponse<String>),
+        (status = 400, description = "Built-in role or role still assigned"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing permission users.manage")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Roles"
+)]
+pub async fn delete_role(
+    State(state): State<AppState>,
+    audit: AuditContext,
+    Path(name): Path<String>,
+) -> impl IntoResponse {
+    let before = RoleRepository::find_role(&state.db, &name).await.ok().flatten();
+
+    match RoleService::delete_role(state.clone(), &name).await {
+        Ok(_) => {
+            AuditService::record(&state, &audit, AuditEntry::new("role.delete", "role", Some(&name)).before(&before)).await;
+            ApiSuccess(ApiResponse::success((), "Role deleted"), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
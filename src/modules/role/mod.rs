@@ -0,0 +1,25 @@
+use axum::Router;
+use axum::routing::{get, put};
+use crate::state::AppState;
+use axum::middleware;
+
+pub mod dto;
+pub mod handler;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+pub fn router(state: AppState) -> axum::Router<AppState> {
+    Router::new()
+        .route("/", get(handler::list_roles).post(handler::create_role))
+        .route("/permissions", get(handler::list_permissions))
+        .route("/{name}", put(handler::update_role).delete(handler::delete_role))
+        .route_layer(middleware::from_fn(|req, next| {
+            crate::middleware::role::require_permission(model::USERS_MANAGE, req, next)
+        }))
+        .route_layer(middleware::from_fn(crate::middleware::auth::session_only))
+        .route_layer(middleware::from_fn_with_state(
+            state,
+            crate::middleware::auth::auth_middleware
+        ))
+}
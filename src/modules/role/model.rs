@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+
+// Built-in roles
+pub const ROLE_ADMIN: &str = "ADMIN";
+pub const ROLE_USER: &str = "USER";
+
+// Permissions checked by `middleware::role::require_permission`
+pub const CONTENT_WRITE: &str = "content.write";
+pub const CONTENT_DELETE: &str = "content.delete";
+pub const GENRE_MANAGE: &str = "genre.manage";
+pub const USERS_MANAGE: &str = "users.manage";
+pub const USERS_IMPERSONATE: &str = "users.impersonate";
+pub const AUDIT_READ: &str = "audit.read";
+
+#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema, Clone)]
+pub struct Role {
+    pub name: String,
+    pub description: Option<String>,
+    pub is_system: bool,
+    pub permissions: Vec<String>,
+    #[serde(with = "time::serde::iso8601")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::iso8601")]
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema, Clone)]
+pub struct Permission {
+    pub name: String,
+    pub description: String,
+}
@@ -0,0 +1,157 @@
+use super::model::{Permission, Role};
+use anyhow::Result;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub struct RoleRepository;
+
+impl RoleRepository {
+    pub async fn list_roles(pool: &PgPool) -> Result<Vec<Role>> {
+        let roles = sqlx::query_as!(
+            Role,
+            r#"
+            SELECT r.name, r.description, r.is_system, r.created_at, r.updated_at,
+                   COALESCE(ARRAY_AGG(rp.permission ORDER BY rp.permission) FILTER (WHERE rp.permission IS NOT NULL), '{}') as "permissions!"
+            FROM roles r
+            LEFT JOIN role_permissions rp ON rp.role_name = r.name
+            GROUP BY r.name
+            ORDER BY r.name ASC
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(roles)
+    }
+
+    pub async fn find_role(pool: &PgPool, name: &str) -> Result<Option<Role>> {
+        let role = sqlx::query_as!(
+            Role,
+            r#"
+            SELECT r.name, r.description, r.is_system, r.created_at, r.updated_at,
+                   COALESCE(ARRAY_AGG(rp.permission ORDER BY rp.permission) FILTER (WHERE rp.permission IS NOT NULL), '{}') as "permissions!"
+            FROM roles r
+            LEFT JOIN role_permissions rp ON rp.role_name = r.name
+            WHERE r.name = $1
+            GROUP BY r.name
+            "#,
+            name
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(role)
+    }
+
+    pub async fn list_permissions(pool: &PgPool) -> Result<Vec<Permission>> {
+        let permissions = sqlx::query_as!(
+            Permission,
+            "SELECT name, description FROM permissions ORDER BY name ASC"
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(permissions)
+    }
+
+    pub async fn permissions_for_role(pool: &PgPool, role: &str) -> Result<Vec<String>> {
+        let rows = sqlx::query!(
+            "SELECT permission FROM role_permissions WHERE role_name = $1 ORDER BY permission",
+            role
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|r| r.permission).collect())
+    }
+
+    pub async fn create_role(
+        pool: &PgPool,
+        name: &str,
+        description: Option<&str>,
+        permissions: &[String],
+    ) -> Result<()> {
+        let mut tx = pool.begin().await?;
+
+        sqlx::query!(
+            "INSERT INTO roles (name, description) VALUES ($1, $2)",
+            name,
+            description
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            "INSERT INTO role_permissions (role_name, permission) SELECT $1, UNNEST($2::VARCHAR[])",
+            name,
+            permissions
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn update_role(
+        pool: &PgPool,
+        name: &str,
+        description: Option<&str>,
+        permissions: Option<&[String]>,
+    ) -> Result<()> {
+        let mut tx = pool.begin().await?;
+
+        sqlx::query!(
+            "UPDATE roles SET description = COALESCE($1, description), updated_at = NOW() WHERE name = $2",
+            description,
+            name
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        if let Some(permissions) = permissions {
+            sqlx::query!("DELETE FROM role_permissions WHERE role_name = $1", name)
+                .execute(&mut *tx)
+                .await?;
+
+            sqlx::query!(
+                "INSERT INTO role_permissions (role_name, permission) SELECT $1, UNNEST($2::VARCHAR[])",
+                name,
+                permissions
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn delete_role(pool: &PgPool, name: &str) -> Result<bool> {
+        let result = sqlx::query!("DELETE FROM roles WHERE name = $1 AND is_system = FALSE", name)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn count_users_with_role(pool: &PgPool, name: &str) -> Result<i64> {
+        let row = sqlx::query!(r#"SELECT COUNT(*) as "count!" FROM users WHERE role = $1"#, name)
+            .fetch_one(pool)
+            .await?;
+
+        Ok(row.count)
+    }
+
+    pub async fn set_user_role(pool: &PgPool, user_id: Uuid, role: &str) -> Result<bool> {
+        let result = sqlx::query!(
+            "UPDATE users SET role = $1, updated_at = NOW() WHERE id = $2",
+            role,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
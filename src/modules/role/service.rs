@@ -0,0 +1,130 @@
+use super::dto::{CreateRoleRequest, UpdateRoleRequest};
+use super::model::{Permission, Role, ROLE_ADMIN};
+use super::repository::RoleRepository;
+use crate::modules::auth::repository::AuthRepository;
+use crate::modules::auth::service::AuthService;
+use crate::state::AppState;
+use crate::common::error::{AppError, AppResult};
+use uuid::Uuid;
+
+pub struct RoleService;
+
+impl RoleService {
+    pub async fn list_roles(state: AppState) -> AppResult<Vec<Role>> {
+        Ok(RoleRepository::list_roles(&state.db).await?)
+    }
+
+    pub async fn list_permissions(state: AppState) -> AppResult<Vec<Permission>> {
+        Ok(RoleRepository::list_permissions(&state.db).await?)
+    }
+
+    pub async fn create_role(state: AppState, req: CreateRoleRequest) -> AppResult<Role> {
+        let name = req.name.trim().to_uppercase();
+        if !name.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_') {
+            return Err(AppError::validation("Role name may only contain letters, digits and underscores"));
+        }
+        if RoleRepository::find_role(&state.db, &name).await?.is_some() {
+            return Err(AppError::conflict(format!("Role '{}' already exists", name)));
+        }
+
+        let permissions = Self::validate_permissions(&state, req.permissions).await?;
+        RoleRepository::create_role(&state.db, &name, req.description.as_deref(), &permissions).await?;
+        tracing::info!("Created role {} with permissions {:?}", name, permissions);
+
+        RoleRepository::find_role(&state.db, &name)
+            .await?
+            .ok_or_else(|| AppError::not_found("Role not found"))
+    }
+
+    /// Updates a role. Permission changes apply to tokens issued afterwards,
+    /// existing access tokens keep theirs until they expire.
+    pub async fn update_role(state: AppState, name: &str, req: UpdateRoleRequest) -> AppResult<Role> {
+        RoleRepository::find_role(&state.db, name)
+            .await?
+            .ok_or_else(|| AppError::not_found("Role not found"))?;
+
+        // ADMIN always keeps every permission, so nobody can lock themselves out
+        if name == ROLE_ADMIN && req.permissions.is_some() {
+            return Err(AppError::validation("Permissions of the ADMIN role cannot be changed"));
+        }
+
+        let permissions = match req.permissions {
+            Some(permissions) => Some(Self::validate_permissions(&state, permissions).await?),
+            None => None,
+        };
+
+        RoleRepository::update_role(&state.db, name, req.description.as_deref(), permissions.as_deref()).await?;
+        tracing::info!("Updated role {}", name);
+
+        RoleRepository::find_role(&state.db, name)
+            .await?
+            .ok_or_else(|| AppError::not_found("Role not found"))
+    }
+
+    pub async fn delete_role(state: AppState, name: &str) -> AppResult<()> {
+        let role = RoleRepository::find_role(&state.db, name)
+            .await?
+            .ok_or_else(|| AppError::not_found("Role not found"))?;
+
+        if role.is_system {
+            return Err(AppError::forbidden(format!("Built-in role '{}' cannot be deleted", role.name)));
+        }
+
+        let users = RoleRepository::count_users_with_role(&state.db, name).await?;
+        if users > 0 {
+            return Err(AppError::conflict(format!("Role is assigned to {} user(s), reassign them first", users)));
+        }
+
+        RoleRepository::delete_role(&state.db, name).await?;
+        tracing::info!("Deleted role {}", name);
+        Ok(())
+    }
+
+    /// Assigns a role to a user and revokes their sessions, so the new
+    /// permissions take effect on the next login instead of after token expiry.
+    /// Returns the previous role.
+    pub async fn assign_role(state: AppState, actor_id: Uuid, user_id: Uuid, role: &str) -> AppResult<String> {
+        if actor_id == user_id {
+            return Err(AppError::forbidden("You cannot change your own role"));
+        }
+
+        let role = role.trim().to_uppercase();
+        RoleRepository::find_role(&state.db, &role)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("Role '{}' does not exist", role)))?;
+
+        let user = AuthRepository::find_user_by_id(&state.db, user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("User not found"))?;
+        if user.role == role {
+            return Ok(user.role);
+        }
+
+        RoleRepository::set_user_role(&state.db, user.id, &role).await?;
+        tracing::info!("User {} changed role of user {} from {} to {}", actor_id, user.id, user.role, role);
+
+        AuthService::revoke_all_sessions(state, user.id).await?;
+        Ok(user.role)
+    }
+
+    /// Permissions to put into an access token. Returns the permissions and
+    /// whether they were withheld because the role requires 2FA the session lacks.
+    pub async fn resolve_permissions(state: &AppState, role: &str, mfa_verified: bool) -> AppResult<(Vec<String>, bool)> {
+        if state.config.require_admin_mfa && role == ROLE_ADMIN && !mfa_verified {
+            return Ok((Vec::new(), true));
+        }
+
+        let permissions = RoleRepository::permissions_for_role(&state.db, role).await?;
+        Ok((permissions, false))
+    }
+
+    async fn validate_permissions(state: &AppState, mut permissions: Vec<String>) -> AppResult<Vec<String>> {
+        let known = RoleRepository::list_permissions(&state.db).await?;
+        if let Some(unknown) = permissions.iter().find(|p| !known.iter().any(|k| &k.name == *p)) {
+            return Err(AppError::validation(format!("Unknown permission '{}'", unknown)));
+        }
+        permissions.sort();
+        permissions.dedup();
+        Ok(permissions)
+    }
+}
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SearchQuery {
+    /// Search text; supports quoted phrases, `or` and `-` to exclude words
+    pub q: String,
+    /// Only `movie`, `series` or `episode` results
+    #[serde(rename = "type")]
+    pub content_type: Option<String>,
+    /// Results per content type, 1-50 (default 10)
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SearchHit {
+    pub id: Uuid,
+    /// `movie`, `series` or `episode`
+    pub content_type: String,
+    pub title: String,
+    /// HTML-escaped title with matched terms in `<mark>`
+    pub highlighted_title: String,
+    /// HTML-escaped excerpt of the description with matched terms in `<mark>`
+    pub snippet: Option<String>,
+    /// Series of an episode
+    pub series_id: Option<Uuid>,
+    pub thumbnail_url: Option<String>,
+    pub release_year: Option<i32>,
+    pub score: f32,
+}
+
+/// Results grouped by content type, best match first.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SearchResponse {
+    pub query: String,
+    pub movies: Vec<SearchHit>,
+    pub series: Vec<SearchHit>,
+    pub episodes: Vec<SearchHit>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SuggestQuery {
+    /// Text typed so far
+    pub q: String,
+    /// Number of suggestions, 1-20 (default 8)
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Suggestion {
+    pub id: Uuid,
+    /// `movie`, `series` or `genre`
+    pub content_type: String,
+    pub title: String,
+    /// Genre slug, usable as the `genre` filter of the movie and series lists
+    pub slug: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SuggestResponse {
+    pub query: String,
+    pub suggestions: Vec<Suggestion>,
+}
@@ -0,0 +1,62 @@
+use super::dto::{SearchQuery, SearchResponse, SuggestQuery, SuggestResponse};
+use super::service::SearchService;
+use crate::common::response::{ApiResponse, ApiSuccess};
+use crate::modules::profile::model::ViewerContext;
+use crate::state::AppState;
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+
+/// Search movies, series and episodes by title and description
+#[utoipa::path(
+    get,
+    path = "/api/v1/search",
+    params(
+        SearchQuery,
+        ("x-parental-pin" = Option<String>, Header, description = "Parental PIN lifting the profile's maturity limit")
+    ),
+    responses(
+        (status = 200, description = "Matches grouped by content type, best first", body = ApiResponse<SearchResponse>),
+        (status = 400, description = "Missing or invalid query"),
+        (status = 403, description = "Invalid parental PIN")
+    ),
+    tag = "Search"
+)]
+pub async fn search(
+    State(state): State<AppState>,
+    viewer: ViewerContext,
+    Query(query): Query<SearchQuery>,
+) -> impl IntoResponse {
+    match SearchService::search(state, viewer, query).await {
+        Ok(results) => ApiSuccess(ApiResponse::success(results, "Search results"), StatusCode::OK).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Suggest titles and genres for the text typed so far
+#[utoipa::path(
+    get,
+    path = "/api/v1/search/suggest",
+    params(
+        SuggestQuery,
+        ("x-parental-pin" = Option<String>, Header, description = "Parental PIN lifting the profile's maturity limit")
+    ),
+    responses(
+        (status = 200, description = "Matching titles and genres, most popular first", body = ApiResponse<SuggestResponse>),
+        (status = 400, description = "Missing or invalid query"),
+        (status = 403, description = "Invalid parental PIN")
+    ),
+    tag = "Search"
+)]
+pub async fn suggest(
+    State(state): State<AppState>,
+    viewer: ViewerContext,
+    Query(query): Query<SuggestQuery>,
+) -> impl IntoResponse {
+    match SearchService::suggest(state, viewer, query).await {
+        Ok(suggestions) => ApiSuccess(ApiResponse::success(suggestions, "Suggestions"), StatusCode::OK).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
@@ -0,0 +1,15 @@
+use axum::Router;
+use axum::routing::get;
+use crate::state::AppState;
+
+pub mod dto;
+pub mod handler;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+pub fn router() -> axum::Router<AppState> {
+    Router::new()
+        .route("/", get(handler::search))
+        .route("/suggest", get(handler::suggest))
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub const CONTENT_MOVIE: &str = "movie";
+pub const CONTENT_SERIES: &str = "series";
+pub const CONTENT_EPISODE: &str = "episode";
+pub const CONTENT_GENRE: &str = "genre";
+
+/// A matching search document with the title it belongs to. Matched terms in
+/// `title_highlight` and `snippet` are wrapped in `HIGHLIGHT_START`/`HIGHLIGHT_STOP`.
+#[derive(Debug, Clone)]
+pub struct SearchHitRow {
+    pub content_type: String,
+    pub content_id: Uuid,
+    /// Series of an episode
+    pub series_id: Option<Uuid>,
+    pub title: String,
+    pub title_highlight: String,
+    pub snippet: Option<String>,
+    pub thumbnail_url: Option<String>,
+    pub release_year: Option<i32>,
+    pub score: f32,
+}
+
+/// Control characters mark highlights in the database; they cannot be confused
+/// with text and are turned into `<mark>` after the text is HTML-escaped.
+pub const HIGHLIGHT_START: char = '\u{1}';
+pub const HIGHLIGHT_STOP: char = '\u{2}';
+
+/// A title or genre in the suggestion index. `score` ranks suggestions for the
+/// same prefix: views for titles, the number of titles for genres.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuggestEntry {
+    pub kind: String,
+    pub id: Uuid,
+    pub title: String,
+    /// Genre slug, for linking to the filtered catalog
+    pub slug: Option<String>,
+    pub maturity_level: Option<i32>,
+    /// Playable: a ready movie, or a series with a ready episode. Genres always are.
+    pub available: bool,
+    /// Shown to viewers; unpublished titles are only suggested to catalog staff
+    #[serde(default)]
+    pub published: bool,
+    pub score: f64,
+}
+
+impl SuggestEntry {
+    pub fn member(kind: &str, id: Uuid) -> String {
+        format!("{}:{}", kind, id)
+    }
+}
@@ -0,0 +1,210 @@
+use super::model::{SearchHitRow, SuggestEntry, HIGHLIGHT_START, HIGHLIGHT_STOP};
+use anyhow::Result;
+use redis::aio::MultiplexedConnection;
+use redis::AsyncCommands;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+const SUGGEST_ENTRIES_KEY: &str = "suggest:entries";
+
+fn prefix_key(prefix: &str) -> String {
+    format!("suggest:prefix:{}", prefix)
+}
+
+pub struct SearchRepository;
+
+impl SearchRepository {
+    /// Best matches per content type, at most `limit` of each. A title matches the
+    /// full-text query or, to tolerate typos, is similar to the text by trigrams.
+    /// Only playable titles (status `READY`; series with a ready episode) within
+    /// `max_maturity_level` are returned, and with `published_only` only published
+    /// ones; episodes go by their series.
+    pub async fn search(
+        pool: &PgPool,
+        text: &str,
+        content_type: Option<&str>,
+        max_maturity_level: Option<i32>,
+        published_only: bool,
+        limit: i64,
+    ) -> Result<Vec<SearchHitRow>> {
+        let title_options = format!("HighlightAll=true, StartSel={}, StopSel={}", HIGHLIGHT_START, HIGHLIGHT_STOP);
+        let snippet_options = format!(
+            "MaxFragments=2, MinWords=5, MaxWords=20, FragmentDelimiter=\" … \", StartSel={}, StopSel={}",
+            HIGHLIGHT_START, HIGHLIGHT_STOP
+        );
+
+        let hits = sqlx::query_as!(
+            SearchHitRow,
+            r#"
+            WITH matches AS (
+                SELECT d.content_type, d.content_id, d.title, d.description, q.query,
+                       sn.series_id,
+                       COALESCE(m.thumbnail_url, e.thumbnail_url, s.thumbnail_url) AS thumbnail_url,
+                       COALESCE(m.release_year, s.release_year) AS release_year,
+                       ts_rank(d.search_vector, q.query) + similarity(d.title, $1) AS score
+                FROM search_documents d
+                CROSS JOIN websearch_to_tsquery('simple', $1) AS q(query)
+                LEFT JOIN movies m ON d.content_type = 'movie' AND m.id = d.content_id
+                LEFT JOIN episodes e ON d.content_type = 'episode' AND e.id = d.content_id
+                LEFT JOIN seasons sn ON sn.id = e.season_id
+                LEFT JOIN series s ON s.id = CASE WHEN d.content_type = 'series' THEN d.content_id ELSE sn.series_id END
+                WHERE (d.search_vector @@ q.query OR d.title % $1)
+                  AND ($2::VARCHAR IS NULL OR d.content_type = $2)
+                  AND CASE d.content_type
+                        WHEN 'movie' THEN m.status = 'READY'
+                        WHEN 'episode' THEN e.status = 'READY'
+                        ELSE EXISTS (
+                            SELECT 1 FROM seasons rs JOIN episodes re ON re.season_id = rs.id
+                            WHERE rs.series_id = s.id AND re.status = 'READY')
+                      END
+                  AND ($3::INT IS NULL
+                       OR COALESCE(m.maturity_rating_id, s.maturity_rating_id)
+                          IN (SELECT id FROM maturity_ratings WHERE level <= $3))
+                  AND (NOT $7::BOOLEAN OR COALESCE(m.visibility, s.visibility) = 'published')
+            ),
+            ranked AS (
+                SELECT *, ROW_NUMBER() OVER (PARTITION BY content_type ORDER BY score DESC, content_id) AS position
+                FROM matches
+            )
+            SELECT content_type as "content_type!",
+                   content_id as "content_id!",
+                   series_id,
+                   title as "title!",
+                   ts_headline('simple', title, query, $5) as "title_highlight!",
+                   NULLIF(ts_headline('simple', COALESCE(description, ''), query, $6), '') as snippet,
+                   thumbnail_url,
+                   release_year,
+                   score as "score!"
+            FROM ranked
+            WHERE position <= $4
+            ORDER BY content_type, score DESC
+            "#,
+            text,
+            content_type,
+            max_maturity_level,
+            limit,
+            title_options,
+            snippet_options,
+            published_only
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(hits)
+    }
+
+    // --- SUGGESTIONS ---
+
+    /// Suggestion entries from the catalog: all of them, those of one kind, or a
+    /// single one when `kind` and `id` are given.
+    pub async fn load_suggest_entries(pool: &PgPool, kind: Option<&str>, id: Option<Uuid>) -> Result<Vec<SuggestEntry>> {
+        let entries = sqlx::query_as!(
+            SuggestEntry,
+            r#"
+            SELECT 'movie' as "kind!", m.id as "id!", m.title as "title!", NULL::VARCHAR as slug,
+                   r.level as maturity_level,
+                   m.status = 'READY' as "available!",
+                   m.visibility = 'published' as "published!",
+                   COALESCE(m.views, 0)::FLOAT8 as "score!"
+            FROM movies m
+            LEFT JOIN maturity_ratings r ON r.id = m.maturity_rating_id
+            WHERE ($1::TEXT IS NULL OR $1 = 'movie') AND ($2::UUID IS NULL OR m.id = $2)
+            UNION ALL
+            SELECT 'series', s.id, s.title, NULL,
+                   r.level,
+                   EXISTS (SELECT 1 FROM seasons sn JOIN episodes e ON e.season_id = sn.id
+                           WHERE sn.series_id = s.id AND e.status = 'READY'),
+                   s.visibility = 'published',
+                   series_views(s.id)::FLOAT8
+            FROM series s
+            LEFT JOIN maturity_ratings r ON r.id = s.maturity_rating_id
+            WHERE ($1::TEXT IS NULL OR $1 = 'series') AND ($2::UUID IS NULL OR s.id = $2)
+            UNION ALL
+            SELECT 'genre', g.id, g.name, g.slug,
+                   NULL,
+                   TRUE,
+                   TRUE,
+                   (SELECT COUNT(*) FROM content_genres cg WHERE cg.genre_id = g.id)::FLOAT8
+            FROM genres g
+            WHERE ($1::TEXT IS NULL OR $1 = 'genre') AND ($2::UUID IS NULL OR g.id = $2)
+            "#,
+            kind,
+            id
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(entries)
+    }
+
+    /// Adds or replaces an entry under each of its prefixes, removing it from the
+    /// prefixes of its previous title.
+    pub async fn index_suggestion(
+        redis: &mut MultiplexedConnection,
+        entry: &SuggestEntry,
+        prefixes: &[String],
+        stale_prefixes: &[String],
+    ) -> Result<()> {
+        let member = SuggestEntry::member(&entry.kind, entry.id);
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        for prefix in stale_prefixes {
+            pipe.zrem(prefix_key(prefix), &member).ignore();
+        }
+        for prefix in prefixes {
+            pipe.zadd(prefix_key(prefix), &member, entry.score).ignore();
+        }
+        pipe.hset(SUGGEST_ENTRIES_KEY, &member, serde_json::to_string(entry)?).ignore();
+        let _: () = pipe.query_async(redis).await?;
+        Ok(())
+    }
+
+    pub async fn remove_suggestion(redis: &mut MultiplexedConnection, member: &str, prefixes: &[String]) -> Result<()> {
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        for prefix in prefixes {
+            pipe.zrem(prefix_key(prefix), member).ignore();
+        }
+        pipe.hdel(SUGGEST_ENTRIES_KEY, member).ignore();
+        let _: () = pipe.query_async(redis).await?;
+        Ok(())
+    }
+
+    pub async fn get_suggest_entry(redis: &mut MultiplexedConnection, member: &str) -> Result<Option<SuggestEntry>> {
+        let payload: Option<String> = redis.hget(SUGGEST_ENTRIES_KEY, member).await?;
+        match payload {
+            Some(p) => Ok(Some(serde_json::from_str(&p)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn list_suggest_members(redis: &mut MultiplexedConnection) -> Result<Vec<String>> {
+        let members: Vec<String> = redis.hkeys(SUGGEST_ENTRIES_KEY).await?;
+        Ok(members)
+    }
+
+    /// One page of the entries under a prefix, highest scored first, with the number of
+    /// index members the page covered; fewer than `count` means the prefix is exhausted.
+    pub async fn find_suggestions(
+        redis: &mut MultiplexedConnection,
+        prefix: &str,
+        offset: isize,
+        count: isize,
+    ) -> Result<(Vec<SuggestEntry>, usize)> {
+        let members: Vec<String> = redis.zrevrange(prefix_key(prefix), offset, offset + count - 1).await?;
+        if members.is_empty() {
+            return Ok((Vec::new(), 0));
+        }
+
+        let payloads: Vec<Option<String>> = redis::cmd("HMGET")
+            .arg(SUGGEST_ENTRIES_KEY)
+            .arg(&members)
+            .query_async(redis)
+            .await?;
+        let entries = payloads
+            .into_iter()
+            .flatten()
+            .filter_map(|p| serde_json::from_str(&p).ok())
+            .collect();
+        Ok((entries, members.len()))
+    }
+}
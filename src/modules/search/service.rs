@@ -0,0 +1,214 @@
+use super::dto::{SearchHit, SearchQuery, SearchResponse, SuggestQuery, SuggestResponse, Suggestion};
+use super::model::{
+    SearchHitRow, SuggestEntry, CONTENT_EPISODE, CONTENT_GENRE, CONTENT_MOVIE, CONTENT_SERIES, HIGHLIGHT_START, HIGHLIGHT_STOP,
+};
+use super::repository::SearchRepository;
+use crate::common::error::{AppError, AppResult};
+use crate::modules::profile::model::ViewerContext;
+use crate::state::AppState;
+use redis::aio::MultiplexedConnection;
+use std::collections::{BTreeSet, HashSet};
+use uuid::Uuid;
+
+const MAX_QUERY_LENGTH: usize = 100;
+/// Longer prefixes are not indexed; longer input is matched on its first characters
+/// and then checked against the full title.
+const MAX_PREFIX_LENGTH: usize = 20;
+
+pub struct SearchService;
+
+impl SearchService {
+    /// Searches movies, series and episodes the viewer may watch.
+    pub async fn search(state: AppState, viewer: ViewerContext, query: SearchQuery) -> AppResult<SearchResponse> {
+        let text = query.q.trim();
+        if text.is_empty() || text.chars().count() > MAX_QUERY_LENGTH {
+            return Err(AppError::validation(format!("q must be 1-{} characters", MAX_QUERY_LENGTH)));
+        }
+        let content_type = query.content_type.as_deref().map(str::trim);
+        if let Some(kind) = content_type
+            && ![CONTENT_MOVIE, CONTENT_SERIES, CONTENT_EPISODE].contains(&kind)
+        {
+            return Err(AppError::validation(format!("Invalid type '{}', expected movie, series or episode", kind)));
+        }
+        let limit = query.limit.unwrap_or(10).clamp(1, 50);
+
+        let rows = SearchRepository::search(&state.db, text, content_type, viewer.max_maturity_level, !viewer.sees_unpublished, limit).await?;
+
+        let mut response = SearchResponse {
+            query: text.to_string(),
+            movies: Vec::new(),
+            series: Vec::new(),
+            episodes: Vec::new(),
+        };
+        for row in rows {
+            let group = match row.content_type.as_str() {
+                CONTENT_MOVIE => &mut response.movies,
+                CONTENT_SERIES => &mut response.series,
+                _ => &mut response.episodes,
+            };
+            group.push(Self::to_hit(row));
+        }
+        Ok(response)
+    }
+
+    fn to_hit(row: SearchHitRow) -> SearchHit {
+        SearchHit {
+            id: row.content_id,
+            content_type: row.content_type,
+            title: row.title,
+            highlighted_title: Self::highlight(&row.title_highlight),
+            snippet: row.snippet.as_deref().map(Self::highlight),
+            series_id: row.series_id,
+            thumbnail_url: row.thumbnail_url,
+            release_year: row.release_year,
+            score: row.score,
+        }
+    }
+
+    /// HTML-escapes text from the database and turns the highlight markers into `<mark>`.
+    fn highlight(text: &str) -> String {
+        let mut html = String::with_capacity(text.len() + 16);
+        for c in text.chars() {
+            match c {
+                '&' => html.push_str("&amp;"),
+                '<' => html.push_str("&lt;"),
+                '>' => html.push_str("&gt;"),
+                '"' => html.push_str("&quot;"),
+                '\'' => html.push_str("&#39;"),
+                HIGHLIGHT_START => html.push_str("<mark>"),
+                HIGHLIGHT_STOP => html.push_str("</mark>"),
+                c => html.push(c),
+            }
+        }
+        html
+    }
+
+    // --- SUGGESTIONS ---
+
+    /// Titles and genres starting with the typed text, most popular first. Served
+    /// from Redis, without touching the database for anonymous viewers.
+    pub async fn suggest(state: AppState, viewer: ViewerContext, query: SuggestQuery) -> AppResult<SuggestResponse> {
+        let text = Self::normalize(&query.q);
+        if text.is_empty() || text.chars().count() > MAX_QUERY_LENGTH {
+            return Err(AppError::validation(format!("q must contain 1-{} letters or digits", MAX_QUERY_LENGTH)));
+        }
+        let limit = query.limit.unwrap_or(8).clamp(1, 20) as usize;
+        let prefix: String = text.chars().take(MAX_PREFIX_LENGTH).collect();
+
+        let allowed = |e: &SuggestEntry| {
+            e.available
+                && (e.published || viewer.sees_unpublished)
+                && (e.kind == CONTENT_GENRE || viewer.allows(e.maturity_level))
+                && (prefix == text || format!(" {}", Self::normalize(&e.title)).contains(&format!(" {}", text)))
+        };
+
+        // Page through the prefix until the limit is filled with entries the viewer may see,
+        // fetching extra per page since restricted ones are hidden
+        let mut redis_conn = state.redis.get_conn().await?;
+        let page = (limit * 3) as isize;
+        let mut offset = 0;
+        let mut suggestions = Vec::new();
+        loop {
+            let (entries, fetched) = SearchRepository::find_suggestions(&mut redis_conn, &prefix, offset, page).await?;
+            suggestions.extend(entries.into_iter().filter(|e| allowed(e)).map(|e| Suggestion {
+                id: e.id,
+                content_type: e.kind,
+                title: e.title,
+                slug: e.slug,
+            }));
+            if suggestions.len() >= limit || fetched < page as usize {
+                break;
+            }
+            offset += page;
+        }
+        suggestions.truncate(limit);
+
+        Ok(SuggestResponse { query: query.q.trim().to_string(), suggestions })
+    }
+
+    /// Brings the suggestion index in line with the catalog for one title or genre,
+    /// after it was created, changed or deleted. Failures are logged, not returned,
+    /// so they never fail the change itself; the next rebuild repairs the index.
+    pub async fn refresh_suggestion(state: &AppState, kind: &str, id: Uuid) {
+        if let Err(e) = Self::try_refresh_suggestion(state, kind, id).await {
+            tracing::warn!("Failed to update search suggestion for {} {}: {}", kind, id, e);
+        }
+    }
+
+    async fn try_refresh_suggestion(state: &AppState, kind: &str, id: Uuid) -> AppResult<()> {
+        let entry = SearchRepository::load_suggest_entries(&state.db, Some(kind), Some(id)).await?.pop();
+        let mut redis_conn = state.redis.get_conn().await?;
+        match entry {
+            Some(entry) => Self::index_entry(&mut redis_conn, &entry).await,
+            None => Self::remove_entry(&mut redis_conn, &SuggestEntry::member(kind, id)).await,
+        }
+    }
+
+    /// Reindexes every title and genre and drops entries whose source is gone.
+    /// Runs at startup; also refreshes the popularity scores.
+    pub async fn rebuild_suggestions(state: &AppState) -> AppResult<usize> {
+        let entries = SearchRepository::load_suggest_entries(&state.db, None, None).await?;
+        let mut redis_conn = state.redis.get_conn().await?;
+
+        let current: HashSet<String> = entries.iter().map(|e| SuggestEntry::member(&e.kind, e.id)).collect();
+        for member in SearchRepository::list_suggest_members(&mut redis_conn).await? {
+            if !current.contains(&member) {
+                Self::remove_entry(&mut redis_conn, &member).await?;
+            }
+        }
+        for entry in &entries {
+            Self::index_entry(&mut redis_conn, entry).await?;
+        }
+        Ok(entries.len())
+    }
+
+    async fn index_entry(redis: &mut MultiplexedConnection, entry: &SuggestEntry) -> AppResult<()> {
+        let member = SuggestEntry::member(&entry.kind, entry.id);
+        let prefixes = Self::prefixes(&entry.title);
+        let stale: Vec<String> = SearchRepository::get_suggest_entry(redis, &member)
+            .await?
+            .map(|previous| Self::prefixes(&previous.title))
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|p| !prefixes.contains(p))
+            .collect();
+        Ok(SearchRepository::index_suggestion(redis, entry, &prefixes, &stale).await?)
+    }
+
+    async fn remove_entry(redis: &mut MultiplexedConnection, member: &str) -> AppResult<()> {
+        let prefixes = SearchRepository::get_suggest_entry(redis, member)
+            .await?
+            .map(|previous| Self::prefixes(&previous.title))
+            .unwrap_or_default();
+        Ok(SearchRepository::remove_suggestion(redis, member, &prefixes).await?)
+    }
+
+    /// Lowercase words of letters and digits, separated by single spaces.
+    fn normalize(text: &str) -> String {
+        text.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Prefixes of the title starting at each word, so "The Matrix" is found by
+    /// "the m" as well as by "mat".
+    fn prefixes(title: &str) -> Vec<String> {
+        let normalized = Self::normalize(title);
+        let words: Vec<&str> = normalized.split(' ').filter(|w| !w.is_empty()).collect();
+
+        let mut prefixes = BTreeSet::new();
+        for start in 0..words.len() {
+            let tail = words[start..].join(" ");
+            let mut prefix = String::new();
+            for c in tail.chars().take(MAX_PREFIX_LENGTH) {
+                prefix.push(c);
+                if c != ' ' {
+                    prefixes.insert(prefix.clone());
+                }
+            }
+        }
+        prefixes.into_iter().collect()
+    }
+}
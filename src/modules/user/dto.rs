@@ -0,0 +1,136 @@
+use crate::modules::auth::model::User;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ChangePasswordRequest {
+    #[validate(length(min = 1, message = "Current password is required"))]
+    pub current_password: String,
+    /// At least `PASSWORD_MIN_LENGTH` characters, not a common password and not containing the username or email
+    #[validate(length(min = 1, max = 128, message = "Password must be 1-128 characters"))]
+    pub new_password: String,
+}
+
+/// Fields left out are not changed.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateProfileRequest {
+    #[validate(length(min = 3, max = 50, message = "Username must be 3-50 characters"))]
+    pub username: Option<String>,
+    /// A new email must be confirmed again before it counts as verified
+    #[validate(email(message = "Invalid email address"))]
+    pub email: Option<String>,
+    #[validate(length(min = 1, max = 100, message = "Full name must be 1-100 characters"))]
+    pub full_name: Option<String>,
+    /// Required when `email` changes
+    pub current_password: Option<String>,
+}
+
+/// Fields left out are not changed; an empty language clears it.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdatePreferencesRequest {
+    #[validate(length(max = 10, message = "Language code is too long"))]
+    pub preferred_audio_language: Option<String>,
+    #[validate(length(max = 10, message = "Language code is too long"))]
+    pub preferred_subtitle_language: Option<String>,
+    pub subtitles_enabled: Option<bool>,
+    pub autoplay_next_episode: Option<bool>,
+    pub autoplay_previews: Option<bool>,
+}
+
+// --- ADMIN ---
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct AdminUserQuery {
+    /// Matches username, email or full name
+    pub q: Option<String>,
+    pub role: Option<String>,
+    /// `active` or `suspended`
+    pub status: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminUserResponse {
+    pub id: Uuid,
+    pub username: String,
+    pub email: String,
+    pub full_name: String,
+    pub role: String,
+    pub email_verified: bool,
+    pub mfa_enabled: bool,
+    pub suspended: bool,
+    #[serde(with = "time::serde::iso8601::option")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub suspended_until: Option<OffsetDateTime>,
+    pub suspension_reason: Option<String>,
+    #[serde(with = "time::serde::iso8601")]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: OffsetDateTime,
+}
+
+impl From<User> for AdminUserResponse {
+    fn from(user: User) -> Self {
+        let suspended = user.is_suspended();
+        Self {
+            email_verified: user.email_verified_at.is_some(),
+            mfa_enabled: user.totp_enabled_at.is_some(),
+            suspended,
+            suspended_until: if suspended { user.suspended_until } else { None },
+            suspension_reason: if suspended { user.suspension_reason } else { None },
+            id: user.id,
+            username: user.username,
+            email: user.email,
+            full_name: user.full_name,
+            role: user.role,
+            created_at: user.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminUserPage {
+    pub items: Vec<AdminUserResponse>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct SuspendUserRequest {
+    #[validate(length(min = 1, max = 500, message = "Reason must be 1-500 characters"))]
+    pub reason: String,
+    /// Lift the suspension automatically after this many hours; indefinite when omitted
+    #[validate(range(min = 1, max = 87600, message = "Duration must be between 1 hour and 10 years"))]
+    pub duration_hours: Option<i64>,
+}
+
+/// Reason for an admin action, kept in the audit log.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct AdminActionRequest {
+    #[validate(length(min = 1, max = 500, message = "Reason must be 1-500 characters"))]
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ImpersonateRequest {
+    /// Why support needs to see the account, e.g. a ticket reference; kept in the audit log
+    #[validate(length(min = 1, max = 500, message = "Reason must be 1-500 characters"))]
+    pub reason: String,
+    /// Viewer profile to open; the account's default profile when omitted
+    pub profile_id: Option<Uuid>,
+}
+
+/// Read-only access token for acting as another user. Send it like a normal
+/// access token; it cannot be refreshed and ends with the impersonation session.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImpersonationResponse {
+    pub access_token: String,
+    /// Seconds until the impersonation session ends
+    pub expires_in: u64,
+    pub user: AdminUserResponse,
+    pub profile_id: Uuid,
+}
@@ -0,0 +1,651 @@
+use super::dto::{
+    AdminActionRequest, AdminUserPage, AdminUserQuery, AdminUserResponse, ChangePasswordRequest,
+    ImpersonateRequest, ImpersonationResponse, SuspendUserRequest, UpdatePreferencesRequest, UpdateProfileRequest,
+};
+use super::model::UserPreferences;
+use super::service::UserService;
+use crate::modules::role::dto::AssignRoleRequest;
+use crate::modules::role::service::RoleService;
+use crate::common::error::AppError;
+use crate::common::response::{ApiResponse, ApiSuccess};
+use crate::common::types::ClientContext;
+use crate::common::upload::stream_to_s3_limited;
+use crate::modules::auth::dto::UserResponse;
+use crate::modules::auth::repository::AuthRepository;
+use crate::modules::auth::dto::TokenClaims;
+use crate::modules::audit::model::{AuditContext, AuditEntry};
+use crate::modules::audit::service::AuditService;
+use crate::state::AppState;
+use axum::{
+    body::Bytes,
+    extract::{Extension, Multipart, Path, Query, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+const MAX_AVATAR_BYTES: usize = 5 * 1024 * 1024;
+
+/// Raster formats accepted as avatars: content type, file extension.
+/// Anything else, SVG in particular, could carry script when served same-origin.
+const AVATAR_FORMATS: &[(&str, &str)] = &[
+    ("image/png", "png"),
+    ("image/jpeg", "jpg"),
+    ("image/webp", "webp"),
+    ("image/gif", "gif"),
+];
+
+/// Bytes needed to tell the avatar formats apart (WebP has the longest signature).
+const AVATAR_SNIFF_BYTES: usize = 12;
+
+/// Detects the avatar format from the file's magic bytes; the client's content type is not trusted.
+fn sniff_avatar_format(bytes: &[u8]) -> Option<(&'static str, &'static str)> {
+    let extension = if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        "png"
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        "jpg"
+    } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        "webp"
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        "gif"
+    } else {
+        return None;
+    };
+    AVATAR_FORMATS.iter().find(|(_, ext)| *ext == extension).copied()
+}
+
+/// Get the profile of the current user
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me",
+    responses(
+        (status = 200, description = "User profile", body = ApiResponse<UserResponse>),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "User"
+)]
+pub async fn get_profile(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+) -> impl IntoResponse {
+    match UserService::get_profile(state, claims.sub).await {
+        Ok(user) => ApiSuccess(ApiResponse::success(user, "User profile retrieved"), StatusCode::OK).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Update username, full name or email of the current user
+#[utoipa::path(
+    patch,
+    path = "/api/v1/users/me",
+    request_body = UpdateProfileRequest,
+    responses(
+        (status = 200, description = "Profile updated; a changed email must be verified again", body = ApiResponse<UserResponse>),
+        (status = 400, description = "Invalid input, or the current password is missing or incorrect for an email change"),
+        (status = 409, description = "Username or email already taken"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "User"
+)]
+pub async fn update_profile(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    audit: AuditContext,
+    Json(payload): Json<UpdateProfileRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = payload.validate() {
+        return AppError::from(e).into_response();
+    }
+
+    let before = UserService::get_profile(state.clone(), claims.sub).await.ok();
+
+    match UserService::update_profile(state.clone(), claims.sub, payload).await {
+        Ok(user) => {
+            let entry = AuditEntry::new("user.profile_update", "user", Some(claims.sub)).before(&before).after(&user);
+            AuditService::record(&state, &audit, entry).await;
+            ApiSuccess(ApiResponse::success(user, "Profile updated"), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Upload an avatar image for the current user
+#[utoipa::path(
+    put,
+    path = "/api/v1/users/me/avatar",
+    request_body(content = String, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Avatar uploaded", body = ApiResponse<UserResponse>),
+        (status = 400, description = "Missing field, not a PNG, JPEG, WebP or GIF image, or larger than 5 MB"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "User"
+)]
+pub async fn upload_avatar(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    audit: AuditContext,
+    mut multipart: Multipart,
+) -> impl IntoResponse {
+    while let Some(mut field) = multipart.next_field().await.unwrap_or(None) {
+        if field.name() != Some("avatar") {
+            continue;
+        }
+
+        // Read just enough to check the magic bytes, the rest is streamed to storage
+        let mut head = Vec::new();
+        while head.len() < AVATAR_SNIFF_BYTES {
+            match field.chunk().await {
+                Ok(Some(chunk)) => head.extend_from_slice(&chunk),
+                Ok(None) => break,
+                Err(e) => return AppError::validation(format!("Upload failed: {}", e)).into_response(),
+            }
+        }
+
+        let Some((content_type, extension)) = sniff_avatar_format(&head) else {
+            return AppError::validation("Avatar must be a PNG, JPEG, WebP or GIF image").into_response();
+        };
+        let key = format!("avatars/{}/avatar.{}", claims.sub, extension);
+
+        let mut storage_for_avatar = state.storage.clone();
+        storage_for_avatar.bucket = state.config.minio_bucket_thumbnails.clone();
+
+        let upload = stream_to_s3_limited(
+            &storage_for_avatar,
+            field,
+            key.clone(),
+            content_type,
+            Bytes::from(head),
+            MAX_AVATAR_BYTES,
+        )
+        .await;
+        if let Err(e) = upload {
+            return AppError::validation(format!("Upload failed: {}", e)).into_response();
+        }
+
+        return match UserService::set_avatar(state.clone(), claims.sub, &key).await {
+            Ok(user) => {
+                let entry = AuditEntry::new("user.avatar_update", "user", Some(claims.sub))
+                    .after(&serde_json::json!({ "avatar_url": key }));
+                AuditService::record(&state, &audit, entry).await;
+                ApiSuccess(ApiResponse::success(user, "Avatar uploaded"), StatusCode::OK).into_response()
+            }
+            Err(e) => e.into_response(),
+        };
+    }
+
+    AppError::validation("No avatar field found in multipart request").into_response()
+}
+
+/// Get the avatar image of a user
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{id}/avatar",
+    params(
+        ("id" = Uuid, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "Avatar image", body = Vec<u8>),
+        (status = 404, description = "User has no avatar")
+    ),
+    tag = "User"
+)]
+pub async fn get_avatar(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    let key = match AuthRepository::find_user_by_id(&state.db, id).await {
+        Ok(Some(user)) => match user.avatar_url {
+            Some(key) => key,
+            None => return AppError::not_found("User has no avatar").into_response(),
+        },
+        Ok(None) => return AppError::not_found("User not found").into_response(),
+        Err(e) => return AppError::from(e).into_response(),
+    };
+
+    let mut storage_for_avatar = state.storage.clone();
+    storage_for_avatar.bucket = state.config.minio_bucket_thumbnails.clone();
+
+    match storage_for_avatar.get_object(&key).await {
+        Ok(bytes) => {
+            // The type comes from the format detected on upload, never from the stored file;
+            // avatars stored before formats were checked are only offered as a download
+            let extension = key.rsplit('.').next().unwrap_or_default();
+            let (content_type, disposition) = match AVATAR_FORMATS.iter().find(|(_, ext)| *ext == extension) {
+                Some((content_type, _)) => (*content_type, "inline"),
+                None => ("application/octet-stream", "attachment"),
+            };
+            (
+                [
+                    (header::CONTENT_TYPE, content_type),
+                    (header::CONTENT_DISPOSITION, disposition),
+                    (header::X_CONTENT_TYPE_OPTIONS, "nosniff"),
+                ],
+                bytes,
+            )
+                .into_response()
+        }
+        Err(e) => {
+            tracing::warn!("Failed to fetch avatar '{}': {}", key, e);
+            AppError::not_found("Avatar not found").into_response()
+        }
+    }
+}
+
+/// Get playback preferences of the active viewer profile
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me/preferences",
+    responses(
+        (status = 200, description = "Preferences", body = ApiResponse<UserPreferences>),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "User"
+)]
+pub async fn get_preferences(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+) -> impl IntoResponse {
+    match UserService::get_preferences(state, claims.sub, claims.profile_id).await {
+        Ok(preferences) => ApiSuccess(ApiResponse::success(preferences, "Preferences retrieved"), StatusCode::OK).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Update playback preferences of the active viewer profile
+#[utoipa::path(
+    patch,
+    path = "/api/v1/users/me/preferences",
+    request_body = UpdatePreferencesRequest,
+    responses(
+        (status = 200, description = "Preferences updated", body = ApiResponse<UserPreferences>),
+        (status = 400, description = "Invalid language code"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "User"
+)]
+pub async fn update_preferences(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    Json(payload): Json<UpdatePreferencesRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = payload.validate() {
+        return AppError::from(e).into_response();
+    }
+
+    match UserService::update_preferences(state, claims.sub, claims.profile_id, payload).await {
+        Ok(preferences) => ApiSuccess(ApiResponse::success(preferences, "Preferences updated"), StatusCode::OK).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Change the password of the current user
+#[utoipa::path(
+    put,
+    path = "/api/v1/users/me/password",
+    request_body = ChangePasswordRequest,
+    responses(
+        (status = 200, description = "Password changed, other sessions revoked", body = ApiResponse<String>),
+        (status = 400, description = "Current password is incorrect"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "User"
+)]
+pub async fn change_password(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    audit: AuditContext,
+    Json(payload): Json<ChangePasswordRequest>,
+) -> impl IntoResponse {
+    match UserService::change_password(state.clone(), claims.sub, claims.sid, payload).await {
+        Ok(_) => {
+            AuditService::record(&state, &audit, AuditEntry::new("user.password_change", "user", Some(claims.sub))).await;
+            ApiSuccess(ApiResponse::success((), "Password changed successfully"), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Assign a role to a user; their sessions are revoked so it applies on next login
+#[utoipa::path(
+    put,
+    path = "/api/v1/users/{id}/role",
+    params(
+        ("id" = Uuid, Path, description = "User ID")
+    ),
+    request_body = AssignRoleRequest,
+    responses(
+        (status = 200, description = "Role assigned", body = ApiResponse<String>),
+        (status = 400, description = "Missing reason"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing permission users.manage, or own account"),
+        (status = 404, description = "User or role not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Roles"
+)]
+pub async fn assign_role(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    audit: AuditContext,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<AssignRoleRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = payload.validate() {
+        return AppError::from(e).into_response();
+    }
+
+    match RoleService::assign_role(state.clone(), claims.sub, id, &payload.role).await {
+        Ok(previous) => {
+            let entry = AuditEntry::new("user.role_change", "user", Some(id))
+                .before(&serde_json::json!({ "role": previous }))
+                .after(&serde_json::json!({ "role": payload.role.trim().to_uppercase(), "reason": payload.reason }));
+            AuditService::record(&state, &audit, entry).await;
+            ApiSuccess(ApiResponse::success((), "Role assigned"), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Search and page through user accounts
+#[utoipa::path(
+    get,
+    path = "/api/v1/users",
+    params(AdminUserQuery),
+    responses(
+        (status = 200, description = "Users, newest first", body = ApiResponse<AdminUserPage>),
+        (status = 400, description = "Invalid filter"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing permission users.manage")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Users"
+)]
+pub async fn list_users(
+    State(state): State<AppState>,
+    Query(query): Query<AdminUserQuery>,
+) -> impl IntoResponse {
+    match UserService::list_users(state, query).await {
+        Ok(page) => ApiSuccess(ApiResponse::success(page, "Users retrieved"), StatusCode::OK).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Get a user account
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{id}",
+    params(
+        ("id" = Uuid, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "User found", body = ApiResponse<AdminUserResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing permission users.manage"),
+        (status = 404, description = "User not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Users"
+)]
+pub async fn get_user(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match UserService::get_user(state, id).await {
+        Ok(user) => ApiSuccess(ApiResponse::success(user, "User retrieved"), StatusCode::OK).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Suspend a user; their sessions are revoked and logins are refused until lifted
+#[utoipa::path(
+    post,
+    path = "/api/v1/users/{id}/suspend",
+    params(
+        ("id" = Uuid, Path, description = "User ID")
+    ),
+    request_body = SuspendUserRequest,
+    responses(
+        (status = 200, description = "User suspended", body = ApiResponse<AdminUserResponse>),
+        (status = 400, description = "Missing reason or invalid duration"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing permission users.manage, or own account"),
+        (status = 404, description = "User not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Users"
+)]
+pub async fn suspend_user(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    audit: AuditContext,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<SuspendUserRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = payload.validate() {
+        return AppError::from(e).into_response();
+    }
+
+    let reason = payload.reason.trim().to_string();
+    match UserService::suspend_user(state.clone(), claims.sub, id, payload).await {
+        Ok(user) => {
+            let entry = AuditEntry::new("user.suspend", "user", Some(id)).after(&serde_json::json!({
+                "suspended_until": user.suspended_until.map(|t| t.unix_timestamp()),
+                "reason": reason,
+            }));
+            AuditService::record(&state, &audit, entry).await;
+            ApiSuccess(ApiResponse::success(user, "User suspended"), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Lift the suspension of a user
+#[utoipa::path(
+    post,
+    path = "/api/v1/users/{id}/unsuspend",
+    params(
+        ("id" = Uuid, Path, description = "User ID")
+    ),
+    request_body = AdminActionRequest,
+    responses(
+        (status = 200, description = "Suspension lifted", body = ApiResponse<AdminUserResponse>),
+        (status = 400, description = "Missing reason"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing permission users.manage"),
+        (status = 404, description = "User not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Users"
+)]
+pub async fn unsuspend_user(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    audit: AuditContext,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<AdminActionRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = payload.validate() {
+        return AppError::from(e).into_response();
+    }
+
+    let before = UserService::get_user(state.clone(), id).await.ok().map(|u| {
+        serde_json::json!({
+            "suspended_until": u.suspended_until.map(|t| t.unix_timestamp()),
+            "suspension_reason": u.suspension_reason,
+        })
+    });
+
+    match UserService::unsuspend_user(state.clone(), claims.sub, id).await {
+        Ok(user) => {
+            let entry = AuditEntry::new("user.unsuspend", "user", Some(id))
+                .before(&before)
+                .after(&serde_json::json!({ "reason": payload.reason.trim() }));
+            AuditService::record(&state, &audit, entry).await;
+            ApiSuccess(ApiResponse::success(user, "Suspension lifted"), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Log a user out on every device
+#[utoipa::path(
+    post,
+    path = "/api/v1/users/{id}/logout",
+    params(
+        ("id" = Uuid, Path, description = "User ID")
+    ),
+    request_body = AdminActionRequest,
+    responses(
+        (status = 200, description = "Number of revoked sessions", body = ApiResponse<usize>),
+        (status = 400, description = "Missing reason"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing permission users.manage"),
+        (status = 404, description = "User not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Users"
+)]
+pub async fn force_logout(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    audit: AuditContext,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<AdminActionRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = payload.validate() {
+        return AppError::from(e).into_response();
+    }
+
+    match UserService::force_logout(state.clone(), claims.sub, id).await {
+        Ok(revoked) => {
+            let entry = AuditEntry::new("user.force_logout", "user", Some(id))
+                .after(&serde_json::json!({ "revoked_sessions": revoked, "reason": payload.reason.trim() }));
+            AuditService::record(&state, &audit, entry).await;
+            ApiSuccess(ApiResponse::success(revoked, "User logged out on all devices"), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Delete a user account
+#[utoipa::path(
+    delete,
+    path = "/api/v1/users/{id}",
+    params(
+        ("id" = Uuid, Path, description = "User ID")
+    ),
+    request_body = AdminActionRequest,
+    responses(
+        (status = 200, description = "User deleted"),
+        (status = 400, description = "Missing reason"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing permission users.manage, or own account"),
+        (status = 404, description = "User not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Users"
+)]
+pub async fn delete_user(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    audit: AuditContext,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<AdminActionRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = payload.validate() {
+        return AppError::from(e).into_response();
+    }
+
+    match UserService::delete_user(state.clone(), claims.sub, id).await {
+        Ok(()) => {
+            // Only the reason: the account has been erased and its details must not live on here.
+            let entry = AuditEntry::new("user.delete", "user", Some(id))
+                .after(&serde_json::json!({ "reason": payload.reason.trim() }));
+            AuditService::record(&state, &audit, entry).await;
+            ApiSuccess(ApiResponse::success((), "User deleted"), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Act as a user to see what they see; returns a short-lived, read-only access token
+#[utoipa::path(
+    post,
+    path = "/api/v1/users/{id}/impersonate",
+    params(
+        ("id" = Uuid, Path, description = "User ID")
+    ),
+    request_body = ImpersonateRequest,
+    responses(
+        (status = 200, description = "Impersonation token issued", body = ApiResponse<ImpersonationResponse>),
+        (status = 400, description = "Missing reason"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing permission users.impersonate, or an own, staff or suspended account"),
+        (status = 404, description = "User or profile not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Users"
+)]
+pub async fn impersonate_user(
+    State(state): State<AppState>,
+    Extension(claims): Extension<TokenClaims>,
+    client: ClientContext,
+    audit: AuditContext,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<ImpersonateRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = payload.validate() {
+        return AppError::from(e).into_response();
+    }
+
+    let reason = payload.reason.trim().to_string();
+    match UserService::impersonate(state.clone(), &claims, client, id, payload).await {
+        Ok(response) => {
+            let entry = AuditEntry::new("user.impersonate", "user", Some(id)).after(&serde_json::json!({
+                "profile_id": response.profile_id,
+                "expires_in": response.expires_in,
+                "reason": reason,
+            }));
+            AuditService::record(&state, &audit, entry).await;
+            ApiSuccess(ApiResponse::success(response, "Impersonation started"), StatusCode::OK).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
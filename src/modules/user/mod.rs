@@ -1 +1,64 @@
-// user module
+use axum::Router;
+use axum::routing::{get, patch, post, put};
+use crate::state::AppState;
+use crate::modules::api_key::model::SCOPE_PROFILE_READ;
+use crate::modules::jobs;
+use crate::modules::role::model::{USERS_IMPERSONATE, USERS_MANAGE};
+use axum::middleware;
+
+pub mod dto;
+pub mod handler;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+pub fn router(state: AppState) -> axum::Router<AppState> {
+    let public_routes = Router::new()
+        .route("/{id}/avatar", get(handler::get_avatar));
+
+    let profile_routes = Router::new()
+        .route("/me", get(handler::get_profile))
+        .route_layer(middleware::from_fn(|req, next| {
+            crate::middleware::auth::require_scope(SCOPE_PROFILE_READ, req, next)
+        }));
+
+    let account_routes = Router::new()
+        .route("/me", patch(handler::update_profile).delete(jobs::handler::delete_account))
+        .route("/me/password", put(handler::change_password))
+        .route("/me/avatar", put(handler::upload_avatar))
+        .route("/me/preferences", get(handler::get_preferences).patch(handler::update_preferences))
+        .route("/me/export", post(jobs::handler::request_export))
+        .route("/me/export/{id}", get(jobs::handler::get_export))
+        .route("/me/export/{id}/download", get(jobs::handler::download_export))
+        .route_layer(middleware::from_fn(crate::middleware::auth::session_only));
+
+    let admin_routes = Router::new()
+        .route("/", get(handler::list_users))
+        .route("/{id}", get(handler::get_user).delete(handler::delete_user))
+        .route("/{id}/role", put(handler::assign_role))
+        .route("/{id}/suspend", post(handler::suspend_user))
+        .route("/{id}/unsuspend", post(handler::unsuspend_user))
+        .route("/{id}/logout", post(handler::force_logout))
+        .route_layer(middleware::from_fn(|req, next| {
+            crate::middleware::role::require_permission(USERS_MANAGE, req, next)
+        }))
+        .route_layer(middleware::from_fn(crate::middleware::auth::session_only));
+
+    let impersonation_routes = Router::new()
+        .route("/{id}/impersonate", post(handler::impersonate_user))
+        .route_layer(middleware::from_fn(|req, next| {
+            crate::middleware::role::require_permission(USERS_IMPERSONATE, req, next)
+        }))
+        .route_layer(middleware::from_fn(crate::middleware::auth::session_only));
+
+    let protected_routes = profile_routes
+        .merge(account_routes)
+        .merge(admin_routes)
+        .merge(impersonation_routes)
+        .route_layer(middleware::from_fn_with_state(
+            state,
+            crate::middleware::auth::auth_middleware
+        ));
+
+    public_routes.merge(protected_routes)
+}
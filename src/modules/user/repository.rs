@@ -0,0 +1,241 @@
+use super::model::UserPreferences;
+use crate::modules::auth::model::User;
+use anyhow::Result;
+use redis::aio::MultiplexedConnection;
+use redis::AsyncCommands;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+pub struct UserRepository;
+
+/// Filters for the admin user list, all optional.
+pub struct UserFilter<'a> {
+    /// Substring of username, email or full name
+    pub q: Option<&'a str>,
+    pub role: Option<&'a str>,
+    /// `Some(true)` only suspended, `Some(false)` only active
+    pub suspended: Option<bool>,
+}
+
+impl UserRepository {
+    /// Updates the profile fields. A changed email is stored unverified.
+    pub async fn update_profile(
+        pool: &PgPool,
+        user_id: Uuid,
+        username: &str,
+        email: &str,
+        full_name: &str,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE users
+            SET username = $1,
+                full_name = $3,
+                email_verified_at = CASE WHEN email = $2 THEN email_verified_at ELSE NULL END,
+                email = $2,
+                updated_at = NOW()
+            WHERE id = $4
+            "#,
+            username,
+            email,
+            full_name,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn set_avatar(pool: &PgPool, user_id: Uuid, avatar_key: &str) -> Result<()> {
+        sqlx::query!(
+            "UPDATE users SET avatar_url = $1, updated_at = NOW() WHERE id = $2",
+            avatar_key,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns the preferences, creating the row with defaults on first access.
+    /// Playback preferences of a viewer profile, created with defaults on first access.
+    pub async fn get_preferences(pool: &PgPool, user_id: Uuid, profile_id: Uuid) -> Result<UserPreferences> {
+        sqlx::query!(
+            "INSERT INTO user_preferences (profile_id, user_id) VALUES ($1, $2) ON CONFLICT (profile_id) DO NOTHING",
+            profile_id,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+
+        let preferences = sqlx::query_as!(
+            UserPreferences,
+            r#"
+            SELECT preferred_audio_language, preferred_subtitle_language, subtitles_enabled,
+                   autoplay_next_episode, autoplay_previews, updated_at
+            FROM user_preferences
+            WHERE profile_id = $1
+            "#,
+            profile_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(preferences)
+    }
+
+    pub async fn save_preferences(
+        pool: &PgPool,
+        user_id: Uuid,
+        profile_id: Uuid,
+        preferences: &UserPreferences,
+    ) -> Result<UserPreferences> {
+        let preferences = sqlx::query_as!(
+            UserPreferences,
+            r#"
+            INSERT INTO user_preferences (
+                profile_id, user_id, preferred_audio_language, preferred_subtitle_language,
+                subtitles_enabled, autoplay_next_episode, autoplay_previews
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (profile_id) DO UPDATE SET
+                preferred_audio_language = EXCLUDED.preferred_audio_language,
+                preferred_subtitle_language = EXCLUDED.preferred_subtitle_language,
+                subtitles_enabled = EXCLUDED.subtitles_enabled,
+                autoplay_next_episode = EXCLUDED.autoplay_next_episode,
+                autoplay_previews = EXCLUDED.autoplay_previews,
+                updated_at = NOW()
+            RETURNING preferred_audio_language, preferred_subtitle_language, subtitles_enabled,
+                      autoplay_next_episode, autoplay_previews, updated_at
+            "#,
+            profile_id,
+            user_id,
+            preferences.preferred_audio_language,
+            preferences.preferred_subtitle_language,
+            preferences.subtitles_enabled,
+            preferences.autoplay_next_episode,
+            preferences.autoplay_previews
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(preferences)
+    }
+
+    // --- ADMIN ---
+
+    pub async fn search(pool: &PgPool, filter: &UserFilter<'_>, limit: i64, offset: i64) -> Result<Vec<User>> {
+        let users = sqlx::query_as!(
+            User,
+            r#"
+            SELECT id, username, email, full_name, role, password_hash, email_verified_at, totp_secret, totp_enabled_at, avatar_url,
+                   suspended_at, suspended_until, suspension_reason, created_at, updated_at
+            FROM users
+            WHERE ($1::VARCHAR IS NULL OR username ILIKE '%' || $1 || '%' OR email ILIKE '%' || $1 || '%' OR full_name ILIKE '%' || $1 || '%')
+              AND ($2::VARCHAR IS NULL OR role = $2)
+              AND ($3::BOOLEAN IS NULL
+                   OR $3 = (suspended_at IS NOT NULL AND (suspended_until IS NULL OR suspended_until > NOW())))
+            ORDER BY created_at DESC
+            LIMIT $4 OFFSET $5
+            "#,
+            filter.q,
+            filter.role,
+            filter.suspended,
+            limit,
+            offset
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(users)
+    }
+
+    pub async fn count(pool: &PgPool, filter: &UserFilter<'_>) -> Result<i64> {
+        let row = sqlx::query!(
+            r#"
+            SELECT COUNT(*) as "count!"
+            FROM users
+            WHERE ($1::VARCHAR IS NULL OR username ILIKE '%' || $1 || '%' OR email ILIKE '%' || $1 || '%' OR full_name ILIKE '%' || $1 || '%')
+              AND ($2::VARCHAR IS NULL OR role = $2)
+              AND ($3::BOOLEAN IS NULL
+                   OR $3 = (suspended_at IS NOT NULL AND (suspended_until IS NULL OR suspended_until > NOW())))
+            "#,
+            filter.q,
+            filter.role,
+            filter.suspended
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(row.count)
+    }
+
+    pub async fn suspend(pool: &PgPool, user_id: Uuid, until: Option<OffsetDateTime>, reason: &str) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE users
+            SET suspended_at = NOW(), suspended_until = $1, suspension_reason = $2, updated_at = NOW()
+            WHERE id = $3
+            "#,
+            until,
+            reason,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn unsuspend(pool: &PgPool, user_id: Uuid) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE users
+            SET suspended_at = NULL, suspended_until = NULL, suspension_reason = NULL, updated_at = NOW()
+            WHERE id = $1
+            "#,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete(pool: &PgPool, user_id: Uuid) -> Result<bool> {
+        let result = sqlx::query!("DELETE FROM users WHERE id = $1", user_id)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // Suspension marker checked by auth_middleware on every request, so it
+    // does not need a database round trip. The database stays the source of truth.
+
+    pub async fn mark_suspended(redis: &mut MultiplexedConnection, user_id: Uuid, ttl_secs: Option<u64>) -> Result<()> {
+        let key = format!("user_suspended:{}", user_id);
+        match ttl_secs {
+            Some(ttl) => {
+                let _: () = redis.set_ex(key, 1, ttl).await?;
+            }
+            None => {
+                let _: () = redis.set(key, 1).await?;
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn clear_suspended(redis: &mut MultiplexedConnection, user_id: Uuid) -> Result<()> {
+        let _: () = redis.del(format!("user_suspended:{}", user_id)).await?;
+        Ok(())
+    }
+
+    pub async fn is_suspended(redis: &mut MultiplexedConnection, user_id: Uuid) -> Result<bool> {
+        let exists: bool = redis.exists(format!("user_suspended:{}", user_id)).await?;
+        Ok(exists)
+    }
+}
@@ -0,0 +1,352 @@
+use super::dto::{
+    AdminUserPage, AdminUserQuery, AdminUserResponse, ChangePasswordRequest, ImpersonateRequest,
+    ImpersonationResponse, SuspendUserRequest, UpdatePreferencesRequest, UpdateProfileRequest,
+};
+use super::model::UserPreferences;
+use super::repository::{UserFilter, UserRepository};
+use crate::common::security;
+use crate::common::types::ClientContext;
+use crate::infrastructure::mail::sender::MailMessage;
+use crate::modules::auth::dto::{TokenClaims, UserResponse};
+use crate::modules::auth::model::{Actor, Session};
+use crate::modules::auth::repository::AuthRepository;
+use crate::modules::auth::service::{AuthService, ACCESS_TOKEN_TTL_SECS};
+use crate::modules::jobs::service::JobService;
+use crate::modules::profile::service::ProfileService;
+use crate::modules::role::service::RoleService;
+use crate::state::AppState;
+use crate::common::error::{AppError, AppResult};
+use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
+
+pub struct UserService;
+
+impl UserService {
+    pub async fn get_profile(state: AppState, user_id: Uuid) -> AppResult<UserResponse> {
+        let user = AuthRepository::find_user_by_id(&state.db, user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("User not found"))?;
+
+        Ok(UserResponse::from(user))
+    }
+
+    /// Updates username, full name and email. A new email is stored unverified:
+    /// a confirmation link goes to the new address and a notice to the old one.
+    pub async fn update_profile(state: AppState, user_id: Uuid, req: UpdateProfileRequest) -> AppResult<UserResponse> {
+        let user = AuthRepository::find_user_by_id(&state.db, user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("User not found"))?;
+
+        let username = req.username.as_deref().map(str::trim).unwrap_or(&user.username).to_string();
+        let full_name = req.full_name.as_deref().map(str::trim).unwrap_or(&user.full_name).to_string();
+        let email = req.email.as_deref().map(str::trim).unwrap_or(&user.email).to_string();
+
+        if username != user.username
+            && AuthRepository::find_user_by_username(&state.db, &username).await?.is_some()
+        {
+            return Err(AppError::conflict("Username already exists"));
+        }
+
+        let email_changed = email != user.email;
+        if email_changed {
+            // Verification and password reset mail follow the email, so an access token alone must not move it
+            let current_password = req
+                .current_password
+                .as_deref()
+                .ok_or_else(|| AppError::validation("Current password is required to change the email"))?;
+            if !state.passwords.verify(current_password, &user.password_hash)? {
+                return Err(AppError::validation("Current password is incorrect"));
+            }
+        }
+        if email_changed && AuthRepository::find_user_by_email(&state.db, &email).await?.is_some() {
+            return Err(AppError::conflict("Email already exists"));
+        }
+
+        UserRepository::update_profile(&state.db, user.id, &username, &email, &full_name).await?;
+
+        let updated = AuthRepository::find_user_by_id(&state.db, user.id)
+            .await?
+            .ok_or_else(|| AppError::not_found("User not found"))?;
+
+        if email_changed {
+            tracing::info!("User {} changed email, verification required", user.id);
+
+            if let Err(e) = AuthService::send_verification_email(&state, &updated).await {
+                tracing::error!("Failed to send verification email to user {}: {}", user.id, e);
+            }
+
+            let notice = MailMessage {
+                to: user.email.clone(),
+                subject: "Your HiuraMovie email address was changed".to_string(),
+                body: format!(
+                    "Hi {},\n\nThe email address of your account was changed to {}.\nIf you did not do this, reset your password and contact support.",
+                    user.full_name, email
+                ),
+            };
+            if let Err(e) = state.mailer.send(&notice).await {
+                tracing::error!("Failed to notify old email of user {}: {}", user.id, e);
+            }
+        }
+
+        Ok(UserResponse::from(updated))
+    }
+
+    /// Stores the object key of an uploaded avatar.
+    pub async fn set_avatar(state: AppState, user_id: Uuid, avatar_key: &str) -> AppResult<UserResponse> {
+        UserRepository::set_avatar(&state.db, user_id, avatar_key).await?;
+        Self::get_profile(state, user_id).await
+    }
+
+    /// Playback preferences of the active viewer profile.
+    pub async fn get_preferences(state: AppState, user_id: Uuid, profile_id: Option<Uuid>) -> AppResult<UserPreferences> {
+        let profile = ProfileService::resolve(&state, user_id, profile_id).await?;
+        Ok(UserRepository::get_preferences(&state.db, user_id, profile.id).await?)
+    }
+
+    pub async fn update_preferences(
+        state: AppState,
+        user_id: Uuid,
+        profile_id: Option<Uuid>,
+        req: UpdatePreferencesRequest,
+    ) -> AppResult<UserPreferences> {
+        let profile = ProfileService::resolve(&state, user_id, profile_id).await?;
+        let mut preferences = UserRepository::get_preferences(&state.db, user_id, profile.id).await?;
+
+        if let Some(language) = req.preferred_audio_language {
+            preferences.preferred_audio_language = Self::normalize_language(&language)?;
+        }
+        if let Some(language) = req.preferred_subtitle_language {
+            preferences.preferred_subtitle_language = Self::normalize_language(&language)?;
+        }
+        if let Some(enabled) = req.subtitles_enabled {
+            preferences.subtitles_enabled = enabled;
+        }
+        if let Some(enabled) = req.autoplay_next_episode {
+            preferences.autoplay_next_episode = enabled;
+        }
+        if let Some(enabled) = req.autoplay_previews {
+            preferences.autoplay_previews = enabled;
+        }
+
+        Ok(UserRepository::save_preferences(&state.db, user_id, profile.id, &preferences).await?)
+    }
+
+    /// Accepts language tags like `en`, `id` or `pt-BR`; an empty string clears the preference.
+    fn normalize_language(language: &str) -> AppResult<Option<String>> {
+        let language = language.trim();
+        if language.is_empty() {
+            return Ok(None);
+        }
+
+        let mut parts = language.split('-');
+        let primary = parts.next().unwrap_or_default();
+        let valid = (2..=3).contains(&primary.len())
+            && primary.chars().all(|c| c.is_ascii_alphabetic())
+            && parts.all(|p| (2..=4).contains(&p.len()) && p.chars().all(|c| c.is_ascii_alphanumeric()));
+        if !valid {
+            return Err(AppError::validation(format!("Invalid language code '{}'", language)));
+        }
+
+        Ok(Some(language.to_string()))
+    }
+
+    /// Changes the password of the logged-in user. The current session stays
+    /// active, every other session (and its refresh token) is revoked.
+    pub async fn change_password(
+        state: AppState,
+        user_id: Uuid,
+        session_id: Uuid,
+        req: ChangePasswordRequest,
+    ) -> AppResult<()> {
+        let user = AuthRepository::find_user_by_id(&state.db, user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("User not found"))?;
+
+        if !state.passwords.verify(&req.current_password, &user.password_hash)? {
+            return Err(AppError::validation("Current password is incorrect"));
+        }
+
+        if req.current_password == req.new_password {
+            return Err(AppError::validation("New password must be different from the current one"));
+        }
+
+        state.passwords.check_policy(&req.new_password, &[&user.username, &user.email, &user.full_name])?;
+
+        let password_hash = state.passwords.hash(&req.new_password)?;
+        AuthRepository::update_password(&state.db, user.id, &password_hash).await?;
+
+        AuthService::revoke_other_sessions(state, user.id, session_id).await?;
+        Ok(())
+    }
+
+    // --- ADMIN ---
+
+    pub async fn list_users(state: AppState, query: AdminUserQuery) -> AppResult<AdminUserPage> {
+        let limit = query.limit.unwrap_or(50).clamp(1, 200);
+        let offset = query.offset.unwrap_or(0).max(0);
+        let suspended = match query.status.as_deref() {
+            None => None,
+            Some("active") => Some(false),
+            Some("suspended") => Some(true),
+            Some(other) => return Err(AppError::validation(format!("Invalid status '{}', expected 'active' or 'suspended'", other))),
+        };
+        let role = query.role.as_deref().map(|r| r.trim().to_uppercase());
+        let filter = UserFilter {
+            q: query.q.as_deref().map(str::trim).filter(|q| !q.is_empty()),
+            role: role.as_deref(),
+            suspended,
+        };
+
+        let users = UserRepository::search(&state.db, &filter, limit, offset).await?;
+        let total = UserRepository::count(&state.db, &filter).await?;
+
+        Ok(AdminUserPage {
+            items: users.into_iter().map(AdminUserResponse::from).collect(),
+            total,
+            limit,
+            offset,
+        })
+    }
+
+    pub async fn get_user(state: AppState, user_id: Uuid) -> AppResult<AdminUserResponse> {
+        let user = AuthRepository::find_user_by_id(&state.db, user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("User not found"))?;
+
+        Ok(AdminUserResponse::from(user))
+    }
+
+    /// Suspends a user and revokes all their sessions. Access tokens already
+    /// issued are rejected by `auth_middleware` through the Redis marker.
+    pub async fn suspend_user(
+        state: AppState,
+        actor_id: Uuid,
+        user_id: Uuid,
+        req: SuspendUserRequest,
+    ) -> AppResult<AdminUserResponse> {
+        if actor_id == user_id {
+            return Err(AppError::forbidden("You cannot suspend your own account"));
+        }
+
+        let user = AuthRepository::find_user_by_id(&state.db, user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("User not found"))?;
+
+        let until = req.duration_hours.map(|h| OffsetDateTime::now_utc() + Duration::hours(h));
+        UserRepository::suspend(&state.db, user.id, until, req.reason.trim()).await?;
+
+        let mut redis_conn = state.redis.get_conn().await?;
+        let ttl = req.duration_hours.map(|h| h as u64 * 3600);
+        UserRepository::mark_suspended(&mut redis_conn, user.id, ttl).await?;
+
+        let revoked = AuthService::revoke_all_sessions(state.clone(), user.id).await?;
+        tracing::info!("User {} suspended user {} until {:?}, revoked {} session(s)", actor_id, user.id, until, revoked);
+
+        Self::get_user(state, user.id).await
+    }
+
+    pub async fn unsuspend_user(state: AppState, actor_id: Uuid, user_id: Uuid) -> AppResult<AdminUserResponse> {
+        let user = AuthRepository::find_user_by_id(&state.db, user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("User not found"))?;
+
+        UserRepository::unsuspend(&state.db, user.id).await?;
+
+        let mut redis_conn = state.redis.get_conn().await?;
+        UserRepository::clear_suspended(&mut redis_conn, user.id).await?;
+        tracing::info!("User {} lifted the suspension of user {}", actor_id, user.id);
+
+        Self::get_user(state, user.id).await
+    }
+
+    /// Logs a user out on every device. Returns how many sessions were revoked.
+    pub async fn force_logout(state: AppState, actor_id: Uuid, user_id: Uuid) -> AppResult<usize> {
+        let user = AuthRepository::find_user_by_id(&state.db, user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("User not found"))?;
+
+        let revoked = AuthService::revoke_all_sessions(state, user.id).await?;
+        tracing::info!("User {} forced logout of user {} ({} session(s))", actor_id, user.id, revoked);
+        Ok(revoked)
+    }
+
+    /// Opens a read-only session as another user so support can see what they see.
+    /// Accounts whose role grants any permission cannot be impersonated, which keeps
+    /// the token from carrying more rights than a regular viewer has.
+    pub async fn impersonate(
+        state: AppState,
+        admin: &TokenClaims,
+        client: ClientContext,
+        user_id: Uuid,
+        req: ImpersonateRequest,
+    ) -> AppResult<ImpersonationResponse> {
+        if admin.act.is_some() {
+            return Err(AppError::forbidden("Already impersonating a user"));
+        }
+        if admin.sub == user_id {
+            return Err(AppError::forbidden("You cannot impersonate your own account"));
+        }
+
+        let user = AuthRepository::find_user_by_id(&state.db, user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("User not found"))?;
+
+        if user.is_suspended() {
+            return Err(AppError::forbidden("Suspended accounts cannot be impersonated"));
+        }
+        let (permissions, _) = RoleService::resolve_permissions(&state, &user.role, true).await?;
+        if !permissions.is_empty() {
+            return Err(AppError::forbidden("Staff accounts cannot be impersonated"));
+        }
+
+        let profile = ProfileService::resolve(&state, user.id, req.profile_id).await?;
+
+        let now = OffsetDateTime::now_utc();
+        let session = Session {
+            id: Uuid::new_v4(),
+            user_id: user.id,
+            // Never handed out: impersonation sessions cannot be refreshed
+            refresh_token_hash: security::hash_token(&security::generate_token()),
+            ip_address: client.ip_address,
+            user_agent: client.user_agent,
+            created_at: now,
+            last_used_at: now,
+            mfa_verified: false,
+            profile_id: Some(profile.id),
+            impersonator: Some(Actor {
+                sub: admin.sub,
+                role: admin.role.clone(),
+            }),
+        };
+
+        let ttl = (state.config.impersonation_ttl_minutes * 60).min(ACCESS_TOKEN_TTL_SECS);
+        let mut redis_conn = state.redis.get_conn().await?;
+        AuthRepository::store_impersonation_session(&mut redis_conn, &session, ttl).await?;
+
+        let access_token = AuthService::create_access_token(&state, &user, &session).await?;
+        tracing::warn!("User {} started impersonating user {} (session {})", admin.sub, user.id, session.id);
+
+        Ok(ImpersonationResponse {
+            access_token,
+            expires_in: ttl,
+            user: AdminUserResponse::from(user),
+            profile_id: profile.id,
+        })
+    }
+
+    /// Deletes an account right away, the same way a self-service deletion does.
+    pub async fn delete_user(state: AppState, actor_id: Uuid, user_id: Uuid) -> AppResult<()> {
+        if actor_id == user_id {
+            return Err(AppError::forbidden("You cannot delete your own account"));
+        }
+
+        let user = AuthRepository::find_user_by_id(&state.db, user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("User not found"))?;
+
+        JobService::erase_account(&state, &user).await?;
+        tracing::info!("User {} deleted user {}", actor_id, user.id);
+
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 use crate::docs::ApiDoc;
-use axum::Router;
+use axum::{middleware, Router};
 use crate::state::AppState;
 
 use tower_http::cors::{Any, CorsLayer};
@@ -14,11 +14,20 @@ pub fn configure_routes(state: AppState) -> Router<AppState> {
 
     Router::new()
         .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
+        .route("/.well-known/jwks.json", axum::routing::get(crate::modules::auth::handler::jwks))
         .nest("/api/v1", api_routes())
         .nest("/api/v1/auth", crate::modules::auth::router(state.clone()))
+        .nest("/api/v1/users", crate::modules::user::router(state.clone()))
+        .nest("/api/v1/profiles", crate::modules::profile::router(state.clone()))
+        .nest("/api/v1/api-keys", crate::modules::api_key::router(state.clone()))
+        .nest("/api/v1/roles", crate::modules::role::router(state.clone()))
+        .nest("/api/v1/audit-logs", crate::modules::audit::router(state.clone()))
         .nest("/api/v1/genres", crate::modules::genre::router(state.clone()))
+        .nest("/api/v1/maturity-ratings", crate::modules::rating::router(state.clone()))
+        .nest("/api/v1/search", crate::modules::search::router())
         .nest("/api/v1", crate::modules::content::router(state))
         .layer(cors)
+        .layer(middleware::from_fn(crate::middleware::request_id::request_id))
 }
 
 fn api_routes() -> Router<AppState> {
@@ -1,8 +1,13 @@
 use crate::config::settings::AppConfig;
+use crate::common::jwt::JwtKeys;
+use crate::common::password::Passwords;
+use crate::infrastructure::mail::sender::MailSender;
+use crate::infrastructure::oidc::client::OidcClient;
 use crate::infrastructure::db::pool::DbPool;
 use crate::infrastructure::redis::client::RedisService;
 use crate::infrastructure::storage::s3::StorageService;
 use crate::infrastructure::queue::rabbitmq::RabbitMqService;
+use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct AppState {
@@ -11,15 +16,25 @@ pub struct AppState {
     pub redis: RedisService,
     pub storage: StorageService,
     pub queue: RabbitMqService,
+    pub jwt: JwtKeys,
+    pub passwords: Passwords,
+    pub mailer: Arc<dyn MailSender>,
+    /// `None` when no OIDC provider is configured
+    pub oidc: Option<OidcClient>,
 }
 
 impl AppState {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         config: AppConfig,
         db: DbPool,
         redis: RedisService,
         storage: StorageService,
         queue: RabbitMqService,
+        jwt: JwtKeys,
+        passwords: Passwords,
+        mailer: Arc<dyn MailSender>,
+        oidc: Option<OidcClient>,
     ) -> Self {
         Self {
             config,
@@ -27,6 +42,10 @@ impl AppState {
             redis,
             storage,
             queue,
+            jwt,
+            passwords,
+            mailer,
+            oidc,
         }
     }
 }
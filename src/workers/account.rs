@@ -0,0 +1,99 @@
+use crate::modules::jobs::events::AccountTask;
+use crate::modules::jobs::model::ACCOUNT_TASKS_QUEUE;
+use crate::modules::jobs::service::JobService;
+use crate::state::AppState;
+use futures_util::StreamExt;
+use lapin::options::{BasicAckOptions, BasicConsumeOptions, QueueDeclareOptions};
+use lapin::types::FieldTable;
+use tokio::time::{sleep, Duration};
+use tracing::{error, info, warn};
+
+/// Runs data exports and account deletions queued by `JobService`.
+pub async fn start_account_worker(state: AppState) {
+    info!("🗂️ Starting Account Worker...");
+
+    let queue_name = ACCOUNT_TASKS_QUEUE;
+
+    loop {
+        let channel = state.queue.get_channel().await;
+        let channel_guard = channel.lock().await;
+
+        if let Err(e) = channel_guard
+            .queue_declare(
+                queue_name,
+                QueueDeclareOptions {
+                    durable: true,
+                    ..QueueDeclareOptions::default()
+                },
+                FieldTable::default(),
+            )
+            .await
+        {
+            error!("Failed to declare queue '{}': {}", queue_name, e);
+            drop(channel_guard);
+            if let Err(err) = state.queue.reconnect().await {
+                warn!("Failed to reconnect RabbitMQ after declare error: {}", err);
+            }
+            sleep(Duration::from_secs(2)).await;
+            continue;
+        }
+
+        let mut consumer = match channel_guard
+            .basic_consume(
+                queue_name,
+                "account_worker",
+                BasicConsumeOptions::default(),
+                FieldTable::default(),
+            )
+            .await
+        {
+            Ok(consumer) => consumer,
+            Err(e) => {
+                error!("Failed to create consumer: {}", e);
+                drop(channel_guard);
+                if let Err(err) = state.queue.reconnect().await {
+                    warn!("Failed to reconnect RabbitMQ after consume error: {}", err);
+                }
+                sleep(Duration::from_secs(2)).await;
+                continue;
+            }
+        };
+
+        drop(channel_guard);
+
+        info!("🗂️ Account Worker listening on '{}'", queue_name);
+
+        while let Some(delivery) = consumer.next().await {
+            match delivery {
+                Ok(delivery) => {
+                    match serde_json::from_slice::<AccountTask>(&delivery.data) {
+                        Ok(task) => {
+                            if let Err(e) = JobService::process(&state, &task).await {
+                                error!("❌ Account job {} failed: {}", task.job_id, e);
+                            } else {
+                                info!("✅ Account job {} completed", task.job_id);
+                            }
+                        }
+                        Err(e) => {
+                            error!("❌ Failed to parse account task: {}", e);
+                        }
+                    }
+
+                    if let Err(e) = delivery.ack(BasicAckOptions::default()).await {
+                        error!("Failed to ack message: {}", e);
+                    }
+                }
+                Err(e) => {
+                    error!("Account consumer error: {}", e);
+                    break;
+                }
+            }
+        }
+
+        warn!("Account consumer stopped, retrying in 2s...");
+        if let Err(err) = state.queue.reconnect().await {
+            warn!("Failed to reconnect RabbitMQ after consumer stop: {}", err);
+        }
+        sleep(Duration::from_secs(2)).await;
+    }
+}
@@ -1,2 +1,3 @@
 pub mod transcoder;
-// pub mod scheduler; // If exists
+pub mod account;
+pub mod scheduler;
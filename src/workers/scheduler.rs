@@ -0,0 +1,23 @@
+use crate::modules::content::service::ContentService;
+use crate::state::AppState;
+use tokio::time::{interval, Duration, MissedTickBehavior};
+use tracing::{error, info};
+
+/// Publishes and unpublishes titles whose `publish_at` / `unpublish_at` has passed.
+/// Running several instances is safe: each due change is applied by one update.
+pub async fn start_publish_scheduler(state: AppState) {
+    let period = Duration::from_secs(state.config.publish_schedule_interval_secs.max(1));
+    info!("🗓️ Starting Publish Scheduler (every {}s)...", period.as_secs());
+
+    let mut ticker = interval(period);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        ticker.tick().await;
+        match ContentService::apply_visibility_schedules(&state).await {
+            Ok(0) => {}
+            Ok(count) => info!("🗓️ Applied {} scheduled visibility change(s)", count),
+            Err(e) => error!("Failed to apply publishing schedules: {}", e),
+        }
+    }
+}
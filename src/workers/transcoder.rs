@@ -1,5 +1,8 @@
 use crate::infrastructure::storage::s3::StorageService;
 use crate::modules::content::events::TranscodeJob;
+use crate::modules::content::service::ContentService;
+use crate::modules::search::model::{CONTENT_MOVIE, CONTENT_SERIES};
+use crate::modules::search::service::SearchService;
 use crate::state::AppState;
 use bytes::Bytes;
 use futures_util::StreamExt;
@@ -234,6 +237,15 @@ async fn process_job(state: &AppState, job: &TranscodeJob) -> anyhow::Result<()>
         .await
         .map_err(|e| anyhow::anyhow!("DB Error: {}", e))?;
     }
+
+    // The title is playable now, so it can be suggested
+    if job.content_type == "episode" {
+        if let Ok(Some(series_id)) = ContentService::episode_series_id(state, job.content_id).await {
+            SearchService::refresh_suggestion(state, CONTENT_SERIES, series_id).await;
+        }
+    } else {
+        SearchService::refresh_suggestion(state, CONTENT_MOVIE, job.content_id).await;
+    }
     
     // 7. Cleanup
     let _ = fs::remove_file(input_path);